@@ -5,7 +5,9 @@ async fn main() -> Result<(), Error> {
     //yeehaw::log::reset_log_file("./debug_test.log".to_string());
     //std::env::set_var("RUST_BACKTRACE", "1");
 
-    let (mut tui, ctx) = Tui::new()?;
+    // the rows of the tui are cleared on exit leaving only the committed lines
+    let (tui, ctx) = Tui::new()?;
+    let mut tui = tui.with_keep_inline_frame(false);
     let main_el = ParentPane::new(&ctx, "main_element").with_bg(Color::GREY10);
 
     // place the label at 30% of the screen width and height
@@ -16,14 +18,40 @@ async fn main() -> Result<(), Error> {
     let y = DynVal::new_flex(0.3).plus(1.into()); // 30% screen height + 1 ch
 
     let label_ = label.clone(); // clone for closure move
+    let mut clicks = 0;
     let button = Button::new(&ctx, "Click Here!")
-        .with_fn(Box::new(move |_, _| {
+        .with_fn(Box::new(move |_, ctx| {
+            clicks += 1;
             label_.set_text("Button clicked!".to_string());
+            // committed lines remain in the terminal scrollback
+            ctx.inline
+                .print_line(&format!("button clicked {clicks} times"));
             EventResponses::default()
         }))
         .at(x, y);
 
+    // change the height of the tui at runtime
+    let height = Rc::new(RefCell::new(10));
+    let height_ = height.clone();
+    let grow = Button::new(&ctx, "Grow")
+        .with_fn(Box::new(move |_, ctx| {
+            *height_.borrow_mut() += 2;
+            ctx.inline.set_height(*height_.borrow());
+            EventResponses::default()
+        }))
+        .at(1, 0);
+    let shrink = Button::new(&ctx, "Shrink")
+        .with_fn(Box::new(move |_, ctx| {
+            let h = (*height.borrow() - 2).max(4);
+            *height.borrow_mut() = h;
+            ctx.inline.set_height(h);
+            EventResponses::default()
+        }))
+        .at(8, 0);
+
     main_el.add_element(Box::new(label));
     main_el.add_element(Box::new(button));
+    main_el.add_element(Box::new(grow));
+    main_el.add_element(Box::new(shrink));
     tui.run_in_line(Box::new(main_el), 10).await
 }
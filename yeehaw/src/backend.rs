@@ -0,0 +1,511 @@
+use {
+    crate::{ChPlus, Error, HeadlessScreen, Size},
+    crossterm::{
+        cursor,
+        event::{
+            DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+            EnableFocusChange, EnableMouseCapture, KeyboardEnhancementFlags,
+            PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+        },
+        queue,
+        style::{self, StyledContent},
+        terminal,
+    },
+    std::io::{Stdout, Write},
+};
+
+/// Backend is the output target of the Tui. All the drawing and terminal state changes made by
+/// the Tui are made through this trait, so a Tui may be rendered to something other than the
+/// stdout terminal (a file, a socket, a pipe or an alternative terminal library).
+///
+/// Commands may be buffered by the backend, they are only guaranteed to be written once flush is
+/// called.
+pub trait Backend {
+    /// whether this backend is the actual terminal of the process, if so the terminal will be
+    /// restored should the application panic
+    fn is_terminal(&self) -> bool {
+        false
+    }
+
+    /// the size of the output target
+    fn size(&self) -> Result<Size, Error>;
+
+    /// the current cursor position (x, y)
+    fn cursor_position(&mut self) -> Result<(u16, u16), Error>;
+
+    fn move_to(&mut self, x: u16, y: u16) -> Result<(), Error>;
+
+    /// print styled content at the current cursor position
+    fn print_styled(&mut self, content: StyledContent<ChPlus>) -> Result<(), Error>;
+
+    /// print a run of single column chars sharing a style at the current cursor position, the
+    /// cursor is left just after the run
+    fn print_styled_str(&mut self, content: StyledContent<&str>) -> Result<(), Error> {
+        for ch in content.content().chars() {
+            self.print_styled(StyledContent::new(*content.style(), ChPlus::Char(ch)))?;
+        }
+        Ok(())
+    }
+
+    /// have the terminal hold off displaying the output until end_synchronized_update such that
+    /// a frame is displayed all at once (DEC mode 2026), terminals which do not support the mode
+    /// simply ignore it
+    fn begin_synchronized_update(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn end_synchronized_update(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// clear the whole output target
+    fn clear_all(&mut self) -> Result<(), Error>;
+
+    /// clear a single row of the output target, the cursor is left at the start of the row
+    fn clear_row(&mut self, y: u16) -> Result<(), Error> {
+        let width = self.size()?.width as usize;
+        self.move_to(0, y)?;
+        self.print_styled_str(StyledContent::new(Default::default(), &" ".repeat(width)))?;
+        self.move_to(0, y)
+    }
+
+    fn scroll_up(&mut self, rows: u16) -> Result<(), Error>;
+    fn scroll_down(&mut self, rows: u16) -> Result<(), Error>;
+
+    fn show_cursor(&mut self) -> Result<(), Error>;
+    fn hide_cursor(&mut self) -> Result<(), Error>;
+
+    fn reset_color(&mut self) -> Result<(), Error>;
+
+    fn enable_mouse_capture(&mut self) -> Result<(), Error>;
+    fn disable_mouse_capture(&mut self) -> Result<(), Error>;
+
+    fn enter_alternate_screen(&mut self) -> Result<(), Error>;
+    fn leave_alternate_screen(&mut self) -> Result<(), Error>;
+
+    /// have pasted text delivered as a whole (Event::Paste) rather than as key events
+    fn enable_bracketed_paste(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn disable_bracketed_paste(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// have the terminal report when it gains or loses focus (Event::FocusGained/FocusLost)
+    fn enable_focus_change(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn disable_focus_change(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// whether the terminal supports keyboard enhancement (the kitty keyboard protocol)
+    fn supports_keyboard_enhancement(&mut self) -> bool {
+        false
+    }
+    /// have the terminal report key events as per the flags (key releases, repeats and
+    /// disambiguated keys), must be popped before closedown
+    fn push_keyboard_enhancement(&mut self, _flags: KeyboardEnhancementFlags) -> Result<(), Error> {
+        Ok(())
+    }
+    fn pop_keyboard_enhancement(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// set the title of the terminal window
+    fn set_title(&mut self, _title: &str) -> Result<(), Error> {
+        Ok(())
+    }
+    /// save the current title of the terminal window onto the terminal's title stack
+    fn push_title(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    /// restore the title of the terminal window last saved with push_title
+    fn pop_title(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// write a raw escape sequence (such as OSC 52) to the output target, backends which are not
+    /// a terminal may ignore it
+    fn print_raw(&mut self, _seq: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn enable_raw_mode(&mut self) -> Result<(), Error>;
+    fn disable_raw_mode(&mut self) -> Result<(), Error>;
+
+    fn flush(&mut self) -> Result<(), Error>;
+
+    /// prepare the output target for a full screen tui
+    fn startup(&mut self) -> Result<(), Error> {
+        self.hide_cursor()?;
+        self.enter_alternate_screen()?;
+        self.enable_mouse_capture()?;
+        self.enable_bracketed_paste()?;
+        self.enable_focus_change()?;
+        self.flush()?;
+        self.enable_raw_mode()
+    }
+
+    /// restore the output target after a full screen tui
+    fn closedown(&mut self) -> Result<(), Error> {
+        self.reset_color()?;
+        self.show_cursor()?;
+        self.leave_alternate_screen()?;
+        self.disable_mouse_capture()?;
+        self.disable_bracketed_paste()?;
+        self.disable_focus_change()?;
+        self.flush()?;
+        self.disable_raw_mode()
+    }
+
+    /// prepare the output target for an inline tui
+    fn line_startup(&mut self) -> Result<(), Error> {
+        self.hide_cursor()?;
+        self.enable_mouse_capture()?;
+        self.enable_bracketed_paste()?;
+        self.enable_focus_change()?;
+        self.flush()?;
+        self.enable_raw_mode()
+    }
+
+    /// restore the output target after an inline tui, the cursor is left at the provided row
+    fn line_closedown(&mut self, cursor_row: u16) -> Result<(), Error> {
+        self.move_to(0, cursor_row)?;
+        self.reset_color()?;
+        self.show_cursor()?;
+        self.disable_mouse_capture()?;
+        self.disable_bracketed_paste()?;
+        self.disable_focus_change()?;
+        self.flush()?;
+        self.disable_raw_mode()
+    }
+}
+
+/// The default backend which writes crossterm commands to any Write target.
+///
+/// When writing to stdout (`CrosstermBackend::stdout`) the backend is considered to be the
+/// terminal: the size and cursor position are queried from the terminal and raw mode is managed.
+/// For all other Write targets the size must be provided and raw mode is left untouched.
+pub struct CrosstermBackend<W: Write> {
+    out: W,
+
+    /// fixed size of the output target, if None the terminal size is used
+    size: Option<Size>,
+
+    /// whether this backend manages the raw mode of the terminal
+    raw_mode: bool,
+
+    /// the last position the cursor was moved to, used when the cursor position cannot be
+    /// queried from the terminal
+    last_cursor: (u16, u16),
+}
+
+impl CrosstermBackend<Stdout> {
+    /// a backend which writes to the stdout terminal
+    pub fn stdout() -> Self {
+        CrosstermBackend {
+            out: std::io::stdout(),
+            size: None,
+            raw_mode: true,
+            last_cursor: (0, 0),
+        }
+    }
+}
+
+impl Default for CrosstermBackend<Stdout> {
+    fn default() -> Self {
+        Self::stdout()
+    }
+}
+
+impl<W: Write> CrosstermBackend<W> {
+    /// a backend which writes to an arbitrary Write target of a fixed size
+    pub fn new(out: W, size: Size) -> Self {
+        CrosstermBackend {
+            out,
+            size: Some(size),
+            raw_mode: false,
+            last_cursor: (0, 0),
+        }
+    }
+
+    pub fn with_size(mut self, size: Size) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn set_size(&mut self, size: Size) {
+        self.size = Some(size);
+    }
+
+    /// whether or not this backend should enable/disable the terminal raw mode
+    pub fn with_raw_mode(mut self, raw_mode: bool) -> Self {
+        self.raw_mode = raw_mode;
+        self
+    }
+
+    pub fn writer(&self) -> &W {
+        &self.out
+    }
+
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.out
+    }
+
+    pub fn into_writer(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> Backend for CrosstermBackend<W> {
+    fn is_terminal(&self) -> bool {
+        self.raw_mode && self.size.is_none()
+    }
+
+    fn size(&self) -> Result<Size, Error> {
+        if let Some(size) = self.size {
+            return Ok(size);
+        }
+        let (width, height) = terminal::size()?;
+        Ok(Size::new(width, height))
+    }
+
+    fn cursor_position(&mut self) -> Result<(u16, u16), Error> {
+        if self.size.is_some() {
+            return Ok(self.last_cursor);
+        }
+        Ok(cursor::position()?)
+    }
+
+    fn move_to(&mut self, x: u16, y: u16) -> Result<(), Error> {
+        self.last_cursor = (x, y);
+        Ok(queue!(self.out, cursor::MoveTo(x, y))?)
+    }
+
+    fn print_styled(&mut self, content: StyledContent<ChPlus>) -> Result<(), Error> {
+        Ok(queue!(self.out, style::PrintStyledContent(content))?)
+    }
+
+    fn print_styled_str(&mut self, content: StyledContent<&str>) -> Result<(), Error> {
+        Ok(queue!(self.out, style::PrintStyledContent(content))?)
+    }
+
+    fn begin_synchronized_update(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, terminal::BeginSynchronizedUpdate)?)
+    }
+
+    fn end_synchronized_update(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, terminal::EndSynchronizedUpdate)?)
+    }
+
+    fn clear_all(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, terminal::Clear(terminal::ClearType::All))?)
+    }
+
+    fn clear_row(&mut self, y: u16) -> Result<(), Error> {
+        self.move_to(0, y)?;
+        Ok(queue!(
+            self.out,
+            terminal::Clear(terminal::ClearType::CurrentLine)
+        )?)
+    }
+
+    fn scroll_up(&mut self, rows: u16) -> Result<(), Error> {
+        Ok(queue!(self.out, terminal::ScrollUp(rows))?)
+    }
+
+    fn scroll_down(&mut self, rows: u16) -> Result<(), Error> {
+        Ok(queue!(self.out, terminal::ScrollDown(rows))?)
+    }
+
+    fn show_cursor(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, cursor::Show)?)
+    }
+
+    fn hide_cursor(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, cursor::Hide)?)
+    }
+
+    fn reset_color(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, style::ResetColor)?)
+    }
+
+    fn enable_mouse_capture(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, EnableMouseCapture)?)
+    }
+
+    fn disable_mouse_capture(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, DisableMouseCapture)?)
+    }
+
+    fn enter_alternate_screen(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, terminal::EnterAlternateScreen)?)
+    }
+
+    fn leave_alternate_screen(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, terminal::LeaveAlternateScreen)?)
+    }
+
+    fn enable_bracketed_paste(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, EnableBracketedPaste)?)
+    }
+
+    fn disable_bracketed_paste(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, DisableBracketedPaste)?)
+    }
+
+    fn enable_focus_change(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, EnableFocusChange)?)
+    }
+
+    fn disable_focus_change(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, DisableFocusChange)?)
+    }
+
+    fn supports_keyboard_enhancement(&mut self) -> bool {
+        self.is_terminal() && terminal::supports_keyboard_enhancement().unwrap_or(false)
+    }
+
+    fn push_keyboard_enhancement(&mut self, flags: KeyboardEnhancementFlags) -> Result<(), Error> {
+        Ok(queue!(self.out, PushKeyboardEnhancementFlags(flags))?)
+    }
+
+    fn pop_keyboard_enhancement(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, PopKeyboardEnhancementFlags)?)
+    }
+
+    /// OSC 0 (`\e]0;title\a`)
+    fn set_title(&mut self, title: &str) -> Result<(), Error> {
+        Ok(queue!(self.out, terminal::SetTitle(title))?)
+    }
+
+    /// XTWINOPS 22, unsupported terminals simply ignore it
+    fn push_title(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, style::Print("\x1b[22;0t"))?)
+    }
+
+    /// XTWINOPS 23
+    fn pop_title(&mut self) -> Result<(), Error> {
+        Ok(queue!(self.out, style::Print("\x1b[23;0t"))?)
+    }
+
+    fn print_raw(&mut self, seq: &str) -> Result<(), Error> {
+        Ok(queue!(self.out, style::Print(seq))?)
+    }
+
+    fn enable_raw_mode(&mut self) -> Result<(), Error> {
+        if self.raw_mode {
+            terminal::enable_raw_mode()?;
+        }
+        Ok(())
+    }
+
+    fn disable_raw_mode(&mut self) -> Result<(), Error> {
+        if self.raw_mode {
+            terminal::disable_raw_mode()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        Ok(self.out.flush()?)
+    }
+}
+
+/// The headless screen acts as a backend which simply records the printed cells and emulates
+/// scrolling, all other terminal state changes are ignored. The drawing output is also measured
+/// as the number of bytes the CrosstermBackend would have written for it (see
+/// HeadlessScreen::bytes_written).
+impl Backend for HeadlessScreen {
+    fn size(&self) -> Result<Size, Error> {
+        Ok(HeadlessScreen::size(self))
+    }
+
+    fn cursor_position(&mut self) -> Result<(u16, u16), Error> {
+        Ok(self.cursor)
+    }
+
+    fn move_to(&mut self, x: u16, y: u16) -> Result<(), Error> {
+        self.count_bytes(cursor::MoveTo(x, y));
+        self.cursor = (x, y);
+        Ok(())
+    }
+
+    fn print_styled(&mut self, content: StyledContent<ChPlus>) -> Result<(), Error> {
+        self.count_bytes(style::PrintStyledContent(content.clone()));
+        let (x, y) = self.cursor;
+        self.set_cell(x as usize, y as usize, content);
+        self.cursor.0 = x.saturating_add(1);
+        Ok(())
+    }
+
+    fn print_styled_str(&mut self, content: StyledContent<&str>) -> Result<(), Error> {
+        self.count_bytes(style::PrintStyledContent(content));
+        for ch in content.content().chars() {
+            let (x, y) = self.cursor;
+            self.set_cell(
+                x as usize,
+                y as usize,
+                StyledContent::new(*content.style(), ChPlus::Char(ch)),
+            );
+            self.cursor.0 = x.saturating_add(1);
+        }
+        Ok(())
+    }
+
+    fn begin_synchronized_update(&mut self) -> Result<(), Error> {
+        self.count_bytes(terminal::BeginSynchronizedUpdate);
+        Ok(())
+    }
+
+    fn end_synchronized_update(&mut self) -> Result<(), Error> {
+        self.count_bytes(terminal::EndSynchronizedUpdate);
+        Ok(())
+    }
+
+    fn clear_all(&mut self) -> Result<(), Error> {
+        self.clear();
+        Ok(())
+    }
+
+    fn scroll_up(&mut self, rows: u16) -> Result<(), Error> {
+        self.count_bytes(terminal::ScrollUp(rows));
+        HeadlessScreen::scroll_up(self, rows as usize);
+        Ok(())
+    }
+    fn scroll_down(&mut self, rows: u16) -> Result<(), Error> {
+        self.count_bytes(terminal::ScrollDown(rows));
+        HeadlessScreen::scroll_down(self, rows as usize);
+        Ok(())
+    }
+    fn show_cursor(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn hide_cursor(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn reset_color(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn enable_mouse_capture(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn disable_mouse_capture(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn enter_alternate_screen(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn leave_alternate_screen(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn enable_raw_mode(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn disable_raw_mode(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn flush(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
@@ -0,0 +1,285 @@
+use {
+    crate::{Backend, Error},
+    std::io::IsTerminal,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+/// A system (or not so system) clipboard which text may be copied to and pasted from.
+pub trait ClipboardProvider {
+    /// a short name for the kind of clipboard ("arboard", "osc52", "in-process")
+    fn name(&self) -> &'static str;
+    fn set_text(&mut self, text: &str) -> Result<(), Error>;
+    fn get_text(&mut self) -> Result<String, Error>;
+
+    /// take the escape sequences which are yet to be written to the terminal, these are written
+    /// through the backend of the tui
+    fn take_output(&mut self) -> String {
+        String::new()
+    }
+}
+
+/// The native system clipboard through the arboard crate. This is unavailable over ssh and in
+/// containers without a display server.
+#[cfg(feature = "textbox")]
+pub struct ArboardClipboard {
+    // NOTE the clipboard is kept alive as on some platforms the copied text is lost once the
+    // clipboard which set it is dropped
+    cb: arboard::Clipboard,
+}
+
+#[cfg(feature = "textbox")]
+impl ArboardClipboard {
+    pub fn new() -> Result<Self, Error> {
+        Ok(ArboardClipboard {
+            cb: arboard::Clipboard::new()?,
+        })
+    }
+}
+
+#[cfg(feature = "textbox")]
+impl ClipboardProvider for ArboardClipboard {
+    fn name(&self) -> &'static str {
+        "arboard"
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), Error> {
+        Ok(self.cb.set_text(text)?)
+    }
+
+    fn get_text(&mut self) -> Result<String, Error> {
+        Ok(self.cb.get_text()?)
+    }
+}
+
+/// Copies text by writing an OSC 52 escape sequence through the terminal, which then sets the
+/// clipboard of the machine the terminal is running on (this works over ssh). The sequences are
+/// queued and written through the backend of the tui on the next render. Reading the clipboard
+/// through the terminal is rarely permitted, so pasting returns the text last copied through this
+/// clipboard.
+pub struct Osc52Clipboard {
+    /// sequences not yet written to the terminal
+    pending: String,
+    /// wrap the sequence in a tmux passthrough
+    pub tmux: bool,
+    last: String,
+}
+
+impl Default for Osc52Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Osc52Clipboard {
+    pub fn new() -> Self {
+        Osc52Clipboard {
+            pending: String::new(),
+            tmux: std::env::var_os("TMUX").is_some(),
+            last: String::new(),
+        }
+    }
+
+    /// the escape sequence which sets the clipboard to the text
+    pub fn sequence(&self, text: &str) -> String {
+        let seq = format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()));
+        if self.tmux {
+            // escapes within the passthrough are doubled
+            format!("\x1bPtmux;{}\x1b\\", seq.replace('\x1b', "\x1b\x1b"))
+        } else {
+            seq
+        }
+    }
+}
+
+impl ClipboardProvider for Osc52Clipboard {
+    fn name(&self) -> &'static str {
+        "osc52"
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), Error> {
+        let seq = self.sequence(text);
+        self.pending.push_str(&seq);
+        self.last = text.to_string();
+        Ok(())
+    }
+
+    fn get_text(&mut self) -> Result<String, Error> {
+        Ok(self.last.clone())
+    }
+
+    fn take_output(&mut self) -> String {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// A clipboard which only lives within this process, used when no other clipboard is available
+/// and by headless tuis.
+#[derive(Default)]
+pub struct InProcessClipboard {
+    text: String,
+}
+
+impl ClipboardProvider for InProcessClipboard {
+    fn name(&self) -> &'static str {
+        "in-process"
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), Error> {
+        self.text = text.to_string();
+        Ok(())
+    }
+
+    fn get_text(&mut self) -> Result<String, Error> {
+        Ok(self.text.clone())
+    }
+}
+
+/// The Clipboard is carried in the Context and shared between all clones. Unless a provider is
+/// set one is picked automatically when the clipboard is first used (see `Clipboard::auto`).
+#[derive(Clone, Default)]
+pub struct Clipboard {
+    provider: Rc<RefCell<Option<Box<dyn ClipboardProvider>>>>,
+    /// text has been set since the backend was last updated
+    dirty: Rc<Cell<bool>>,
+}
+
+impl std::fmt::Debug for Clipboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self.provider.borrow().as_ref().map(|p| p.name());
+        f.debug_struct("Clipboard")
+            .field("provider", &name)
+            .finish()
+    }
+}
+
+impl Clipboard {
+    pub fn new(provider: Box<dyn ClipboardProvider>) -> Self {
+        Clipboard {
+            provider: Rc::new(RefCell::new(Some(provider))),
+            dirty: Rc::new(Cell::new(false)),
+        }
+    }
+
+    /// Pick the most appropriate clipboard: over ssh the terminal's clipboard (OSC 52), otherwise
+    /// the system clipboard should it be available (textbox feature), falling back to the
+    /// terminal's clipboard when running in a terminal and finally an in-process clipboard.
+    pub fn auto() -> Box<dyn ClipboardProvider> {
+        let over_ssh =
+            std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some();
+        #[cfg(feature = "textbox")]
+        if !over_ssh {
+            match ArboardClipboard::new() {
+                Ok(cb) => return Box::new(cb),
+                Err(e) => debug!("system clipboard unavailable: {}", e),
+            }
+        }
+        if over_ssh || std::io::stdout().is_terminal() {
+            return Box::new(Osc52Clipboard::default());
+        }
+        Box::new(InProcessClipboard::default())
+    }
+
+    pub fn with_provider(self, provider: Box<dyn ClipboardProvider>) -> Self {
+        self.set_provider(provider);
+        self
+    }
+
+    /// replace the clipboard provider for all clones of this clipboard
+    pub fn set_provider(&self, provider: Box<dyn ClipboardProvider>) {
+        *self.provider.borrow_mut() = Some(provider);
+    }
+
+    /// the name of the provider in use, picking one if none has been yet
+    pub fn provider_name(&self) -> &'static str {
+        self.with(|p| p.name())
+    }
+
+    pub fn set_text(&self, text: &str) -> Result<(), Error> {
+        self.dirty.set(true);
+        self.with(|p| p.set_text(text))
+    }
+
+    pub fn get_text(&self) -> Result<String, Error> {
+        self.with(|p| p.get_text())
+    }
+
+    /// whether the backend has output from the provider yet to be written
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.get()
+    }
+
+    /// write the output of the provider (such as OSC 52 sequences) to the backend. Returns whether
+    /// anything was written.
+    pub fn update_backend(&self, backend: &mut dyn Backend) -> Result<bool, Error> {
+        if !self.dirty.replace(false) {
+            return Ok(false);
+        }
+        let out = match self.provider.borrow_mut().as_mut() {
+            Some(p) => p.take_output(),
+            None => return Ok(false),
+        };
+        if out.is_empty() {
+            return Ok(false);
+        }
+        backend.print_raw(&out)?;
+        Ok(true)
+    }
+
+    fn with<T>(&self, f: impl FnOnce(&mut dyn ClipboardProvider) -> T) -> T {
+        let mut provider = self.provider.borrow_mut();
+        let provider = provider.get_or_insert_with(Self::auto);
+        f(provider.as_mut())
+    }
+}
+
+/// standard base64 encoding (with padding)
+fn base64_encode(bz: &[u8]) -> String {
+    const CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bz.len().div_ceil(3) * 4);
+    for chunk in bz.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(CHARS[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_osc52() {
+        let mut cb = Osc52Clipboard::new();
+        cb.tmux = false;
+        assert_eq!(cb.sequence("hi"), "\x1b]52;c;aGk=\x07");
+        cb.tmux = true;
+        assert_eq!(cb.sequence("hi"), "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\");
+        cb.set_text("hi").unwrap();
+        assert_eq!(cb.get_text().unwrap(), "hi");
+        assert_eq!(cb.take_output(), cb.sequence("hi"));
+        assert_eq!(cb.take_output(), "");
+    }
+}
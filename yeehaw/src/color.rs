@@ -1,5 +1,5 @@
 use {
-    crate::{Context, DynVal, Size},
+    crate::{Context, DynVal, Error, Size},
     crossterm::style::Color as CrosstermColor,
     std::time::Duration,
     std::{cell::RefCell, rc::Rc},
@@ -1653,12 +1653,14 @@ impl Color {
     pub const WHITE_SMOKE:             Color = Color::new(245, 245, 245);
     pub const WHITE:                   Color = Color::new(255, 255, 255);
 
-    pub fn from_name(name: &str) -> Color {
+    /// the named color (case insensitive, spaces or underscores between words), errors on unknown
+    /// names
+    pub fn from_name(name: &str) -> Result<Color, Error> {
         // normalize the name
         let name = name.to_lowercase();
         let name = name.replace(' ', "_");
 
-        match name.as_str() {
+        let c = match name.as_str() {
             "maroon"                  => Self::MAROON,
             "dark_red"                => Self::DARK_RED,
             "brown"                   => Self::BROWN,
@@ -1803,9 +1805,9 @@ impl Color {
             "gainsboro"               => Self::GAINSBORO,
             "white_smoke"             => Self::WHITE_SMOKE,
             "white"                   => Self::WHITE,
-            _                         => Self::WHITE,
-        }
-
+            _ => return Err(Error::new(&format!("unknown color name: {name}"))),
+        };
+        Ok(c)
     }
 
 }
@@ -1,7 +1,10 @@
 use {
     // crate::ColorContext,
-    crate::{ColorStore, Event, SortingHat},
-    std::collections::HashMap,
+    crate::{
+        Clipboard, ColorStore, Element, Error, Event, EventResponse, InlineControl, KeyMap, Pane,
+        Profiler, SortingHat, SpawnedTask, TaskHandle, Tasks, TerminalTitle, Theme, ThemeEntries,
+    },
+    std::{collections::HashMap, future::Future},
     tokio::sync::mpsc::Sender,
 };
 
@@ -20,11 +23,30 @@ pub struct Context {
     pub hat: SortingHat,
     pub ev_tx: Sender<Event>,
     pub color_store: ColorStore,
+    pub theme: Theme,
+    /// user overrides of the key combos of widget actions
+    pub keymap: KeyMap,
+    /// the clipboard used to copy and paste text
+    pub clipboard: Clipboard,
+    /// the keys typed so far of a multi-key combo which is waiting to be completed, this may be
+    /// displayed to the user (like vim's showcmd)
+    pub pending_keys: Vec<crossterm::event::KeyEvent>,
+    /// the title of the host terminal, changes are written to the terminal by the tui
+    pub terminal_title: TerminalTitle,
+    /// the changes to an inline tui (its height and the lines committed above it), applied by
+    /// the tui
+    pub inline: InlineControl,
+    /// the async tasks spawned by elements (see Context::spawn)
+    pub tasks: Tasks,
+    /// the draw and event times of the elements, collected only when enabled
+    pub profiler: Profiler,
 }
 
 impl Context {
+    /// a context whose time since launch is zero, see new_context
     pub fn new_context_no_dur(
-        hat: &SortingHat, ev_tx: Sender<Event>, color_store: &ColorStore,
+        hat: &SortingHat, ev_tx: Sender<Event>, color_store: &ColorStore, theme: &Theme,
+        keymap: &KeyMap, clipboard: &Clipboard,
     ) -> Context {
         Context {
             dur_since_launch: std::time::Duration::default(),
@@ -32,12 +54,24 @@ impl Context {
             hat: hat.clone(),
             ev_tx,
             color_store: color_store.clone(),
+            theme: theme.clone(),
+            keymap: keymap.clone(),
+            clipboard: clipboard.clone(),
+            pending_keys: Vec::new(),
+            terminal_title: TerminalTitle::default(),
+            inline: InlineControl::default(),
+            tasks: Tasks::default(),
+            profiler: Profiler::default(),
         }
     }
 
+    /// NOTE the terminal title, inline control, tasks and profiler of the new context are not
+    /// connected to any tui: spawned tasks never deliver their outputs and title or inline changes
+    /// are never applied. Only the contexts provided by the tui (Tui::new, Tui::context) are
+    /// connected.
     pub fn new_context(
         launch_instant: std::time::Instant, hat: &SortingHat, ev_tx: Sender<Event>,
-        color_store: &ColorStore,
+        color_store: &ColorStore, theme: &Theme, keymap: &KeyMap, clipboard: &Clipboard,
     ) -> Context {
         Context {
             dur_since_launch: launch_instant.elapsed(),
@@ -45,9 +79,55 @@ impl Context {
             hat: hat.clone(),
             ev_tx,
             color_store: color_store.clone(),
+            theme: theme.clone(),
+            keymap: keymap.clone(),
+            clipboard: clipboard.clone(),
+            pending_keys: Vec::new(),
+            terminal_title: TerminalTitle::default(),
+            inline: InlineControl::default(),
+            tasks: Tasks::default(),
+            profiler: Profiler::default(),
         }
     }
 
+    /// use a different theme for elements created with this context, typically a child of the
+    /// current theme (`ctx.theme.child()`) to override the styles of a subtree
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Replace the entries of the current theme. The tui renders again and elements restyle
+    /// themselves when next drawn (see ThemedStyles).
+    ///
+    /// NOTE only changes to the theme of the tui itself are noticed by the tui, the elements of a
+    /// subtree with a child theme restyle when they are next drawn.
+    pub fn set_theme(&self, entries: ThemeEntries) {
+        self.theme.set_entries(entries);
+    }
+
+    /// Spawn a future on the tokio runtime of the tui without blocking the draw thread. Once the
+    /// future completes its output is delivered to the element of the provided pane as an
+    /// Event::TaskDone (taken out with `TaskOutput::take`) on the next iteration of the event
+    /// loop. The task is cancelled once the element receives Event::Exit or is removed, or
+    /// through the returned handle.
+    ///
+    /// NOTE the path to the element is recorded as the task is spawned, the element must already
+    /// be part of the element tree otherwise its output is never delivered.
+    pub fn spawn<F>(&self, pane: &Pane, fut: F) -> Result<TaskHandle, Error>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let handle = self.tasks.spawn(self.ev_tx.clone(), &pane.id(), fut)?;
+        let st = SpawnedTask {
+            task: handle.id(),
+            path: Vec::new(),
+        };
+        pane.send_responses_upward(self, EventResponse::TaskSpawned(st).into());
+        Ok(handle)
+    }
+
     pub fn with_metadata(mut self, key: String, md: Vec<u8>) -> Self {
         self.metadata.insert(key, md);
         self
@@ -4,7 +4,7 @@ use crate::{DrawRegion, DynVal, MouseEvent};
 /// The higher the z-index, further "on top" the element is.
 pub type ZIndex = i32;
 
-#[derive(Default, Debug, Clone, PartialEq)]
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, Clone, PartialEq)]
 pub struct DynLocation {
     /// inclusive
     pub start_x: DynVal,
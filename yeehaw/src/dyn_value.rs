@@ -64,6 +64,89 @@ impl From<f64> for DynVal {
     }
 }
 
+/// parses a sum of fixed values and percentages of the parent size (flex), for instance "3", "50%"
+/// or "100% - 2"
+impl std::str::FromStr for DynVal {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || crate::Error::new(&format!("invalid dynamic value: {s:?}"));
+        let s_ = s.replace(' ', "");
+        if s_.is_empty() {
+            return Err(err());
+        }
+        // split into terms keeping the sign of each term
+        let mut terms = Vec::new();
+        let mut start = 0;
+        for (i, c) in s_.char_indices() {
+            if (c == '+' || c == '-') && i > start {
+                terms.push(&s_[start..i]);
+                start = i;
+            }
+        }
+        terms.push(&s_[start..]);
+
+        let mut out = DynVal::default();
+        for term in terms {
+            let term = term.strip_prefix('+').unwrap_or(term);
+            if let Some(pct) = term.strip_suffix('%') {
+                out.flex += pct.parse::<f64>().map_err(|_| err())? / 100.;
+            } else {
+                out.fixed += term.parse::<i32>().map_err(|_| err())?;
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// written in the form parsed by from_str where possible, for instance "100% - 2"
+impl std::fmt::Display for DynVal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |vs: &[DynVal]| {
+            vs.iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut terms = Vec::new();
+        if self.flex != 0. {
+            terms.push(format!("{}%", self.flex * 100.));
+        }
+        if self.fixed != 0 {
+            terms.push(self.fixed.to_string());
+        }
+        terms.extend(self.plus.iter().map(|v| v.to_string()));
+        if !self.plus_min_of.is_empty() {
+            terms.push(format!("min({})", join(&self.plus_min_of)));
+        }
+        if !self.plus_max_of.is_empty() {
+            terms.push(format!("max({})", join(&self.plus_max_of)));
+        }
+
+        let mut s = String::new();
+        for (i, term) in terms.iter().enumerate() {
+            match term.strip_prefix('-') {
+                Some(t) if i > 0 => s += &format!(" - {t}"),
+                _ if i > 0 => s += &format!(" + {term}"),
+                _ => s += term,
+            }
+        }
+        if s.is_empty() {
+            s = "0".to_string();
+        }
+        if self.mul == 1. {
+            write!(f, "{s}")
+        } else if self.mul == -1. && terms.len() <= 1 {
+            match s.strip_prefix('-') {
+                Some(s) => write!(f, "{s}"),
+                None => write!(f, "-{s}"),
+            }
+        } else {
+            write!(f, "({s}) * {}", self.mul)
+        }
+    }
+}
+
 impl DynVal {
     pub const FULL: DynVal = DynVal {
         mul: 1.0,
@@ -372,4 +455,14 @@ pub mod dyn_val_tests {
         assert_eq!(9, sv.get_val(10));
         assert_eq!(19, sv.get_val(20));
     }
+
+    #[test]
+    fn test_dyn_val_display() {
+        assert_eq!(DynVal::new_fixed(3).to_string(), "3");
+        assert_eq!(DynVal::default().to_string(), "0");
+        assert_eq!(DynVal::FULL.minus_fixed(2).to_string(), "100% - 2");
+        let sv: DynVal = "50% + 3".parse().unwrap();
+        assert_eq!(sv.to_string(), "50% + 3");
+        assert_eq!(DynVal::new_flex(0.5).mul(2.).to_string(), "(50%) * 2");
+    }
 }
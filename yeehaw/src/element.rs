@@ -1,7 +1,8 @@
 use {
     crate::{
         Context, DrawChPos, DrawRegion, DynLocation, DynLocationSet, ElementID, Event,
-        EventResponses, Label, ReceivableEvents, ZIndex,
+        EventResponses, InspectNode, KeyBindings, KeyPossibility, Label, MouseEvent,
+        ReceivableEvents, SessionState, ZIndex,
     },
     dyn_clone::DynClone,
     std::{
@@ -45,6 +46,14 @@ pub trait Element: DynClone {
     /// TODO it'd be nicer to return an iterator here, bit of a pain to make Element clonable then though.
     fn receivable(&self) -> Vec<Rc<RefCell<ReceivableEvents>>>;
 
+    /// Whether the element accepts the provided mouse event. The element organizer only sends a
+    /// mouse event to the elements under the mouse which accept it (in z-order), elements which
+    /// do not accept it receive it as an ExternalMouse event. By default all mouse events are
+    /// accepted.
+    fn accepts_mouse(&self, _ev: &Event) -> bool {
+        true
+    }
+
     /// Receive an event from a parent. The receiving element may consume the event and/or pass it
     /// to a child. The element is expected to return a response to the event, along with any
     /// changes receivable events. When the event is captured, the element is expected to returns
@@ -68,6 +77,11 @@ pub trait Element: DynClone {
     ///
     /// Current attributes used within this library:
     ///  - "description": a string description of the element used everywhere
+    ///  - "key_descriptions": descriptions of the key combos of the element (see
+    ///    set_key_description)
+    ///  - "tooltip": the text displayed when the mouse rests over the element (see set_tooltip)
+    ///  - "state_name": the name under which the state of the element is saved (see
+    ///    set_state_name)
     fn get_attribute(&self, key: &str) -> Option<Vec<u8>>;
 
     fn set_attribute(&self, key: &str, value: Vec<u8>) {
@@ -80,6 +94,62 @@ pub trait Element: DynClone {
 
     fn set_attribute_inner(&self, key: &str, value: Vec<u8>);
 
+    /// attach a human readable description to one of the receivable key combos of this element,
+    /// the description is displayed within the key help (see WhichKey)
+    fn set_key_description(&self, combo: &[KeyPossibility], desc: &str) {
+        let mut descs = KeyBindings::descriptions_of(self);
+        descs.insert(KeyPossibility::combo_to_string(combo), desc.to_string());
+        match serde_json::to_vec(&descs) {
+            Ok(bz) => self.set_attribute(KeyBindings::ATTR_KEY_DESCRIPTIONS, bz),
+            Err(e) => log_err!("failed to serialize key descriptions: {}", e),
+        }
+    }
+
+    /// the receivable key combos of this element and of its descendants grouped by the element
+    /// which receives them, as with `receivable` only the combos of focused elements are included
+    fn key_bindings(&self) -> Vec<KeyBindings> {
+        KeyBindings::from_receivable(self, self.receivable())
+            .into_iter()
+            .collect()
+    }
+
+    /// The tooltip of the element under the mouse event (which is relative to this element).
+    /// Elements with children should return the tooltip of the child under the mouse should it
+    /// have one, otherwise their own.
+    fn tooltip_at(&self, _ev: &MouseEvent) -> Option<String> {
+        self.get_tooltip()
+    }
+
+    /// the inspection of this element and of its descendants (see the Inspector) provided its
+    /// draw region and its position (x, y) on the screen. Elements with children should include
+    /// the inspections of their children.
+    fn inspect(&self, dr: &DrawRegion, x: i32, y: i32) -> InspectNode {
+        InspectNode::new(self, dr, x, y)
+    }
+
+    /// add the state of this element and of its descendants to the session state, only elements
+    /// given a state name (see with_state_name) are included. By default the location of the
+    /// element is saved, elements with further state (or with children) should extend this.
+    fn save_state(&self, state: &mut SessionState) {
+        if let Some(name) = self.get_state_name() {
+            state.set(
+                &name,
+                SessionState::KEY_LOCATION,
+                &self.get_dyn_location_set().l,
+            );
+        }
+    }
+
+    /// restore the state of this element and of its descendants from the session state
+    fn restore_state(&self, state: &SessionState) {
+        let Some(name) = self.get_state_name() else {
+            return;
+        };
+        if let Some(l) = state.get(&name, SessionState::KEY_LOCATION) {
+            self.set_dyn_location(l);
+        }
+    }
+
     /// sets the hook for the element, the hook is a function that is called when the element is
     /// although a developer may implement any custom hook kind, the default hooks are:
     ///  - "pre-visible-change": called before the element visibility changes
@@ -184,6 +254,43 @@ pub trait Element: DynClone {
         self.set_attribute(ATTR_DESCRIPTION, bz)
     }
 
+    fn with_tooltip(self, tooltip: &str) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_tooltip(tooltip);
+        self
+    }
+
+    /// the tooltip is stored as utf-8 text, equivalent to setting the "tooltip" attribute
+    fn get_tooltip(&self) -> Option<String> {
+        let bz = self.get_attribute(ATTR_TOOLTIP)?;
+        String::from_utf8(bz).ok().filter(|t| !t.is_empty())
+    }
+
+    fn set_tooltip(&self, tooltip: &str) {
+        self.set_attribute(ATTR_TOOLTIP, tooltip.as_bytes().to_vec())
+    }
+
+    fn with_state_name(self, name: &str) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_state_name(name);
+        self
+    }
+
+    /// the stable name under which the state of the element is saved (see SessionState), stored
+    /// as utf-8 text in the "state_name" attribute
+    fn get_state_name(&self) -> Option<String> {
+        let bz = self.get_attribute(ATTR_STATE_NAME)?;
+        String::from_utf8(bz).ok().filter(|n| !n.is_empty())
+    }
+
+    fn set_state_name(&self, name: &str) {
+        self.set_attribute(ATTR_STATE_NAME, name.as_bytes().to_vec())
+    }
+
     /// create a label for this element
     fn label(&self, ctx: &Context, label: &str) -> Label {
         Label::new_for_el(ctx, self.get_dyn_location_set().l.clone(), label)
@@ -229,6 +336,8 @@ pub trait Element: DynClone {
 pub type HookFn = Box<dyn FnMut(&str, Box<dyn Element>)>;
 
 pub const ATTR_DESCRIPTION: &str = "standard_pane";
+pub const ATTR_TOOLTIP: &str = "tooltip";
+pub const ATTR_STATE_NAME: &str = "state_name";
 
 pub const PRE_VISIBLE_CHANGE_HOOK_NAME: &str = "pre-visible-change";
 pub const POST_VISIBLE_CHANGE_HOOK_NAME: &str = "post-visible-change";
@@ -283,6 +392,15 @@ pub trait Parent: DynClone {
     fn get_parent_focused(&self) -> bool;
 
     fn get_id(&self) -> ElementID;
+
+    /// DO NOT CALL THIS FUNCTION DIRECTLY, use send_dirty_upward found in Pane and ParentPane
+    ///
+    /// Signals that a child element has changed outside of event processing and that the tui must
+    /// re-render. Parents pass the signal on to their own parent until it reaches the tui which
+    /// otherwise sleeps while nothing has changed. The default does nothing, parents which
+    /// themselves have a parent should pass the signal on (as ParentPane does) otherwise changes
+    /// made outside of event processing only appear once something else triggers a render.
+    fn propagate_dirty_upward(&self) {}
 }
 
 // -----------------------------------------------------
@@ -0,0 +1,148 @@
+use {
+    crate::*,
+    std::{
+        path::{Path, PathBuf},
+        time::{Duration, SystemTime},
+    },
+};
+
+/// called each time the layout is (re)built, used to attach callbacks to the named elements
+pub type LayoutAttachFn = Box<dyn FnMut(&Context, &Layout)>;
+
+/// LayoutPane displays the element tree of a layout file (see LayoutNode). With hot reload
+/// enabled the file is checked for changes at an interval and the tree is rebuilt when it changes,
+/// the state of the named elements (see SessionState) is carried over to the rebuilt tree. Should
+/// the changed file be invalid the current tree is kept.
+#[derive(Clone)]
+pub struct LayoutPane {
+    pub pane: ParentPane,
+    pub path: Rc<RefCell<PathBuf>>,
+    pub layout: Rc<RefCell<Layout>>,
+    pub attach_fn: Rc<RefCell<LayoutAttachFn>>,
+    /// if Some, the interval at which the file is checked for changes
+    pub hot_reload: Rc<RefCell<Option<Duration>>>,
+    /// the modification time of the file when it was last loaded
+    modified: Rc<RefCell<Option<SystemTime>>>,
+    reload_handle: Rc<RefCell<Option<ScheduleHandle>>>,
+}
+
+#[yeehaw_derive::impl_pane_basics_from(pane)]
+impl LayoutPane {
+    const KIND: &'static str = "layout_pane";
+    /// the event key of the scheduled event which checks the file for changes
+    const EV_RELOAD: &'static str = "layout_pane_reload";
+
+    pub fn new<P: Into<PathBuf>>(ctx: &Context, path: P) -> Result<Self, Error> {
+        let path = path.into();
+        let modified = Self::modified_time(&path);
+        let layout = LayoutNode::load(&path)?.build(ctx)?;
+        let pane = ParentPane::new(ctx, Self::KIND);
+        pane.add_element(layout.root.element());
+        Ok(Self {
+            pane,
+            path: Rc::new(RefCell::new(path)),
+            layout: Rc::new(RefCell::new(layout)),
+            attach_fn: Rc::new(RefCell::new(Box::new(|_, _| {}))),
+            hot_reload: Rc::new(RefCell::new(None)),
+            modified: Rc::new(RefCell::new(modified)),
+            reload_handle: Rc::new(RefCell::new(None)),
+        })
+    }
+
+    /// the attach function is called immediately as well as after every reload
+    pub fn with_attach_fn(self, ctx: &Context, attach_fn: LayoutAttachFn) -> Self {
+        self.set_attach_fn(ctx, attach_fn);
+        self
+    }
+
+    pub fn set_attach_fn(&self, ctx: &Context, mut attach_fn: LayoutAttachFn) {
+        attach_fn(ctx, &self.layout.borrow());
+        *self.attach_fn.borrow_mut() = attach_fn;
+    }
+
+    /// check the file for changes at the provided interval (intended for development)
+    pub fn with_hot_reload(self, interval: Duration) -> Self {
+        *self.hot_reload.borrow_mut() = Some(interval);
+        self
+    }
+
+    pub fn get_layout(&self) -> Layout {
+        self.layout.borrow().clone()
+    }
+
+    fn modified_time(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    /// rebuild the tree should the file have changed since it was last loaded, returns whether
+    /// the tree was rebuilt
+    pub fn reload_if_modified(&self, ctx: &Context) -> Result<(bool, EventResponses), Error> {
+        let modified = Self::modified_time(&self.path.borrow());
+        if modified == *self.modified.borrow() {
+            return Ok((false, EventResponses::default()));
+        }
+        *self.modified.borrow_mut() = modified;
+        let resps = self.reload(ctx)?;
+        Ok((true, resps))
+    }
+
+    /// rebuild the tree from the file
+    pub fn reload(&self, ctx: &Context) -> Result<EventResponses, Error> {
+        let layout = LayoutNode::load(&*self.path.borrow())?.build(ctx)?;
+        let old_root = self.layout.borrow().root.element();
+        let new_root = layout.root.element();
+
+        let mut state = SessionState::default();
+        old_root.save_state(&mut state);
+        new_root.restore_state(&state);
+        new_root.set_focused(old_root.get_focused());
+
+        self.pane.clear_elements();
+        self.pane.add_element(new_root);
+        *self.layout.borrow_mut() = layout;
+        (self.attach_fn.borrow_mut())(ctx, &self.layout.borrow());
+        Ok(self.pane.eo.initialize(ctx, Box::new(self.pane.clone())))
+    }
+
+    fn schedule_reload(&self) -> EventResponses {
+        let Some(interval) = *self.hot_reload.borrow() else {
+            return EventResponses::default();
+        };
+        if let Some(h) = self.reload_handle.borrow_mut().take() {
+            h.cancel();
+        }
+        let se =
+            ScheduledEvent::heartbeat(interval, Event::Custom(Self::EV_RELOAD.to_string(), vec![]));
+        *self.reload_handle.borrow_mut() = Some(se.handle());
+        se.into()
+    }
+}
+
+#[yeehaw_derive::impl_element_from(pane)]
+impl Element for LayoutPane {
+    fn receive_event(&self, ctx: &Context, ev: Event) -> (bool, EventResponses) {
+        match ev {
+            Event::Initialize => {
+                let (captured, mut resps) = self.pane.receive_event(ctx, ev);
+                resps.extend(self.schedule_reload());
+                (captured, resps)
+            }
+            Event::Custom(ref key, _) if key == Self::EV_RELOAD => {
+                match self.reload_if_modified(ctx) {
+                    Ok((_, resps)) => (true, resps),
+                    Err(e) => {
+                        warn!("keeping the current layout, failed to reload: {e}");
+                        (true, EventResponses::default())
+                    }
+                }
+            }
+            Event::Exit => {
+                if let Some(h) = self.reload_handle.borrow_mut().take() {
+                    h.cancel();
+                }
+                self.pane.receive_event(ctx, ev)
+            }
+            _ => self.pane.receive_event(ctx, ev),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod border;
+pub mod layout_pane;
 pub mod stack;
 
 #[cfg(feature = "table")]
@@ -12,6 +13,7 @@ pub use table::{Table, TableStyle};
 
 pub use {
     border::{Bordered, CornerPos},
+    layout_pane::{LayoutAttachFn, LayoutPane},
     stack::{HorizontalStack, HorizontalStackFocuser, VerticalStack, VerticalStackFocuser},
     tabs::Tabs,
     window::WindowPane,
@@ -261,6 +261,32 @@ impl VerticalStack {
         adjust_els_to_fit_dr_size(dr.get_height(), heights, *self.min_resize_height.borrow());
     }
 
+    /// the fraction of the height of the stack taken up by each element
+    pub fn height_ratios(&self) -> Vec<f64> {
+        let dr = ratio_draw_region(*self.last_size.borrow());
+        let heights = self
+            .els
+            .borrow()
+            .iter()
+            .map(|el| el.get_dyn_location_set().get_height_val(&dr))
+            .collect::<Vec<_>>();
+        ratios(heights)
+    }
+
+    /// set the heights of the elements as fractions of the height of the stack, ignored if the
+    /// number of ratios does not match the number of elements
+    pub fn set_height_ratios(&self, ratios: &[f64]) {
+        if ratios.len() != self.len() {
+            return;
+        }
+        let heights = ratios
+            .iter()
+            .map(|r| DynVal::new_flex(*r))
+            .collect::<Vec<_>>();
+        self.adjust_locations_for_heights(&heights);
+        self.is_dirty.replace(true);
+    }
+
     /// adjust all the locations based on the heights
     pub fn adjust_locations_for_heights(&self, heights: &[DynVal]) {
         let mut y = DynVal::new_fixed(0);
@@ -447,6 +473,32 @@ impl HorizontalStack {
         adjust_els_to_fit_dr_size(dr.get_width(), widths, *self.min_resize_width.borrow());
     }
 
+    /// the fraction of the width of the stack taken up by each element
+    pub fn width_ratios(&self) -> Vec<f64> {
+        let dr = ratio_draw_region(*self.last_size.borrow());
+        let widths = self
+            .els
+            .borrow()
+            .iter()
+            .map(|el| el.get_dyn_location_set().get_width_val(&dr))
+            .collect::<Vec<_>>();
+        ratios(widths)
+    }
+
+    /// set the widths of the elements as fractions of the width of the stack, ignored if the
+    /// number of ratios does not match the number of elements
+    pub fn set_width_ratios(&self, ratios: &[f64]) {
+        if ratios.len() != self.len() {
+            return;
+        }
+        let widths = ratios
+            .iter()
+            .map(|r| DynVal::new_flex(*r))
+            .collect::<Vec<_>>();
+        self.adjust_locations_for_widths(&widths);
+        self.is_dirty.replace(true);
+    }
+
     /// adjust all the locations based on the widths
     pub fn adjust_locations_for_widths(&self, widths: &[DynVal]) {
         let mut x = DynVal::new_fixed(0);
@@ -460,6 +512,26 @@ impl HorizontalStack {
     }
 }
 
+/// the draw region used to determine the ratios of a stack, a large virtual size is used should
+/// the stack not have been drawn yet
+fn ratio_draw_region(last_size: Size) -> DrawRegion {
+    let size = if last_size.width == 0 || last_size.height == 0 {
+        Size::new(1000, 1000)
+    } else {
+        last_size
+    };
+    DrawRegion::default().with_size(size)
+}
+
+/// each of the vals as a fraction of their total
+fn ratios(vals: Vec<usize>) -> Vec<f64> {
+    let total = vals.iter().sum::<usize>();
+    if total == 0 {
+        return vec![0.; vals.len()];
+    }
+    vals.iter().map(|v| *v as f64 / total as f64).collect()
+}
+
 /// incrementally change the flex value of each of the existing element vals (either height or
 /// width), until the total context size is reached. max out at 30 iterations. flex changes are
 /// applied additively evenly to all elements (as opposed to multiplicatively).
@@ -589,6 +661,23 @@ impl Element for VerticalStack {
         self.ensure_normalized_sizes(dr);
         self.pane.drawing(ctx, dr, force_update)
     }
+
+    fn save_state(&self, state: &mut SessionState) {
+        self.pane.save_state(state);
+        if let Some(name) = self.get_state_name() {
+            state.set(&name, SessionState::KEY_RATIOS, &self.height_ratios());
+        }
+    }
+
+    fn restore_state(&self, state: &SessionState) {
+        self.pane.restore_state(state);
+        let Some(name) = self.get_state_name() else {
+            return;
+        };
+        if let Some(ratios) = state.get::<Vec<f64>>(&name, SessionState::KEY_RATIOS) {
+            self.set_height_ratios(&ratios);
+        }
+    }
 }
 
 #[yeehaw_derive::impl_element_from(pane)]
@@ -685,6 +774,23 @@ impl Element for HorizontalStack {
         self.ensure_normalized_sizes(dr);
         self.pane.drawing(ctx, dr, force_update)
     }
+
+    fn save_state(&self, state: &mut SessionState) {
+        self.pane.save_state(state);
+        if let Some(name) = self.get_state_name() {
+            state.set(&name, SessionState::KEY_RATIOS, &self.width_ratios());
+        }
+    }
+
+    fn restore_state(&self, state: &SessionState) {
+        self.pane.restore_state(state);
+        let Some(name) = self.get_state_name() else {
+            return;
+        };
+        if let Some(ratios) = state.get::<Vec<f64>>(&name, SessionState::KEY_RATIOS) {
+            self.set_width_ratios(&ratios);
+        }
+    }
 }
 
 // -----------------------------------------------------------------
@@ -313,4 +313,28 @@ impl Element for Tabs {
         }
         (captured, resps)
     }
+
+    // NOTE the ratios of the inner stack are not saved as the height of the tabs top is fixed
+    fn save_state(&self, state: &mut SessionState) {
+        self.pane.pane.save_state(state);
+        if let Some(name) = self.get_state_name() {
+            state.set(
+                &name,
+                SessionState::KEY_SELECTED_TAB,
+                &*self.tabs_top.selected.borrow(),
+            );
+        }
+    }
+
+    fn restore_state(&self, state: &SessionState) {
+        self.pane.pane.restore_state(state);
+        let Some(name) = self.get_state_name() else {
+            return;
+        };
+        if let Some(idx) = state.get::<usize>(&name, SessionState::KEY_SELECTED_TAB) {
+            if idx < self.tabs_top.els.borrow().len() {
+                self.select(idx);
+            }
+        }
+    }
 }
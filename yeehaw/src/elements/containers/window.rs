@@ -420,6 +420,19 @@ impl Element for WindowPane {
 
         (captured, resps)
     }
+
+    /// a minimized or maximized window is saved at its restore location
+    fn save_state(&self, state: &mut SessionState) {
+        self.pane.save_state(state);
+        let Some(name) = self.get_state_name() else {
+            return;
+        };
+        let restore = self.minimized_restore.borrow().clone();
+        let restore = restore.or_else(|| self.maximized_restore.borrow().clone());
+        if let Some(l) = restore {
+            state.set(&name, SessionState::KEY_LOCATION, &l);
+        }
+    }
 }
 
 #[derive(Clone)]
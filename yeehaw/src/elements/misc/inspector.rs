@@ -0,0 +1,337 @@
+use {
+    crate::*,
+    crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEventKind},
+};
+
+/// The inspection of an element and of its descendants (see Element::inspect) as displayed by the
+/// Inspector.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InspectNode {
+    pub id: ElementID,
+    pub kind: String,
+    pub loc: DynLocationSet,
+    /// the position of the element on the screen
+    pub at: Point,
+    pub size: Size,
+    pub focused: bool,
+    pub visible: bool,
+    /// the receivable events of the element itself (not including those of its descendants)
+    pub receivable: Vec<String>,
+    /// highest z first
+    pub children: Vec<InspectNode>,
+}
+
+impl InspectNode {
+    /// the inspection of the element alone, (x, y) being its position on the screen
+    pub fn new<E: Element + ?Sized>(el: &E, dr: &DrawRegion, x: i32, y: i32) -> Self {
+        let receivable = el
+            .receivable()
+            .iter()
+            .flat_map(|evs| evs.borrow().iter().map(Self::describe).collect::<Vec<_>>())
+            .collect();
+        InspectNode {
+            id: el.id(),
+            kind: el.kind().to_string(),
+            loc: el.get_dyn_location_set().clone(),
+            at: Point::new(x, y),
+            size: dr.size,
+            focused: el.get_focused(),
+            visible: el.get_visible(),
+            receivable,
+            children: Vec::new(),
+        }
+    }
+
+    fn describe(ev: &ReceivableEvent) -> String {
+        match ev {
+            ReceivableEvent::KeyCombo(kc) => KeyPossibility::combo_to_string(kc),
+            ReceivableEvent::Custom(name) => format!("custom {name}"),
+            ReceivableEvent::Mouse(m) => format!("mouse {m:?}"),
+            ReceivableEvent::Paste => "paste".to_string(),
+        }
+    }
+
+    /// whether the position on the screen is within the element
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.at.x
+            && y >= self.at.y
+            && x < self.at.x + self.size.width as i32
+            && y < self.at.y + self.size.height as i32
+    }
+
+    /// the deepest visible element at the position on the screen, preferring the highest z
+    pub fn node_at(&self, x: i32, y: i32) -> Option<&InspectNode> {
+        if !self.visible || !self.contains(x, y) {
+            return None;
+        }
+        self.children
+            .iter()
+            .find_map(|c| c.node_at(x, y))
+            .or(Some(self))
+    }
+
+    /// this element and its descendants depth first, along with their depth
+    pub fn flatten<'a>(&'a self, depth: usize, out: &mut Vec<(usize, &'a InspectNode)>) {
+        out.push((depth, self));
+        for c in self.children.iter() {
+            c.flatten(depth + 1, out);
+        }
+    }
+}
+
+/// Inspector is the element tree inspector (devtools) of the tui, see Tui::with_inspector. While
+/// open it captures all input: the element under the mouse is highlighted, clicking an element
+/// (or moving through the tree with up/down) selects it and outlines its region on screen. The
+/// panel lists the live element tree along with the details of the selected element, tab moves
+/// the panel to the other side of the screen.
+#[derive(Clone)]
+pub struct Inspector {
+    pub pane: Pane,
+    /// the organizer at the top of the inspected tree
+    pub root: ElementOrganizer,
+    pub selected: Rc<RefCell<Option<ElementID>>>,
+    pub hovered: Rc<RefCell<Option<ElementID>>>,
+    pub panel_left: Rc<RefCell<bool>>,
+    /// the inspected tree as of the last draw
+    tree: Rc<RefCell<Vec<InspectNode>>>,
+}
+
+impl Inspector {
+    pub const KIND: &'static str = "inspector";
+    /// above everything, including tooltips
+    pub const Z_INDEX: ZIndex = Tooltip::Z_INDEX + 10;
+    pub const PANEL_WIDTH: usize = 48;
+
+    const PANEL_STYLE: Style = Style::new_const(Color::WHITE, Color::GREY13);
+    const TITLE_STYLE: Style = Style::new_const(Color::YELLOW, Color::GREY13);
+    const SELECTED_STYLE: Style = Style::new_const(Color::BLACK, Color::LIGHT_YELLOW2);
+    const HOVER_COLOR: Color = Color::new_with_alpha(0, 150, 255, 90);
+    const OUTLINE_COLOR: Color = Color::YELLOW;
+
+    pub fn new(ctx: &Context, root: ElementOrganizer) -> Self {
+        let pane = Pane::new(ctx, Self::KIND)
+            .with_dyn_location(DynLocation::full())
+            .with_z(Self::Z_INDEX);
+        Inspector {
+            pane,
+            root,
+            selected: Rc::new(RefCell::new(None)),
+            hovered: Rc::new(RefCell::new(None)),
+            panel_left: Rc::new(RefCell::new(false)),
+            tree: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    /// inspect the tree (excluding the inspector itself) provided the draw region of the screen
+    pub fn refresh(&self, dr: &DrawRegion) {
+        let mut tree = self.root.inspect(dr, 0, 0);
+        tree.retain(|n| n.id != self.id());
+        *self.tree.borrow_mut() = tree;
+    }
+
+    pub fn tree(&self) -> Vec<InspectNode> {
+        self.tree.borrow().clone()
+    }
+
+    /// the inspection of the element within the last inspected tree
+    pub fn node(&self, id: &ElementID) -> Option<InspectNode> {
+        let tree = self.tree.borrow();
+        let mut flat = Vec::new();
+        for n in tree.iter() {
+            n.flatten(0, &mut flat);
+        }
+        flat.into_iter()
+            .find(|(_, n)| n.id == *id)
+            .map(|(_, n)| n.clone())
+    }
+
+    /// the deepest visible element at the position on the screen
+    pub fn node_at(&self, x: i32, y: i32) -> Option<InspectNode> {
+        self.tree
+            .borrow()
+            .iter()
+            .find_map(|n| n.node_at(x, y))
+            .cloned()
+    }
+
+    /// move the selection through the tree (depth first)
+    pub fn select_by(&self, delta: i32) {
+        let tree = self.tree.borrow();
+        let mut flat = Vec::new();
+        for n in tree.iter() {
+            n.flatten(0, &mut flat);
+        }
+        if flat.is_empty() {
+            return;
+        }
+        let cur = self.selected.borrow().clone();
+        let i = match cur.and_then(|id| flat.iter().position(|(_, n)| n.id == id)) {
+            Some(i) => (i as i32 + delta).clamp(0, flat.len() as i32 - 1) as usize,
+            None => 0,
+        };
+        *self.selected.borrow_mut() = Some(flat[i].1.id.clone());
+    }
+
+    pub fn key(&self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Up => self.select_by(-1),
+            KeyCode::Down => self.select_by(1),
+            KeyCode::Tab => {
+                let left = *self.panel_left.borrow();
+                *self.panel_left.borrow_mut() = !left;
+            }
+            _ => {}
+        }
+    }
+
+    /// (x, y) is the position of the mouse on the screen
+    pub fn mouse(&self, kind: MouseEventKind, x: i32, y: i32) {
+        let id = self.node_at(x, y).map(|n| n.id);
+        match kind {
+            MouseEventKind::Down(MouseButton::Left) => *self.selected.borrow_mut() = id,
+            MouseEventKind::Moved | MouseEventKind::Drag(_) => *self.hovered.borrow_mut() = id,
+            _ => {}
+        }
+    }
+
+    fn content(&self, size: Size) -> DrawChs2D {
+        let (w, h) = (size.width as usize, size.height as usize);
+        let mut chs = vec![vec![DrawCh::transparent(); w]; h];
+
+        let hovered = self.hovered.borrow().clone().and_then(|id| self.node(&id));
+        if let Some(n) = hovered {
+            let sty = Style::transparent().with_bg(Self::HOVER_COLOR);
+            for_region(&n, w, h, |x, y| {
+                chs[y][x] = DrawCh::new(ChPlus::Transparent, sty.clone());
+            });
+        }
+
+        let selected = self.selected.borrow().clone().and_then(|id| self.node(&id));
+        if let Some(n) = &selected {
+            let sty = Style::transparent().with_fg(Self::OUTLINE_COLOR);
+            let (x0, y0) = (n.at.x, n.at.y);
+            let (x1, y1) = (x0 + n.size.width as i32 - 1, y0 + n.size.height as i32 - 1);
+            for_region(n, w, h, |x, y| {
+                let (xi, yi) = (x as i32, y as i32);
+                let ch = match (xi == x0, xi == x1, yi == y0, yi == y1) {
+                    (true, _, true, _) => '┌',
+                    (_, true, true, _) => '┐',
+                    (true, _, _, true) => '└',
+                    (_, true, _, true) => '┘',
+                    (_, _, true, _) | (_, _, _, true) => '─',
+                    (true, _, _, _) | (_, true, _, _) => '│',
+                    _ => return,
+                };
+                chs[y][x] = DrawCh::new(ch, sty.clone());
+            });
+        }
+
+        let pw = Self::PANEL_WIDTH.min(w);
+        let px = if *self.panel_left.borrow() { 0 } else { w - pw };
+        let lines = self.panel_lines(h, selected.as_ref());
+        for (row, (line, sty)) in chs.iter_mut().zip(lines) {
+            // padded by a space on the left
+            let mut chars = std::iter::once(' ').chain(line.chars());
+            for ch in row[px..px + pw].iter_mut() {
+                *ch = DrawCh::new(chars.next().unwrap_or(' '), sty.clone());
+            }
+        }
+        DrawChs2D::new(chs)
+    }
+
+    /// the lines of the panel, exactly h lines
+    fn panel_lines(&self, h: usize, selected: Option<&InspectNode>) -> Vec<(String, Style)> {
+        let panel = Self::PANEL_STYLE;
+        let title = Self::TITLE_STYLE;
+        let mut details: Vec<(String, Style)> = vec![];
+        if let Some(n) = selected {
+            let l = &n.loc.l;
+            details.push((String::new(), panel.clone()));
+            details.push((format!("{} ({})", n.id, n.kind), title.clone()));
+            details.push((
+                format!(
+                    "z: {}  at: ({}, {})  size: {}x{}",
+                    n.loc.z, n.at.x, n.at.y, n.size.width, n.size.height
+                ),
+                panel.clone(),
+            ));
+            details.push((
+                format!("focused: {}  visible: {}", n.focused, n.visible),
+                panel.clone(),
+            ));
+            details.push((format!("x: {} .. {}", l.start_x, l.end_x), panel.clone()));
+            details.push((format!("y: {} .. {}", l.start_y, l.end_y), panel.clone()));
+            if !n.loc.extra.is_empty() {
+                details.push((
+                    format!("extra locations: {}", n.loc.extra.len()),
+                    panel.clone(),
+                ));
+            }
+            let rec =
+                if n.receivable.is_empty() { "none".to_string() } else { n.receivable.join(", ") };
+            details.push((format!("receivable: {rec}"), panel.clone()));
+        }
+
+        let mut lines = vec![
+            ("inspector (esc to close)".to_string(), title.clone()),
+            ("↑↓ or click: select, tab: move".to_string(), panel.clone()),
+        ];
+        let tree_h = h.saturating_sub(lines.len() + details.len());
+        let tree = self.tree.borrow();
+        let mut flat = Vec::new();
+        for n in tree.iter() {
+            n.flatten(0, &mut flat);
+        }
+        let sel_i = selected.and_then(|s| flat.iter().position(|(_, n)| n.id == s.id));
+        let offset = match sel_i {
+            Some(i) if i >= tree_h => i + 1 - tree_h,
+            _ => 0,
+        };
+        let hovered = self.hovered.borrow().clone();
+        for (i, (depth, n)) in flat.iter().enumerate().skip(offset).take(tree_h) {
+            let mut line = format!("{}{} z{}", "  ".repeat(*depth), n.id, n.loc.z);
+            if n.focused {
+                line += " *";
+            }
+            if !n.visible {
+                line += " hidden";
+            }
+            let sty = if Some(i) == sel_i {
+                Self::SELECTED_STYLE
+            } else if hovered.as_ref() == Some(&n.id) {
+                title.clone()
+            } else {
+                panel.clone()
+            };
+            lines.push((line, sty));
+        }
+        while lines.len() < h.saturating_sub(details.len()) {
+            lines.push((String::new(), panel.clone()));
+        }
+        lines.extend(details);
+        lines.truncate(h);
+        lines
+    }
+}
+
+/// call f for each position of the element which is on the screen
+fn for_region<F: FnMut(usize, usize)>(n: &InspectNode, w: usize, h: usize, mut f: F) {
+    let x0 = n.at.x.max(0) as usize;
+    let y0 = n.at.y.max(0) as usize;
+    let x1 = (n.at.x + n.size.width as i32).clamp(0, w as i32) as usize;
+    let y1 = (n.at.y + n.size.height as i32).clamp(0, h as i32) as usize;
+    for y in y0..y1 {
+        for x in x0..x1 {
+            f(x, y);
+        }
+    }
+}
+
+#[yeehaw_derive::impl_element_from(pane)]
+impl Element for Inspector {
+    fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
+        self.refresh(dr);
+        self.pane.set_content(self.content(dr.size));
+        self.pane.drawing(ctx, dr, force_update)
+    }
+}
@@ -1,6 +1,6 @@
 use {
     crate::{Keyboard as KB, *},
-    crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEventKind},
+    crossterm::event::{KeyEvent, MouseButton, MouseEventKind},
     rayon::prelude::*,
     std::collections::HashMap,
 };
@@ -34,7 +34,7 @@ pub struct MenuBar {
     close_on_primary_click: Rc<RefCell<bool>>,
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
 pub struct MenuStyle {
     folder_arrow: String,
     /// also including any left padding between the arrow and the menu item
@@ -67,6 +67,24 @@ impl Default for MenuStyle {
     }
 }
 
+impl MenuStyle {
+    /// the default menu style with any styles overridden by the theme
+    /// ("menu.selected", "menu.unselected", "menu.disabled")
+    pub fn themed(ctx: &Context) -> Self {
+        Self::from_theme(&ctx.theme)
+    }
+
+    pub fn from_theme(theme: &Theme) -> Self {
+        let d = Self::default();
+        MenuStyle {
+            unselected_style: theme.style("menu.unselected", d.unselected_style),
+            selected_style: theme.style("menu.selected", d.selected_style),
+            disabled_style: theme.style("menu.disabled", d.disabled_style),
+            ..d
+        }
+    }
+}
+
 /// direction which menu items prefer to open. If there is not enough space
 /// in the preferred direction, the menu will open in the opposite direction
 #[derive(Clone, Copy)]
@@ -86,26 +104,39 @@ impl MenuBar {
     /// very frontward
     const MENU_STYLE_MD_KEY: &'static str = "menu_style";
 
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![
-            (KB::KEY_ESC.into()),
-            (KB::KEY_ENTER.into()),
-            (KB::KEY_DOWN.into()),
-            (KB::KEY_UP.into()),
-            (KB::KEY_LEFT.into()),
-            (KB::KEY_RIGHT.into()),
-        ])
+    pub const ACT_CLOSE: KeyAction =
+        KeyAction::new("menu.close", &[&[KeyPossibility::Key(KB::KEY_ESC)]]);
+    pub const ACT_ENTER: KeyAction =
+        KeyAction::new("menu.enter", &[&[KeyPossibility::Key(KB::KEY_ENTER)]]);
+    pub const ACT_DOWN: KeyAction =
+        KeyAction::new("menu.down", &[&[KeyPossibility::Key(KB::KEY_DOWN)]]);
+    pub const ACT_UP: KeyAction = KeyAction::new("menu.up", &[&[KeyPossibility::Key(KB::KEY_UP)]]);
+    pub const ACT_LEFT: KeyAction =
+        KeyAction::new("menu.left", &[&[KeyPossibility::Key(KB::KEY_LEFT)]]);
+    pub const ACT_RIGHT: KeyAction =
+        KeyAction::new("menu.right", &[&[KeyPossibility::Key(KB::KEY_RIGHT)]]);
+
+    pub const KEY_ACTIONS: &'static [KeyAction] = &[
+        Self::ACT_CLOSE,
+        Self::ACT_ENTER,
+        Self::ACT_DOWN,
+        Self::ACT_UP,
+        Self::ACT_LEFT,
+        Self::ACT_RIGHT,
+    ];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
 
     pub fn top_menu_bar(ctx: &Context) -> Self {
-        let menu_sty = MenuStyle::default();
         let pane = ParentPane::new(ctx, MenuBar::KIND)
             .with_z(MenuBar::Z_INDEX)
-            .with_style(menu_sty.unselected_style.clone())
+            .with_style_from_theme("menu.unselected", MenuStyle::default().unselected_style)
             .with_overflow()
-            .with_focused_receivable_events(Self::default_receivable_events());
+            .with_focused_receivable_events(Self::default_receivable_events(ctx));
 
-        MenuBar {
+        let mb = MenuBar {
             pane,
             horizontal_bar: Rc::new(RefCell::new(true)),
             menu_items: Rc::new(RefCell::new(HashMap::new())),
@@ -114,20 +145,20 @@ impl MenuBar {
             primary_has_show_arrow: Rc::new(RefCell::new(false)),
             primary_open_dir: Rc::new(RefCell::new(OpenDirection::Down)),
             secondary_open_dir: Rc::new(RefCell::new(OpenDirection::RightThenDown)),
-            menu_style: Rc::new(RefCell::new(menu_sty)),
+            menu_style: Rc::new(RefCell::new(MenuStyle::default())),
             make_invisible_on_closedown: Rc::new(RefCell::new(false)),
             close_on_primary_click: Rc::new(RefCell::new(true)),
-        }
-        .with_dyn_height(1)
-        .with_dyn_width(DynVal::FULL)
+        };
+        mb.bind_menu_style();
+        mb.with_dyn_height(1).with_dyn_width(DynVal::FULL)
     }
 
     pub fn right_click_menu(ctx: &Context) -> Self {
         let pane = ParentPane::new(ctx, MenuBar::KIND)
             .with_z(MenuBar::Z_INDEX)
             .with_overflow()
-            .with_focused_receivable_events(Self::default_receivable_events());
-        MenuBar {
+            .with_focused_receivable_events(Self::default_receivable_events(ctx));
+        let mb = MenuBar {
             pane,
             horizontal_bar: Rc::new(RefCell::new(false)),
             menu_items: Rc::new(RefCell::new(HashMap::new())),
@@ -139,7 +170,17 @@ impl MenuBar {
             menu_style: Rc::new(RefCell::new(MenuStyle::default())),
             make_invisible_on_closedown: Rc::new(RefCell::new(true)),
             close_on_primary_click: Rc::new(RefCell::new(false)),
-        }
+        };
+        mb.bind_menu_style();
+        mb
+    }
+
+    /// take the menu style from the theme until a style is set explicitly
+    fn bind_menu_style(&self) {
+        self.pane
+            .pane
+            .theme_styles()
+            .bind(&self.menu_style, MenuStyle::from_theme);
     }
 
     pub fn with_menu_style(self, style: MenuStyle) -> Self {
@@ -721,7 +762,7 @@ impl MenuBar {
         (true, resps)
     }
 
-    pub fn receive_key_event(&self, ctx: &Context, ke: KeyEvent) -> (bool, EventResponses) {
+    pub fn receive_key_event(&self, ctx: &Context, keys: &[KeyEvent]) -> (bool, EventResponses) {
         if !*self.activated.borrow() {
             return (true, EventResponses::default());
         }
@@ -731,13 +772,24 @@ impl MenuBar {
         let is_primary = current_item.as_ref().is_none_or(|item| item.is_primary());
         let is_horizontal = *self.horizontal_bar.borrow();
 
-        match ke.code {
-            KeyCode::Left => self.handle_left_key(current_item, is_primary, is_horizontal),
-            KeyCode::Right => self.handle_right_key(current_item, is_primary, is_horizontal),
-            KeyCode::Up => self.handle_up_key(current_item, is_primary, is_horizontal),
-            KeyCode::Down => self.handle_down_key(current_item, is_primary, is_horizontal),
-            KeyCode::Enter => self.handle_enter_key(current_item, is_primary, is_horizontal, ctx),
-            KeyCode::Esc => {
+        let km = &ctx.keymap;
+        match true {
+            _ if km.matches(&Self::ACT_LEFT, keys) => {
+                self.handle_left_key(current_item, is_primary, is_horizontal)
+            }
+            _ if km.matches(&Self::ACT_RIGHT, keys) => {
+                self.handle_right_key(current_item, is_primary, is_horizontal)
+            }
+            _ if km.matches(&Self::ACT_UP, keys) => {
+                self.handle_up_key(current_item, is_primary, is_horizontal)
+            }
+            _ if km.matches(&Self::ACT_DOWN, keys) => {
+                self.handle_down_key(current_item, is_primary, is_horizontal)
+            }
+            _ if km.matches(&Self::ACT_ENTER, keys) => {
+                self.handle_enter_key(current_item, is_primary, is_horizontal, ctx)
+            }
+            _ if km.matches(&Self::ACT_CLOSE, keys) => {
                 *self.activated.borrow_mut() = false;
                 self.collapse_non_primary();
                 let menu_items = self.menu_items.borrow();
@@ -1058,7 +1110,7 @@ impl Element for MenuBar {
         match ev {
             Event::Mouse(me) => self.receive_mouse_event(ctx, me),
             Event::ExternalMouse(me) => self.receive_external_mouse_event(ctx, me),
-            Event::KeyCombo(keys) if !keys.is_empty() => self.receive_key_event(ctx, keys[0]),
+            Event::KeyCombo(keys) if !keys.is_empty() => self.receive_key_event(ctx, &keys),
             _ => self.pane.receive_event(ctx, ev.clone()),
         }
     }
@@ -1067,6 +1119,7 @@ impl Element for MenuBar {
         if !self.get_visible() {
             return Vec::with_capacity(0);
         }
+        self.pane.refresh_theme();
 
         let menu_style_bz = match serde_json::to_vec(&*self.menu_style.borrow()) {
             Ok(v) => v,
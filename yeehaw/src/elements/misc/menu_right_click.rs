@@ -2,8 +2,8 @@ use {
     crate::{
         elements::menu::{MenuItem, MenuStyle},
         Context, DrawRegion, DrawUpdate, DynLocation, DynLocationSet, DynVal, Element, ElementID,
-        Event, EventResponse, EventResponses, MenuBar, MouseEvent, Parent, Point, ReceivableEvents,
-        ZIndex,
+        Event, EventResponse, EventResponses, InspectNode, KeyBindings, MenuBar, MouseEvent,
+        Parent, Point, ReceivableEvents, SessionState, ZIndex,
     },
     crossterm::event::{MouseButton, MouseEventKind},
     std::{cell::RefCell, rc::Rc},
@@ -1,10 +1,21 @@
 pub mod debug_pane;
 pub mod focuser;
+pub mod inspector;
 pub mod menu;
 pub mod menu_right_click;
+pub mod profile_overlay;
 pub mod shadow;
+pub mod tooltip;
+pub mod which_key;
 
 pub use {
-    debug_pane::DebugSizePane, focuser::Focuser, menu::MenuBar, menu_right_click::RightClickMenu,
+    debug_pane::DebugSizePane,
+    focuser::Focuser,
+    inspector::{InspectNode, Inspector},
+    menu::MenuBar,
+    menu_right_click::RightClickMenu,
+    profile_overlay::ProfileOverlay,
     shadow::Shadowed,
+    tooltip::Tooltip,
+    which_key::{KeyBindings, WhichKey},
 };
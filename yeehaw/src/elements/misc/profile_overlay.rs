@@ -0,0 +1,170 @@
+use {crate::*, crossterm::event::KeyEvent, std::time::Duration};
+
+/// ProfileOverlay displays the render profile of the tui (see Profiler) in the top right corner:
+/// the frame times, the size of the last frame and the most time consuming elements. It is toggled
+/// with its trigger key (F12 by default), opening it enables profiling. While open the overlay is
+/// refreshed at the refresh interval so the profile is kept current without input.
+///
+/// Like the WhichKey, the ProfileOverlay should be added to the main element.
+#[derive(Clone)]
+pub struct ProfileOverlay {
+    pub pane: Pane,
+    pub profiler: Profiler,
+    pub trigger: Rc<RefCell<KeyEvent>>,
+    /// the number of elements listed
+    pub max_elements: Rc<RefCell<usize>>,
+    pub refresh_interval: Rc<RefCell<Duration>>,
+    /// the handle of the refresh heartbeat while open
+    pub refresh_handle: Rc<RefCell<Option<ScheduleHandle>>>,
+}
+
+impl ProfileOverlay {
+    pub const KIND: &'static str = "profile_overlay";
+    /// above the right click menu and the key help, below tooltips
+    pub const Z_INDEX: ZIndex = RightClickMenu::Z_INDEX + 5;
+    pub const DEFAULT_TRIGGER: KeyEvent = Keyboard::KEY_F12;
+    pub const DEFAULT_MAX_ELEMENTS: usize = 10;
+    pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+    /// the event key of the scheduled event which refreshes the overlay
+    pub const EV_REFRESH: &'static str = "profile_overlay_refresh";
+    /// wide enough for the element lines of the report
+    const WIDTH: i32 = 87;
+
+    const STYLE: Style = Style::new_const(Color::WHITE, Color::GREY13);
+
+    pub fn new(ctx: &Context) -> Self {
+        let pane = Pane::new(ctx, Self::KIND)
+            .with_z(Self::Z_INDEX)
+            .with_style_from_theme(Self::KIND, Self::STYLE)
+            .with_always_receivable_events(vec![Self::DEFAULT_TRIGGER.into()].into());
+        pane.set_visible(false);
+        let po = ProfileOverlay {
+            pane,
+            profiler: ctx.profiler.clone(),
+            trigger: Rc::new(RefCell::new(Self::DEFAULT_TRIGGER)),
+            max_elements: Rc::new(RefCell::new(Self::DEFAULT_MAX_ELEMENTS)),
+            refresh_interval: Rc::new(RefCell::new(Self::DEFAULT_REFRESH_INTERVAL)),
+            refresh_handle: Rc::new(RefCell::new(None)),
+        };
+        po.update_location();
+        po.describe_trigger();
+        po
+    }
+
+    /// the key which opens and closes the overlay
+    pub fn with_trigger(self, key: KeyEvent) -> Self {
+        let old = self.trigger.replace(key);
+        let mut evs = self.pane.rec_evs_always.borrow_mut();
+        evs.remove(old.into());
+        evs.push(key.into());
+        drop(evs);
+        self.describe_trigger();
+        self
+    }
+
+    pub fn with_max_elements(self, max: usize) -> Self {
+        *self.max_elements.borrow_mut() = max;
+        self.update_location();
+        self
+    }
+
+    /// how often the overlay is refreshed while open
+    pub fn with_refresh_interval(self, interval: Duration) -> Self {
+        *self.refresh_interval.borrow_mut() = interval;
+        self
+    }
+
+    fn describe_trigger(&self) {
+        let trigger = *self.trigger.borrow();
+        self.set_key_description(&[trigger.into()], "toggle render profile");
+    }
+
+    /// the top right corner, sized to the summary, header and listed elements
+    fn update_location(&self) {
+        let h = 3 + *self.max_elements.borrow() as i32 + 2;
+        self.pane.set_start_x(DynVal::FULL.minus_fixed(Self::WIDTH));
+        self.pane.set_end_x(DynVal::FULL);
+        self.pane.set_start_y(0);
+        self.pane.set_end_y(h);
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.get_visible()
+    }
+
+    /// the returned responses schedule the refresh of the overlay
+    pub fn open(&self) -> EventResponses {
+        self.profiler.set_enabled(true);
+        self.set_visible(true);
+        self.cancel_refresh();
+        let interval = *self.refresh_interval.borrow();
+        let se = ScheduledEvent::heartbeat(
+            interval,
+            Event::Custom(Self::EV_REFRESH.to_string(), vec![]),
+        );
+        *self.refresh_handle.borrow_mut() = Some(se.handle());
+        se.into()
+    }
+
+    /// NOTE profiling remains enabled
+    pub fn close(&self) {
+        self.set_visible(false);
+        self.cancel_refresh();
+    }
+
+    fn cancel_refresh(&self) {
+        if let Some(h) = self.refresh_handle.borrow_mut().take() {
+            h.cancel();
+        }
+    }
+
+    fn content(&self) -> DrawChs2D {
+        let lines = self
+            .profiler
+            .report_lines(Some(*self.max_elements.borrow()));
+        let padded = std::iter::once(String::new())
+            .chain(lines.into_iter().map(|l| format!(" {l}")))
+            .collect::<Vec<_>>()
+            .join("\n");
+        DrawChs2D::from_string(padded, self.pane.get_style())
+    }
+}
+
+#[yeehaw_derive::impl_element_from(pane)]
+impl Element for ProfileOverlay {
+    fn receive_event(&self, _ctx: &Context, ev: Event) -> (bool, EventResponses) {
+        match ev {
+            Event::KeyCombo(keys) if keys.first() == Some(&*self.trigger.borrow()) => {
+                if self.is_open() {
+                    self.close();
+                    return (true, EventResponses::default());
+                }
+                (true, self.open())
+            }
+            Event::Custom(ref key, _) if key == Self::EV_REFRESH => {
+                // redrawn with the latest profile
+                self.pane.set_dirty();
+                (true, EventResponses::default())
+            }
+            Event::Exit => {
+                self.cancel_refresh();
+                (false, EventResponses::default())
+            }
+            _ => (false, EventResponses::default()),
+        }
+    }
+
+    /// the profile shown is that of the renders prior to this one
+    fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
+        self.pane.refresh_theme();
+        if self.is_open() {
+            self.pane.set_content(self.content());
+        }
+        self.pane.drawing(ctx, dr, force_update)
+    }
+
+    /// the overlay is purely visual, mouse events pass through to the elements below
+    fn accepts_mouse(&self, _ev: &Event) -> bool {
+        false
+    }
+}
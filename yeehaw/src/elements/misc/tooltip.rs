@@ -0,0 +1,50 @@
+use crate::*;
+
+/// Tooltip is the floating pane which the tui spawns to display the tooltip of the element
+/// resting under the mouse (see Element::set_tooltip). It is positioned just below the mouse,
+/// shifted to remain fully on screen, and is removed by the tui on the next mouse or key event.
+#[derive(Clone)]
+pub struct Tooltip {
+    pub pane: Pane,
+}
+
+impl Tooltip {
+    pub const KIND: &'static str = "tooltip";
+    /// above the right click menu and the key help
+    pub const Z_INDEX: ZIndex = RightClickMenu::Z_INDEX + 10;
+
+    const STYLE: Style = Style::new_const(Color::BLACK, Color::LIGHT_YELLOW);
+
+    /// create a tooltip for the mouse position (x, y) within a screen of the provided size
+    pub fn new(ctx: &Context, text: &str, x: i32, y: i32, screen: Size) -> Self {
+        let sty = ctx.theme.style(Self::KIND, Self::STYLE);
+        let padded = text
+            .lines()
+            .map(|l| format!(" {l} "))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let content = DrawChs2D::from_string(padded, sty.clone());
+        let (w, h) = (content.width() as i32, content.height() as i32);
+        let (scr_w, scr_h) = (screen.width as i32, screen.height as i32);
+
+        // below the mouse unless there is only room above
+        let x = x.min(scr_w - w).max(0);
+        let y = if y + 1 + h > scr_h && y - h >= 0 { y - h } else { y + 1 };
+
+        let loc = DynLocation::new_fixed(x, x + w, y, y + h);
+        let pane = Pane::new(ctx, Self::KIND)
+            .with_dyn_location(loc)
+            .with_z(Self::Z_INDEX)
+            .with_style(sty)
+            .with_content(content);
+        Tooltip { pane }
+    }
+}
+
+#[yeehaw_derive::impl_element_from(pane)]
+impl Element for Tooltip {
+    /// the tooltip is purely visual, mouse events pass through to the elements below
+    fn accepts_mouse(&self, _ev: &Event) -> bool {
+        false
+    }
+}
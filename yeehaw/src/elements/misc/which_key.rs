@@ -0,0 +1,249 @@
+use {crate::*, crossterm::event::KeyEvent, std::collections::BTreeMap};
+
+/// The receivable key combos of a single element along with their optional descriptions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyBindings {
+    pub kind: String,
+    pub el_id: ElementID,
+    /// (key combo, description) the description is empty if none was provided
+    pub bindings: Vec<(String, String)>,
+}
+
+impl KeyBindings {
+    /// the element attribute which holds the key descriptions of an element
+    pub const ATTR_KEY_DESCRIPTIONS: &'static str = "key_descriptions";
+
+    /// the key descriptions of the element keyed by key combo (see
+    /// KeyPossibility::combo_to_string)
+    pub fn descriptions_of<E: Element + ?Sized>(el: &E) -> BTreeMap<String, String> {
+        el.get_attribute(Self::ATTR_KEY_DESCRIPTIONS)
+            .and_then(|bz| serde_json::from_slice(&bz).ok())
+            .unwrap_or_default()
+    }
+
+    /// the key bindings of the provided receivable events of the element, None if there are no
+    /// key combos
+    pub fn from_receivable<E: Element + ?Sized>(
+        el: &E, recs: Vec<Rc<RefCell<ReceivableEvents>>>,
+    ) -> Option<KeyBindings> {
+        let descs = Self::descriptions_of(el);
+        let mut bindings: Vec<(String, String)> = Vec::new();
+        for rec in recs {
+            for rec in rec.borrow().iter() {
+                let ReceivableEvent::KeyCombo(ref kc) = rec else {
+                    continue;
+                };
+                let keys = KeyPossibility::combo_to_string(kc);
+                if bindings.iter().any(|(k, _)| *k == keys) {
+                    continue;
+                }
+                let desc = descs.get(&keys).cloned().unwrap_or_default();
+                bindings.push((keys, desc));
+            }
+        }
+        if bindings.is_empty() {
+            return None;
+        }
+        Some(KeyBindings {
+            kind: el.kind().to_string(),
+            el_id: el.id(),
+            bindings,
+        })
+    }
+}
+
+/// WhichKey is a help overlay which lists the key bindings of the focused elements within a target
+/// parent (typically the main element), grouped by the element which receives them. It is
+/// opened with its trigger key (F1 by default) and closed with Esc or the trigger key.
+///
+/// The WhichKey should be added to the target, it sits above all other elements (like the
+/// RightClickMenu) and is hidden while closed. Help larger than the screen is cut off.
+#[derive(Clone)]
+pub struct WhichKey {
+    pub pane: Pane,
+    /// the elements of the target, held weakly as the which key is itself one of them
+    pub target: WeakElementOrganizer,
+    pub trigger: Rc<RefCell<KeyEvent>>,
+    pub style: Rc<RefCell<Style>>,
+    pub title_style: Rc<RefCell<Style>>,
+}
+
+impl WhichKey {
+    pub const KIND: &'static str = "which_key";
+    pub const Z_INDEX: ZIndex = RightClickMenu::Z_INDEX;
+    pub const DEFAULT_TRIGGER: KeyEvent = Keyboard::KEY_F1;
+
+    const STYLE: Style = Style::new_const(Color::WHITE, Color::GREY13);
+    const TITLE_STYLE: Style = Style::new_const(Color::YELLOW, Color::GREY13);
+
+    pub fn new(ctx: &Context, target: &ParentPane) -> Self {
+        let pane = Pane::new(ctx, Self::KIND)
+            .with_z(Self::Z_INDEX)
+            .with_always_receivable_events(vec![Self::DEFAULT_TRIGGER.into()].into());
+        pane.set_visible(false);
+        let themed = pane.theme_styles();
+        let wk = WhichKey {
+            pane,
+            target: target.eo.downgrade(),
+            trigger: Rc::new(RefCell::new(Self::DEFAULT_TRIGGER)),
+            style: themed.style(Self::KIND, Self::STYLE),
+            title_style: themed.style("which_key.title", Self::TITLE_STYLE),
+        };
+        wk.describe_trigger();
+        wk
+    }
+
+    /// the key which opens and closes the overlay
+    pub fn with_trigger(self, key: KeyEvent) -> Self {
+        let old = self.trigger.replace(key);
+        let mut evs = self.pane.rec_evs_always.borrow_mut();
+        evs.remove(old.into());
+        evs.push(key.into());
+        drop(evs);
+        self.describe_trigger();
+        self
+    }
+
+    pub fn with_style(self, sty: Style) -> Self {
+        *self.style.borrow_mut() = sty;
+        self
+    }
+
+    pub fn with_title_style(self, sty: Style) -> Self {
+        *self.title_style.borrow_mut() = sty;
+        self
+    }
+
+    fn describe_trigger(&self) {
+        let trigger = *self.trigger.borrow();
+        self.set_key_description(&[trigger.into()], "toggle key help");
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.get_visible()
+    }
+
+    /// the key bindings of the focused elements of the target
+    pub fn target_key_bindings(&self) -> Vec<KeyBindings> {
+        self.target
+            .upgrade()
+            .map(|eo| eo.key_bindings())
+            .unwrap_or_default()
+    }
+
+    /// collect the key bindings of the target and display them
+    pub fn open(&self) {
+        // NOTE the bindings are collected before Esc is made receivable
+        self.update_content();
+        self.pane
+            .rec_evs_always
+            .borrow_mut()
+            .push(Keyboard::KEY_ESC.into());
+        self.set_visible(true);
+    }
+
+    pub fn close(&self) {
+        self.pane
+            .rec_evs_always
+            .borrow_mut()
+            .remove(Keyboard::KEY_ESC.into());
+        self.set_visible(false);
+    }
+
+    /// regenerate the help and size the overlay to fit it
+    fn update_content(&self) {
+        let content = self.help_content(self.target_key_bindings());
+        let (w, h) = (content.width() as i32, content.height() as i32);
+
+        // centered, but kept within the screen should the help be larger
+        let start_x = DynVal::new_flex(0.5).minus_fixed(w / 2).with_min(0);
+        let start_y = DynVal::new_flex(0.5).minus_fixed(h / 2).with_min(0);
+        self.pane.set_start_x(start_x.clone());
+        self.pane
+            .set_end_x(start_x.plus_fixed(w).with_max(DynVal::FULL));
+        self.pane.set_start_y(start_y.clone());
+        self.pane
+            .set_end_y(start_y.plus_fixed(h).with_max(DynVal::FULL));
+        self.pane.set_content(content);
+    }
+
+    /// the help text, one group per element with a header of the element kind
+    fn help_content(&self, groups: Vec<KeyBindings>) -> DrawChs2D {
+        let mut lines: Vec<(String, bool)> = vec![
+            ("key help (esc to close)".to_string(), true),
+            (String::new(), false),
+        ];
+
+        let key_width = groups
+            .iter()
+            .flat_map(|g| g.bindings.iter())
+            .map(|(k, _)| k.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut shown: Vec<(&str, &Vec<(String, String)>)> = vec![];
+        for g in groups.iter() {
+            // identical elements (such as many buttons) are only shown once
+            if shown.contains(&(g.kind.as_str(), &g.bindings)) {
+                continue;
+            }
+            shown.push((g.kind.as_str(), &g.bindings));
+            lines.push((g.kind.clone(), true));
+            for (keys, desc) in g.bindings.iter() {
+                let line = format!("  {keys:key_width$}  {desc}");
+                lines.push((line.trim_end().to_string(), false));
+            }
+        }
+
+        let width = lines
+            .iter()
+            .map(|(l, _)| l.chars().count())
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let sty = self.style.borrow().clone();
+        let title_sty = self.title_style.borrow().clone();
+        let mut chs = vec![vec![DrawCh::new(' ', sty.clone()); width]];
+        for (line, is_title) in lines {
+            let sty = if is_title { &title_sty } else { &sty };
+            let mut row = vec![DrawCh::new(' ', sty.clone()); width];
+            for (i, c) in line.chars().enumerate() {
+                row[i + 1] = DrawCh::new(c, sty.clone());
+            }
+            chs.push(row);
+        }
+        chs.push(vec![DrawCh::new(' ', sty); width]);
+        DrawChs2D::new(chs)
+    }
+}
+
+#[yeehaw_derive::impl_element_from(pane)]
+impl Element for WhichKey {
+    fn receive_event(&self, _ctx: &Context, ev: Event) -> (bool, EventResponses) {
+        let Event::KeyCombo(keys) = ev else {
+            return (false, EventResponses::default());
+        };
+        let Some(key) = keys.first() else {
+            return (false, EventResponses::default());
+        };
+        if *key == *self.trigger.borrow() {
+            if self.is_open() {
+                self.close();
+            } else {
+                self.open();
+            }
+            return (true, EventResponses::default());
+        }
+        if *key == Keyboard::KEY_ESC && self.is_open() {
+            self.close();
+            return (true, EventResponses::default());
+        }
+        (false, EventResponses::default())
+    }
+
+    fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
+        if self.pane.refresh_theme() && self.is_open() {
+            self.update_content();
+        }
+        self.pane.drawing(ctx, dr, force_update)
+    }
+}
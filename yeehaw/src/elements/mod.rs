@@ -5,4 +5,11 @@ pub mod pane_parent;
 pub mod panes;
 pub mod widgets;
 
-pub use {containers::*, misc::*, pane::Pane, pane_parent::ParentPane, panes::*, widgets::*};
+pub use {
+    containers::*,
+    misc::*,
+    pane::{Pane, UpdateDirtyContentFn},
+    pane_parent::ParentPane,
+    panes::*,
+    widgets::*,
+};
@@ -2,7 +2,7 @@ use {
     crate::{
         Color, Context, DrawCh, DrawChPos, DrawChs2D, DrawRegion, DrawUpdate, DynLocation,
         DynLocationSet, DynVal, Element, ElementID, Event, EventResponses, Loc, Parent,
-        ReceivableEvents, Size, Style, ZIndex,
+        ReceivableEvents, Size, Style, ThemedStyles, ZIndex,
     },
     std::{
         collections::HashMap,
@@ -13,6 +13,9 @@ use {
     },
 };
 
+/// regenerates the content of a pane (see Pane::set_update_dirty_content)
+pub type UpdateDirtyContentFn = Box<dyn FnMut(&Context, &DrawRegion)>;
+
 /// Pane is a pane element which other objects can embed and build off
 /// of. It defines the basic draw functionality of a pane.
 #[derive(Clone)]
@@ -40,7 +43,16 @@ pub struct Pane {
         Rc<RefCell<HashMap<String, Vec<(ElementID, Box<dyn FnMut(&str, Box<dyn Element>)>)>>>>,
 
     content: Rc<RefCell<DrawChs2D>>,
+    /// whether the content has been changed since it was last drawn, in which case the content is
+    /// resent to the tui on the next draw
     is_content_dirty: Rc<RefCell<bool>>,
+
+    /// whether the content must be regenerated by update_dirty_content before the next draw (see
+    /// set_dirty), regenerating the content then marks it as changed (is_content_dirty)
+    needs_update: Rc<RefCell<bool>>,
+    /// if Some, called during drawing to regenerate the content when the pane is dirty or its
+    /// size has changed
+    update_dirty_content: Rc<RefCell<Option<UpdateDirtyContentFn>>>,
     last_size: Rc<RefCell<Size>>,
     last_visible_region: Rc<RefCell<Option<Loc>>>,
 
@@ -55,6 +67,9 @@ pub struct Pane {
 
     /// allows a pane to overflow its bounds during drawing. Useful for menus
     overflow: Rc<RefCell<bool>>,
+
+    /// the styles of the element which are taken from the theme of the context it was created with
+    theme_styles: ThemedStyles,
 }
 
 impl Pane {
@@ -74,6 +89,8 @@ impl Pane {
             hooks: Rc::new(RefCell::new(HashMap::new())),
             content: Rc::new(RefCell::new(DrawChs2D::default())),
             is_content_dirty: Rc::new(RefCell::new(true)),
+            needs_update: Rc::new(RefCell::new(true)),
+            update_dirty_content: Rc::new(RefCell::new(None)),
             last_size: Rc::new(RefCell::new(Size::default())),
             last_visible_region: Rc::new(RefCell::new(None)),
             default_ch: Rc::new(RefCell::new(DrawCh::default())),
@@ -82,6 +99,7 @@ impl Pane {
             loc: Rc::new(RefCell::new(DynLocationSet::full())),
             visible: Rc::new(RefCell::new(true)),
             overflow: Rc::new(RefCell::new(false)),
+            theme_styles: ThemedStyles::new(&ctx.theme),
         }
     }
 
@@ -316,6 +334,48 @@ impl Pane {
         *self.is_content_dirty.borrow_mut() = true;
     }
 
+    /// mark the content to be regenerated by the update_dirty_content fn before the next draw, the
+    /// tui is also signalled to re-render (see send_dirty_upward)
+    pub fn set_dirty(&self) {
+        self.needs_update.replace(true);
+        self.send_dirty_upward();
+    }
+
+    /// whether the content is waiting to be regenerated (see set_dirty)
+    pub fn is_dirty(&self) -> bool {
+        *self.needs_update.borrow()
+    }
+
+    pub fn with_update_dirty_content(self, update_fn: UpdateDirtyContentFn) -> Pane {
+        self.set_update_dirty_content(update_fn);
+        self
+    }
+
+    /// Register the fn which regenerates the content of the pane (typically with set_content).
+    /// During drawing the fn is only called when the pane has been marked dirty (set_dirty), its
+    /// size has changed or the draw is forced, otherwise the existing content is drawn.
+    pub fn set_update_dirty_content(&self, update_fn: UpdateDirtyContentFn) {
+        *self.update_dirty_content.borrow_mut() = Some(update_fn);
+        self.set_dirty();
+    }
+
+    /// call the update_dirty_content fn if the content needs to be regenerated
+    fn update_dirty_content(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) {
+        if !force_update && !*self.needs_update.borrow() && *self.last_size.borrow() == dr.size {
+            return;
+        }
+        // taken out for the call so that the fn may itself use the pane
+        let Some(mut update_fn) = self.update_dirty_content.borrow_mut().take() else {
+            return;
+        };
+        self.needs_update.replace(false);
+        update_fn(ctx, dr);
+        let mut slot = self.update_dirty_content.borrow_mut();
+        if slot.is_none() {
+            *slot = Some(update_fn);
+        }
+    }
+
     pub fn get_content(&self) -> Ref<'_, DrawChs2D> {
         self.content.borrow()
     }
@@ -367,6 +427,40 @@ impl Pane {
         self
     }
 
+    /// the styles of the element bound to the theme, see ThemedStyles
+    pub fn theme_styles(&self) -> ThemedStyles {
+        self.theme_styles.clone()
+    }
+
+    pub fn with_style_from_theme(self, key: &str, default: Style) -> Pane {
+        self.set_style_from_theme(key, default);
+        self
+    }
+
+    /// style the pane with the style of the theme for the key (or the default), the style is
+    /// updated whenever the theme changes
+    pub fn set_style_from_theme(&self, key: &str, default: Style) {
+        self.is_content_dirty.replace(true);
+        let ch = self.default_ch.borrow().ch.clone();
+        let key = key.to_string();
+        self.theme_styles.bind(&self.default_ch, move |t| DrawCh {
+            ch: ch.clone(),
+            style: t.style(&key, default.clone()),
+        });
+    }
+
+    /// re-resolve the styles bound to the theme (see ThemedStyles) should the theme have changed
+    /// and regenerate the content. This is called when drawing the pane, elements which read their
+    /// styles before then should call it first. Returns whether any of the styles changed.
+    pub fn refresh_theme(&self) -> bool {
+        if !self.theme_styles.refresh() {
+            return false;
+        }
+        self.needs_update.replace(true);
+        self.is_content_dirty.replace(true);
+        true
+    }
+
     pub fn set_style(&self, style: Style) {
         self.is_content_dirty.replace(true);
         self.default_ch.borrow_mut().style = style;
@@ -440,6 +534,18 @@ impl Pane {
         }
     }
 
+    /// signal up the tree that this element has changed and that the tui must re-render, this is
+    /// only necessary for changes made outside of event processing (which always re-renders)
+    pub fn send_dirty_upward(&self) {
+        // the parent may be borrowed should this be called while propagating responses upward,
+        // the tui will re-render after that propagation regardless
+        if let Ok(parent) = self.parent.try_borrow() {
+            if let Some(parent) = parent.as_ref() {
+                parent.propagate_dirty_upward();
+            }
+        }
+    }
+
     pub fn has_parent(&self) -> bool {
         self.parent.borrow().is_some()
     }
@@ -502,6 +608,22 @@ impl Element for Pane {
         }
     }
 
+    /// all mouse events are accepted unless some kinds of mouse events have been declared in the
+    /// receivable events, then only those kinds are accepted
+    fn accepts_mouse(&self, ev: &Event) -> bool {
+        let mut declared = false;
+        for evs in self.receivable() {
+            let evs = evs.borrow();
+            if evs.has_mouse() {
+                if evs.contains_match(ev) {
+                    return true;
+                }
+                declared = true;
+            }
+        }
+        !declared
+    }
+
     ///                                                       (captured, resp          )
     fn receive_event(&self, _ctx: &Context, _ev: Event) -> (bool, EventResponses) {
         (false, EventResponses::default())
@@ -520,7 +642,9 @@ impl Element for Pane {
     }
 
     /// Drawing compiles all of the DrawChPos necessary to draw this element
-    fn drawing(&self, _: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
+    fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
+        self.refresh_theme();
+        self.update_dirty_content(ctx, dr, force_update);
         if !force_update
             && !*self.is_content_dirty.borrow()
             && *self.last_size.borrow() == dr.size
@@ -1,8 +1,9 @@
 use {
     crate::{
         Color, Context, DrawCh, DrawChs2D, DrawRegion, DrawUpdate, DynLocation, DynLocationSet,
-        DynVal, Element, ElementID, ElementOrganizer, Event, EventResponses, Pane, Parent,
-        ReceivableEvents, Size, Style, ZIndex,
+        DynVal, Element, ElementID, ElementOrganizer, Event, EventResponses, InspectNode,
+        KeyBindings, MouseEvent, Pane, Parent, ReceivableEvents, SessionState, Size, Style,
+        ThemedStyles, UpdateDirtyContentFn, ZIndex,
     },
     std::collections::HashMap,
     std::{
@@ -133,6 +134,32 @@ impl Element for ParentPane {
         rec
     }
 
+    fn key_bindings(&self) -> Vec<KeyBindings> {
+        let mut kbs = self.pane.key_bindings();
+        kbs.extend(self.eo.key_bindings());
+        kbs
+    }
+
+    fn tooltip_at(&self, ev: &MouseEvent) -> Option<String> {
+        self.eo.tooltip_at(ev).or_else(|| self.get_tooltip())
+    }
+
+    fn inspect(&self, dr: &DrawRegion, x: i32, y: i32) -> InspectNode {
+        let mut node = self.pane.inspect(dr, x, y);
+        node.children = self.eo.inspect(dr, x, y);
+        node
+    }
+
+    fn save_state(&self, state: &mut SessionState) {
+        self.pane.save_state(state);
+        self.eo.save_state(state);
+    }
+
+    fn restore_state(&self, state: &SessionState) {
+        self.pane.restore_state(state);
+        self.eo.restore_state(state);
+    }
+
     //                                               (captured, resp          )
     fn receive_event(&self, ctx: &Context, ev: Event) -> (bool, EventResponses) {
         self.eo.event_process(ctx, ev, Box::new(self.clone()))
@@ -201,4 +228,8 @@ impl Parent for ParentPane {
     fn get_id(&self) -> ElementID {
         self.pane.id()
     }
+
+    fn propagate_dirty_upward(&self) {
+        self.pane.send_dirty_upward();
+    }
 }
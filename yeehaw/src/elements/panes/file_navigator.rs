@@ -18,13 +18,11 @@ pub struct FileNavPane {
     pub offset: Rc<RefCell<usize>>,
     #[allow(clippy::type_complexity)]
     pub file_enter_fn: Rc<RefCell<FileNavPaneFn>>,
-
-    pub is_dirty: Rc<RefCell<bool>>,
 }
 
 pub type FileNavPaneFn = Box<dyn FnMut(Context, PathBuf) -> EventResponses>;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct FileNavStyle {
     pub up_dir: Style,
     pub file: Style,
@@ -48,21 +46,57 @@ impl Default for FileNavStyle {
     }
 }
 
+impl FileNavStyle {
+    /// the default file navigator style with any styles overridden by the theme
+    /// ("file_nav.up_dir", "file_nav.file", "file_nav.folder", "file_nav.top_dir",
+    /// "file_nav.background" and the color "file_nav.cursor_bg")
+    pub fn themed(ctx: &Context) -> Self {
+        Self::from_theme(&ctx.theme)
+    }
+
+    pub fn from_theme(t: &Theme) -> Self {
+        let d = Self::default();
+        FileNavStyle {
+            up_dir: t.style("file_nav.up_dir", d.up_dir),
+            file: t.style("file_nav.file", d.file),
+            folder: t.style("file_nav.folder", d.folder),
+            top_dir: t.style("file_nav.top_dir", d.top_dir),
+            background: t.style("file_nav.background", d.background),
+            cursor_bg: t.color("file_nav.cursor_bg", d.cursor_bg),
+        }
+    }
+}
+
 impl FileNavPane {
     const INDENT_SIZE: usize = 2;
 
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![
-            (KB::KEY_ENTER.into()),
-            (KB::KEY_UP.into()),
-            (KB::KEY_DOWN.into()),
-            (KB::KEY_J.into()),
-            (KB::KEY_K.into()),
-        ])
+    /// open the highlighted file or expand/collapse the highlighted folder
+    pub const ACT_ENTER: KeyAction =
+        KeyAction::new("file_nav.enter", &[&[KeyPossibility::Key(KB::KEY_ENTER)]]);
+    pub const ACT_CURSOR_UP: KeyAction = KeyAction::new(
+        "file_nav.cursor_up",
+        &[
+            &[KeyPossibility::Key(KB::KEY_UP)],
+            &[KeyPossibility::Key(KB::KEY_K)],
+        ],
+    );
+    pub const ACT_CURSOR_DOWN: KeyAction = KeyAction::new(
+        "file_nav.cursor_down",
+        &[
+            &[KeyPossibility::Key(KB::KEY_DOWN)],
+            &[KeyPossibility::Key(KB::KEY_J)],
+        ],
+    );
+
+    pub const KEY_ACTIONS: &'static [KeyAction] =
+        &[Self::ACT_ENTER, Self::ACT_CURSOR_UP, Self::ACT_CURSOR_DOWN];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
 
     pub fn new(ctx: &Context, dir: PathBuf) -> Self {
-        let styles = FileNavStyle::default();
+        let styles = FileNavStyle::themed(ctx);
         let pane = Pane::new(ctx, "file_nav_pane");
         let up_dir = UpDir::new(".. (up a dir)".to_string(), styles.up_dir.clone(), 0);
         let top_dir = TopDir::new(
@@ -79,12 +113,12 @@ impl FileNavPane {
 
         pane.rec_evs_focused
             .borrow_mut()
-            .extend(Self::default_receivable_events().0);
+            .extend(Self::default_receivable_events(ctx).0);
 
         pane.set_dyn_height(DynVal::FULL);
         pane.set_dyn_width(DynVal::new_fixed(32));
 
-        Self {
+        let fnp = Self {
             pane,
             styles: Rc::new(RefCell::new(styles)),
             nav_items: Rc::new(RefCell::new(nav_items)),
@@ -93,8 +127,14 @@ impl FileNavPane {
             file_enter_fn: Rc::new(RefCell::new(Box::new(|_ctx, _path| {
                 EventResponses::default()
             }))),
-            is_dirty: Rc::new(RefCell::new(true)),
-        }
+        };
+        fnp.pane
+            .theme_styles()
+            .bind(&fnp.styles, FileNavStyle::from_theme);
+        let fnp_ = fnp.clone();
+        fnp.pane
+            .set_update_dirty_content(Box::new(move |_, dr| fnp_.update_content(dr)));
+        fnp
     }
 
     pub fn set_fn(&self, file_enter_fn: FileNavPaneFn) {
@@ -106,12 +146,21 @@ impl FileNavPane {
         self
     }
 
-    pub fn update_content(&self, dr: &DrawRegion, force_update: bool) {
-        if !force_update && !*self.is_dirty.borrow() {
-            return;
+    pub fn update_content(&self, dr: &DrawRegion) {
+        // correct offsets
+        if *self.highlight_position.borrow() >= *self.offset.borrow() + dr.size.height as usize - 1
+        {
+            *self.offset.borrow_mut() += 1;
         }
+        if *self.highlight_position.borrow() < *self.offset.borrow() {
+            *self.offset.borrow_mut() -= 1;
+        }
+
+        // the styles may have changed with the theme
+        let styles = self.styles.borrow().clone();
         let mut content = vec![Vec::new()];
-        for (i, item) in self.nav_items.borrow().0.iter().enumerate() {
+        for (i, item) in self.nav_items.borrow_mut().0.iter_mut().enumerate() {
+            item.set_styles(&styles);
             if i < *self.offset.borrow() {
                 continue;
             }
@@ -120,21 +169,12 @@ impl FileNavPane {
             // cursor logic
             if i == *self.highlight_position.borrow() {
                 for ch in chs.iter_mut() {
-                    ch.style.set_bg(self.styles.borrow().cursor_bg.clone());
+                    ch.style.set_bg(styles.cursor_bg.clone());
                 }
             }
             content.push(chs);
         }
         self.pane.set_content(content.into());
-
-        // correct offsets
-        if *self.highlight_position.borrow() >= *self.offset.borrow() + dr.size.height as usize - 1
-        {
-            *self.offset.borrow_mut() += 1;
-        }
-        if *self.highlight_position.borrow() < *self.offset.borrow() {
-            *self.offset.borrow_mut() -= 1;
-        }
     }
 }
 
@@ -143,20 +183,21 @@ impl Element for FileNavPane {
     fn receive_event(&self, ctx: &Context, ev: Event) -> (bool, EventResponses) {
         match ev {
             Event::KeyCombo(ke) => {
-                self.is_dirty.replace(true);
+                self.pane.set_dirty();
+                let km = &ctx.keymap;
                 match true {
-                    _ if ke[0] == KB::KEY_J || ke[0] == KB::KEY_DOWN => {
-                        if *self.highlight_position.borrow() < (*self.nav_items.borrow()).len() - 1
-                        {
-                            *self.highlight_position.borrow_mut() += 1;
-                        }
+                    _ if km.matches(&Self::ACT_CURSOR_DOWN, &ke)
+                        && *self.highlight_position.borrow()
+                            < (*self.nav_items.borrow()).len() - 1 =>
+                    {
+                        *self.highlight_position.borrow_mut() += 1;
                     }
-                    _ if ke[0] == KB::KEY_K || ke[0] == KB::KEY_UP => {
-                        if *self.highlight_position.borrow() > 0 {
-                            *self.highlight_position.borrow_mut() -= 1;
-                        }
+                    _ if km.matches(&Self::ACT_CURSOR_UP, &ke)
+                        && *self.highlight_position.borrow() > 0 =>
+                    {
+                        *self.highlight_position.borrow_mut() -= 1;
                     }
-                    _ if ke[0] == KB::KEY_ENTER => {
+                    _ if km.matches(&Self::ACT_ENTER, &ke) => {
                         let (ni, resps) = {
                             let nav_items = self.nav_items.borrow().clone();
                             self.nav_items.borrow_mut()[*self.highlight_position.borrow()].enter(
@@ -175,16 +216,12 @@ impl Element for FileNavPane {
                 }
             }
             Event::Initialize => {
-                self.is_dirty.replace(true);
+                self.pane.set_dirty();
             }
             _ => {}
         }
         (true, EventResponses::default())
     }
-    fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
-        self.update_content(dr, force_update);
-        self.pane.drawing(ctx, dr, force_update)
-    }
 }
 
 // NOTE use box due to large widely varying variant sizes.
@@ -234,6 +271,19 @@ impl NavItem {
         (None, EventResponses::default())
     }
 
+    /// restyle the item with the styles of the navigator
+    pub fn set_styles(&mut self, styles: &FileNavStyle) {
+        match self {
+            NavItem::File(f) => f.style = styles.file.clone(),
+            NavItem::Folder(f) => f.set_styles(styles),
+            NavItem::TopDir(f) => {
+                f.sty = styles.top_dir.clone();
+                f.folder.set_styles(styles);
+            }
+            NavItem::UpDir(f) => f.sty = styles.up_dir.clone(),
+        }
+    }
+
     pub fn indentation(&self) -> usize {
         match self {
             NavItem::File(f) => f.indentation(),
@@ -430,6 +480,11 @@ impl Folder {
         sub_items
     }
 
+    pub fn set_styles(&mut self, styles: &FileNavStyle) {
+        self.folder_style = styles.folder.clone();
+        self.file_style = styles.file.clone();
+    }
+
     pub fn enter(&self, nis: &NavItems, highlight_position: usize) -> NavItems {
         let mut nis = nis.clone();
         if !self.is_expanded {
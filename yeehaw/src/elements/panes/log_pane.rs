@@ -0,0 +1,478 @@
+use {
+    crate::{Keyboard as KB, *},
+    crossterm::event::{KeyCode, KeyEvent, MouseEventKind},
+    std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    tokio::sync::mpsc::error::TrySendError,
+};
+
+/// LogPane displays the records of the yeehaw logger (see the log module) live, including those
+/// of the log crate and tracing when their facades are installed. Records are colored by level and
+/// may be filtered by level and by a (case insensitive) substring. The pane follows the newest
+/// records until scrolled up or paused, the last line displays the status of the pane.
+#[derive(Clone)]
+pub struct LogPane {
+    pub pane: Pane,
+    pub styles: Rc<RefCell<LogPaneStyle>>,
+    /// the least severe level displayed
+    pub max_level: Rc<RefCell<LogLevel>>,
+    /// only records containing the filter are displayed
+    pub filter: Rc<RefCell<String>>,
+    /// whether the filter is being typed
+    pub editing_filter: Rc<RefCell<bool>>,
+    /// the first displayed line while paused (the seq of its record and the line within the
+    /// record), None while following the newest records
+    pub paused_at: Rc<RefCell<Option<(u64, usize)>>>,
+    /// the number of record lines displayed as of the last draw
+    view_height: Rc<RefCell<usize>>,
+    /// the logger count as of the last draw
+    seen: Rc<RefCell<u64>>,
+    /// shared with the log listener of the pane
+    wake: Arc<LogWake>,
+}
+
+/// The state shared between a log pane and its log listener, which is called from the logging
+/// thread.
+#[derive(Default)]
+struct LogWake {
+    /// a redraw has been requested since the last draw, further records do not request another
+    pending: AtomicBool,
+    /// the pane has exited, its listener is removed with the next record
+    exited: AtomicBool,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct LogPaneStyle {
+    pub error: Style,
+    pub warn: Style,
+    pub info: Style,
+    pub debug: Style,
+    pub trace: Style,
+    /// the target and message of a record
+    pub message: Style,
+    pub status: Style,
+}
+
+impl Default for LogPaneStyle {
+    fn default() -> Self {
+        LogPaneStyle {
+            error: Style::default_const().with_fg(Color::RED),
+            warn: Style::default_const().with_fg(Color::YELLOW),
+            info: Style::default_const().with_fg(Color::GREEN),
+            debug: Style::default_const().with_fg(Color::LIGHT_BLUE),
+            trace: Style::default_const().with_fg(Color::GREY13),
+            message: Style::default_const(),
+            status: Style::new_const(Color::BLACK, Color::GREY13),
+        }
+    }
+}
+
+impl LogPaneStyle {
+    /// the default log pane style with any styles overridden by the theme ("log_pane.error",
+    /// "log_pane.warn", "log_pane.info", "log_pane.debug", "log_pane.trace", "log_pane.message"
+    /// and "log_pane.status")
+    pub fn themed(ctx: &Context) -> Self {
+        Self::from_theme(&ctx.theme)
+    }
+
+    pub fn from_theme(t: &Theme) -> Self {
+        let d = Self::default();
+        LogPaneStyle {
+            error: t.style("log_pane.error", d.error),
+            warn: t.style("log_pane.warn", d.warn),
+            info: t.style("log_pane.info", d.info),
+            debug: t.style("log_pane.debug", d.debug),
+            trace: t.style("log_pane.trace", d.trace),
+            message: t.style("log_pane.message", d.message),
+            status: t.style("log_pane.status", d.status),
+        }
+    }
+
+    pub fn level(&self, level: LogLevel) -> Style {
+        match level {
+            LogLevel::Error => self.error.clone(),
+            LogLevel::Warn => self.warn.clone(),
+            LogLevel::Info => self.info.clone(),
+            LogLevel::Debug => self.debug.clone(),
+            LogLevel::Trace => self.trace.clone(),
+        }
+    }
+}
+
+impl LogPane {
+    pub const KIND: &'static str = "log_pane";
+
+    /// the width of the level column
+    const LEVEL_WIDTH: usize = 6;
+
+    pub const ACT_SCROLL_UP: KeyAction = KeyAction::new(
+        "log_pane.scroll_up",
+        &[
+            &[KeyPossibility::Key(KB::KEY_UP)],
+            &[KeyPossibility::Key(KB::KEY_K)],
+        ],
+    );
+    pub const ACT_SCROLL_DOWN: KeyAction = KeyAction::new(
+        "log_pane.scroll_down",
+        &[
+            &[KeyPossibility::Key(KB::KEY_DOWN)],
+            &[KeyPossibility::Key(KB::KEY_J)],
+        ],
+    );
+    pub const ACT_PAGE_UP: KeyAction = KeyAction::new(
+        "log_pane.page_up",
+        &[&[KeyPossibility::Key(KB::KEY_PAGE_UP)]],
+    );
+    pub const ACT_PAGE_DOWN: KeyAction = KeyAction::new(
+        "log_pane.page_down",
+        &[&[KeyPossibility::Key(KB::KEY_PAGE_DOWN)]],
+    );
+    /// pause at the oldest record
+    pub const ACT_OLDEST: KeyAction =
+        KeyAction::new("log_pane.oldest", &[&[KeyPossibility::Key(KB::KEY_HOME)]]);
+    /// follow the newest records
+    pub const ACT_FOLLOW: KeyAction =
+        KeyAction::new("log_pane.follow", &[&[KeyPossibility::Key(KB::KEY_END)]]);
+    pub const ACT_TOGGLE_FOLLOW: KeyAction = KeyAction::new(
+        "log_pane.toggle_follow",
+        &[&[KeyPossibility::Key(KB::KEY_F)]],
+    );
+    /// cycle through the least severe level displayed
+    pub const ACT_CYCLE_LEVEL: KeyAction =
+        KeyAction::new("log_pane.cycle_level", &[&[KeyPossibility::Key(KB::KEY_L)]]);
+    /// start typing the filter, typing ends with enter (esc clears the filter)
+    pub const ACT_EDIT_FILTER: KeyAction = KeyAction::new(
+        "log_pane.edit_filter",
+        &[&[KeyPossibility::Key(KB::KEY_SLASH)]],
+    );
+
+    pub const KEY_ACTIONS: &'static [KeyAction] = &[
+        Self::ACT_SCROLL_UP,
+        Self::ACT_SCROLL_DOWN,
+        Self::ACT_PAGE_UP,
+        Self::ACT_PAGE_DOWN,
+        Self::ACT_OLDEST,
+        Self::ACT_FOLLOW,
+        Self::ACT_TOGGLE_FOLLOW,
+        Self::ACT_CYCLE_LEVEL,
+        Self::ACT_EDIT_FILTER,
+    ];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
+    }
+
+    /// the keys received while typing the filter
+    fn filter_receivable_events() -> Vec<ReceivableEvent> {
+        vec![
+            KeyPossibility::Chars.into(),
+            KB::KEY_BACKSPACE.into(),
+            KB::KEY_ENTER.into(),
+            KB::KEY_ESC.into(),
+        ]
+    }
+
+    /// NOTE the pane is redrawn after records are logged until it receives Event::Exit
+    pub fn new(ctx: &Context) -> Self {
+        let pane = Pane::new(ctx, Self::KIND)
+            .with_focused_receivable_events(Self::default_receivable_events(ctx))
+            .with_dyn_width(DynVal::FULL)
+            .with_dyn_height(DynVal::FULL);
+
+        // records may be logged from any thread, the tui is woken to draw them
+        let wake = Arc::new(LogWake::default());
+        let wake_ = Arc::downgrade(&wake);
+        let ev_tx = ctx.ev_tx.clone();
+        log::add_listener(Arc::new(move || {
+            let Some(wake) = wake_.upgrade() else {
+                return false;
+            };
+            if wake.exited.load(Ordering::Relaxed) {
+                return false;
+            }
+            // only the first record since the last draw wakes the tui
+            if wake.pending.swap(true, Ordering::Relaxed) {
+                return true;
+            }
+            !matches!(ev_tx.try_send(Event::Redraw), Err(TrySendError::Closed(_)))
+        }));
+
+        let lp = LogPane {
+            pane,
+            styles: Rc::new(RefCell::new(LogPaneStyle::default())),
+            max_level: Rc::new(RefCell::new(LogLevel::Trace)),
+            filter: Rc::new(RefCell::new(String::new())),
+            editing_filter: Rc::new(RefCell::new(false)),
+            paused_at: Rc::new(RefCell::new(None)),
+            view_height: Rc::new(RefCell::new(0)),
+            seen: Rc::new(RefCell::new(log::count())),
+            wake,
+        };
+        lp.pane
+            .theme_styles()
+            .bind(&lp.styles, LogPaneStyle::from_theme);
+        let lp_ = lp.clone();
+        lp.pane
+            .set_update_dirty_content(Box::new(move |_, dr| lp_.update_content(dr)));
+        lp
+    }
+
+    // ----------------------------------------------
+    // decorators
+
+    pub fn with_max_level(self, level: LogLevel) -> Self {
+        self.set_max_level(level);
+        self
+    }
+
+    pub fn with_filter<S: Into<String>>(self, filter: S) -> Self {
+        self.set_filter(filter);
+        self
+    }
+
+    pub fn with_styles(self, styles: LogPaneStyle) -> Self {
+        *self.styles.borrow_mut() = styles;
+        self.pane.set_dirty();
+        self
+    }
+
+    // ----------------------------------------------
+
+    pub fn set_max_level(&self, level: LogLevel) {
+        *self.max_level.borrow_mut() = level;
+        self.pane.set_dirty();
+    }
+
+    pub fn set_filter<S: Into<String>>(&self, filter: S) {
+        *self.filter.borrow_mut() = filter.into();
+        self.pane.set_dirty();
+    }
+
+    pub fn is_following(&self) -> bool {
+        self.paused_at.borrow().is_none()
+    }
+
+    /// follow the newest records
+    pub fn follow(&self) {
+        *self.paused_at.borrow_mut() = None;
+        self.pane.set_dirty();
+    }
+
+    /// stop following, the displayed lines remain in place as records are logged
+    pub fn pause(&self) {
+        let lines = self.lines();
+        let top = self.top(&lines);
+        self.pause_at(&lines, top);
+    }
+
+    fn pause_at(&self, lines: &[(u64, usize, Vec<DrawCh>)], top: usize) {
+        let at = lines.get(top).map(|(seq, i, _)| (*seq, *i));
+        *self.paused_at.borrow_mut() = Some(at.unwrap_or_default());
+        self.pane.set_dirty();
+    }
+
+    /// scroll by the number of lines (up when negative), scrolling pauses the pane
+    pub fn scroll_by(&self, delta: i32) {
+        let lines = self.lines();
+        let max_top = lines.len().saturating_sub(*self.view_height.borrow());
+        let top = (self.top(&lines) as i32 + delta).clamp(0, max_top as i32);
+        self.pause_at(&lines, top as usize);
+    }
+
+    pub fn displays(&self, record: &LogRecord) -> bool {
+        if record.level > *self.max_level.borrow() {
+            return false;
+        }
+        let filter = self.filter.borrow().to_lowercase();
+        filter.is_empty() || record.to_string().to_lowercase().contains(&filter)
+    }
+
+    /// the displayed lines of the records (with the seq of their record and the line within the
+    /// record), oldest first
+    fn lines(&self) -> Vec<(u64, usize, Vec<DrawCh>)> {
+        let styles = self.styles.borrow();
+        let mut out = Vec::new();
+        for record in log::records().iter().filter(|r| self.displays(r)) {
+            let mut text = String::new();
+            if !record.target.is_empty() {
+                text = format!("{}: ", record.target);
+            }
+            text.push_str(&record.message);
+            for (i, line) in text.lines().enumerate() {
+                let level = match i {
+                    0 => format!("{:<w$}", record.level, w = Self::LEVEL_WIDTH),
+                    _ => " ".repeat(Self::LEVEL_WIDTH),
+                };
+                let level_sty = styles.level(record.level);
+                let chs = level
+                    .chars()
+                    .map(|c| DrawCh::new(c, level_sty.clone()))
+                    .chain(line.chars().map(|c| DrawCh::new(c, styles.message.clone())))
+                    .collect();
+                out.push((record.seq, i, chs));
+            }
+        }
+        out
+    }
+
+    /// the index of the first displayed line
+    fn top(&self, lines: &[(u64, usize, Vec<DrawCh>)]) -> usize {
+        let max_top = lines.len().saturating_sub(*self.view_height.borrow());
+        match *self.paused_at.borrow() {
+            None => max_top,
+            Some(at) => lines
+                .iter()
+                .position(|(seq, i, _)| (*seq, *i) >= at)
+                .unwrap_or(lines.len())
+                .min(max_top),
+        }
+    }
+
+    fn status(&self, n_lines: usize) -> String {
+        let mode = if self.is_following() { "following" } else { "paused" };
+        let mut s = format!(" {mode} | level: {} | ", self.max_level.borrow());
+        let filter = self.filter.borrow();
+        if *self.editing_filter.borrow() {
+            s += &format!("filter: {filter}_");
+        } else if !filter.is_empty() {
+            s += &format!("filter: {filter}");
+        } else {
+            s += "/ filter, l level, f follow";
+        }
+        s + &format!(" | {n_lines} lines")
+    }
+
+    pub fn update_content(&self, dr: &DrawRegion) {
+        let (w, h) = (dr.size.width as usize, dr.size.height as usize);
+        let view_h = h.saturating_sub(1);
+        *self.view_height.borrow_mut() = view_h;
+        let lines = self.lines();
+        let top = self.top(&lines);
+        let n_lines = lines.len();
+
+        let mut rows: Vec<Vec<DrawCh>> = lines
+            .into_iter()
+            .skip(top)
+            .take(view_h)
+            .map(|(_, _, line)| line)
+            .collect();
+        rows.resize(view_h, Vec::new());
+        if h > 0 {
+            let status = format!("{:<w$}", self.status(n_lines));
+            let sty = self.styles.borrow().status.clone();
+            rows.push(
+                status
+                    .chars()
+                    .map(|c| DrawCh::new(c, sty.clone()))
+                    .collect(),
+            );
+        }
+        self.pane.set_content(DrawChs2D::new(rows));
+    }
+
+    fn edit_filter(&self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) => self.filter.borrow_mut().push(c),
+            KeyCode::Backspace => {
+                self.filter.borrow_mut().pop();
+            }
+            KeyCode::Esc => {
+                self.filter.borrow_mut().clear();
+                self.stop_editing_filter();
+            }
+            KeyCode::Enter => self.stop_editing_filter(),
+            _ => {}
+        }
+        self.pane.set_dirty();
+    }
+
+    fn start_editing_filter(&self) {
+        *self.editing_filter.borrow_mut() = true;
+        self.pane
+            .rec_evs_focused
+            .borrow_mut()
+            .extend(Self::filter_receivable_events());
+        self.pane.set_dirty();
+    }
+
+    fn stop_editing_filter(&self) {
+        *self.editing_filter.borrow_mut() = false;
+        self.pane
+            .rec_evs_focused
+            .borrow_mut()
+            .remove_many(Self::filter_receivable_events());
+        self.pane.set_dirty();
+    }
+}
+
+#[yeehaw_derive::impl_element_from(pane)]
+impl Element for LogPane {
+    fn receive_event(&self, ctx: &Context, ev: Event) -> (bool, EventResponses) {
+        match ev {
+            Event::KeyCombo(ke) => {
+                if ke.is_empty() {
+                    return (false, EventResponses::default());
+                }
+                if *self.editing_filter.borrow() {
+                    self.edit_filter(ke[0]);
+                    return (true, EventResponses::default());
+                }
+                let page = *self.view_height.borrow() as i32;
+                let km = &ctx.keymap;
+                match km.action(Self::KEY_ACTIONS, &ke) {
+                    Some(a) if *a == Self::ACT_SCROLL_UP => self.scroll_by(-1),
+                    Some(a) if *a == Self::ACT_SCROLL_DOWN => self.scroll_by(1),
+                    Some(a) if *a == Self::ACT_PAGE_UP => self.scroll_by(-page),
+                    Some(a) if *a == Self::ACT_PAGE_DOWN => self.scroll_by(page),
+                    Some(a) if *a == Self::ACT_OLDEST => {
+                        let lines = self.lines();
+                        self.pause_at(&lines, 0);
+                    }
+                    Some(a) if *a == Self::ACT_FOLLOW => self.follow(),
+                    Some(a) if *a == Self::ACT_TOGGLE_FOLLOW => {
+                        if self.is_following() {
+                            self.pause();
+                        } else {
+                            self.follow();
+                        }
+                    }
+                    Some(a) if *a == Self::ACT_CYCLE_LEVEL => {
+                        let level = *self.max_level.borrow();
+                        let i = LogLevel::ALL.iter().position(|l| *l == level).unwrap_or(0);
+                        self.set_max_level(LogLevel::ALL[(i + 1) % LogLevel::ALL.len()]);
+                    }
+                    Some(a) if *a == Self::ACT_EDIT_FILTER => self.start_editing_filter(),
+                    _ => return (false, EventResponses::default()),
+                }
+                (true, EventResponses::default())
+            }
+            Event::Mouse(me) => match me.kind {
+                MouseEventKind::ScrollUp => {
+                    self.scroll_by(-1);
+                    (true, EventResponses::default())
+                }
+                MouseEventKind::ScrollDown => {
+                    self.scroll_by(1);
+                    (true, EventResponses::default())
+                }
+                _ => self.pane.receive_event(ctx, Event::Mouse(me)),
+            },
+            Event::Exit => {
+                self.wake.exited.store(true, Ordering::Relaxed);
+                self.pane.receive_event(ctx, ev)
+            }
+            _ => self.pane.receive_event(ctx, ev),
+        }
+    }
+
+    fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
+        self.wake.pending.store(false, Ordering::Relaxed);
+        let count = log::count();
+        if self.seen.replace(count) != count {
+            self.pane.set_dirty();
+        }
+        self.pane.drawing(ctx, dr, force_update)
+    }
+}
@@ -1,4 +1,5 @@
 pub mod file_navigator;
+pub mod log_pane;
 
 pub mod pane_limiter;
 pub mod pane_scrollable;
@@ -17,9 +18,13 @@ pub mod terminal_editor;
 
 pub use {
     file_navigator::FileNavPane,
+    log_pane::{LogPane, LogPaneStyle},
     pane_limiter::PaneLimiter,
     pane_scrollable::PaneScrollable,
-    pane_selectable::{ParentPaneOfSelectable, SelStyles, Selectability, SelectablePane},
+    pane_selectable::{
+        DirectionalNav, NavDirection, ParentPaneOfSelectable, SelStyles, Selectability,
+        SelectablePane,
+    },
 };
 
 #[cfg(feature = "terminal")]
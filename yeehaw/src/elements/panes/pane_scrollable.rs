@@ -225,6 +225,24 @@ impl Element for PaneScrollable {
         (captured, resps)
     }
 
+    fn tooltip_at(&self, ev: &MouseEvent) -> Option<String> {
+        // adjusted as the mouse events are within receive_event
+        let mut ev = ev.clone();
+        ev.column += *self.content_offset_x.borrow() as i32;
+        ev.row += *self.content_offset_y.borrow() as i32;
+        ev.dr = self.inner_draw_region(&ev.dr);
+        self.pane.tooltip_at(&ev)
+    }
+
+    fn inspect(&self, dr: &DrawRegion, x: i32, y: i32) -> InspectNode {
+        // the children are positioned within the inner draw region, shifted by the offset
+        let mut node = self.pane.pane.inspect(dr, x, y);
+        let x = x - *self.content_offset_x.borrow() as i32;
+        let y = y - *self.content_offset_y.borrow() as i32;
+        node.children = self.pane.eo.inspect(&self.inner_draw_region(dr), x, y);
+        node
+    }
+
     fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
         let x_off = *self.content_offset_x.borrow();
         let y_off = *self.content_offset_y.borrow();
@@ -298,6 +316,26 @@ impl Element for PaneScrollable {
         *self.content_offset_y.borrow_mut() = offset
     }
 
+    fn save_state(&self, state: &mut SessionState) {
+        self.pane.save_state(state);
+        if let Some(name) = self.get_state_name() {
+            let offset = (self.get_content_x_offset(), self.get_content_y_offset());
+            state.set(&name, SessionState::KEY_OFFSET, &offset);
+        }
+    }
+
+    fn restore_state(&self, state: &SessionState) {
+        self.pane.restore_state(state);
+        let Some(name) = self.get_state_name() else {
+            return;
+        };
+        // NOTE the offsets are set directly as the view size may not be known yet
+        if let Some((x, y)) = state.get::<(usize, usize)>(&name, SessionState::KEY_OFFSET) {
+            *self.content_offset_x.borrow_mut() = x.min(*self.content_width.borrow());
+            *self.content_offset_y.borrow_mut() = y.min(*self.content_height.borrow());
+        }
+    }
+
     fn get_content_x_offset(&self) -> usize {
         *self.content_offset_x.borrow()
     }
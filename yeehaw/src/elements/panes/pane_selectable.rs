@@ -1,6 +1,6 @@
 use {
     crate::{Keyboard as KB, *},
-    crossterm::event::{MouseButton, MouseEventKind},
+    crossterm::event::{KeyEvent, MouseButton, MouseEventKind},
     std::{cell::RefCell, rc::Rc},
 };
 
@@ -37,6 +37,9 @@ pub const ATTR_SELECTABILITY: &str = "selectability";
 pub struct SelectablePane {
     pub pane: ParentPane,
     pub styles: Rc<RefCell<SelStyles>>,
+    /// if Some the styles are bound to the theme (falling back to the default styles) and are
+    /// updated when drawn after the theme changes
+    pub themed: Rc<RefCell<Option<ThemedStyles>>>,
 }
 
 #[yeehaw_derive::impl_pane_basics_from(pane)]
@@ -47,6 +50,7 @@ impl SelectablePane {
         let out = SelectablePane {
             pane: ParentPane::new(ctx, kind),
             styles: Rc::new(RefCell::new(SelStyles::default())),
+            themed: Rc::new(RefCell::new(None)),
         };
         out.enable();
         out
@@ -71,7 +75,13 @@ impl SelectablePane {
         self.pane.pane.set_hook(&pre_hook_name, self.id(), hook);
     }
 
+    /// set the styles explicitly, these styles are no longer taken from the theme
     pub fn set_styles(&self, styles: SelStyles) {
+        *self.themed.borrow_mut() = None;
+        self.set_styles_inner(styles);
+    }
+
+    fn set_styles_inner(&self, styles: SelStyles) {
         *self.styles.borrow_mut() = styles;
         self.set_style(self.get_current_style());
     }
@@ -81,6 +91,39 @@ impl SelectablePane {
         self
     }
 
+    /// use the styles of the context's theme for this kind of element, falling back to the
+    /// provided default styles
+    pub fn set_theme_styles(&self, ctx: &Context, default: SelStyles) {
+        let themed = ThemedStyles::new(&ctx.theme);
+        let kind = self.kind();
+        themed.bind(&self.styles, move |t| t.sel_styles(kind, default.clone()));
+        *self.themed.borrow_mut() = Some(themed);
+        self.apply_styles();
+    }
+
+    pub fn with_theme_styles(self, ctx: &Context, default: SelStyles) -> Self {
+        self.set_theme_styles(ctx, default);
+        self
+    }
+
+    /// re-resolve the styles from the theme (if themed) should the theme have changed, the post
+    /// selectability hooks are then called so that the element may redraw itself. This is called
+    /// when drawing the pane, elements which read their styles before then should call it first.
+    /// Returns whether any of the styles changed.
+    pub fn refresh_theme(&self) -> bool {
+        let restyled = self.themed.borrow().as_ref().is_some_and(|t| t.refresh());
+        if restyled {
+            self.apply_styles();
+        }
+        self.pane.refresh_theme() || restyled
+    }
+
+    /// style the pane for its current selectability and call the post selectability hooks
+    fn apply_styles(&self) {
+        self.set_style(self.get_current_style());
+        self.set_attr_selectability(self.get_selectability());
+    }
+
     pub fn disable(&self) -> EventResponses {
         self.set_selectability(Selectability::Unselectable, true)
     }
@@ -212,6 +255,14 @@ impl Element for SelectablePane {
         }
     }
 
+    fn key_bindings(&self) -> Vec<KeyBindings> {
+        if let Selectability::Selected = self.get_selectability() {
+            self.pane.key_bindings()
+        } else {
+            Vec::with_capacity(0)
+        }
+    }
+
     fn can_receive(&self, ev: &Event) -> bool {
         let attr_sel = self.get_selectability();
         if let Selectability::Selected = attr_sel {
@@ -267,10 +318,15 @@ impl Element for SelectablePane {
         resps.extend(resps_);
         (captured, resps)
     }
+
+    fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
+        self.refresh_theme();
+        self.pane.drawing(ctx, dr, force_update)
+    }
 }
 
 // ---------------------------------------
-#[derive(Clone, Default)]
+#[derive(Clone, Default, PartialEq)]
 pub struct SelStyles {
     pub selected_style: Style,
     pub ready_style: Style,
@@ -305,6 +361,101 @@ impl SelStyles {
 
 // ---------------------------------------
 
+/// direction of travel for directional focus navigation
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NavDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// Configuration of the directional (spatial) focus navigation of a ParentPaneOfSelectable.
+/// When a navigation key is pressed the focus moves to the nearest selectable element in that
+/// direction.
+#[derive(Clone, Debug)]
+pub struct DirectionalNav {
+    pub left: Vec<KeyEvent>,
+    pub right: Vec<KeyEvent>,
+    pub up: Vec<KeyEvent>,
+    pub down: Vec<KeyEvent>,
+
+    /// the weight applied to the distance perpendicular to the direction of travel, larger
+    /// values favour elements which are more closely aligned with the current element over those
+    /// which are nearer
+    pub perpendicular_weight: f64,
+}
+
+impl Default for DirectionalNav {
+    /// arrow keys and hjkl
+    fn default() -> Self {
+        DirectionalNav {
+            left: vec![KB::KEY_LEFT, KB::KEY_H],
+            right: vec![KB::KEY_RIGHT, KB::KEY_L],
+            up: vec![KB::KEY_UP, KB::KEY_K],
+            down: vec![KB::KEY_DOWN, KB::KEY_J],
+            perpendicular_weight: 2.,
+        }
+    }
+}
+
+impl DirectionalNav {
+    /// arrow keys only
+    pub fn arrows() -> Self {
+        DirectionalNav {
+            left: vec![KB::KEY_LEFT],
+            right: vec![KB::KEY_RIGHT],
+            up: vec![KB::KEY_UP],
+            down: vec![KB::KEY_DOWN],
+            ..Default::default()
+        }
+    }
+
+    pub fn with_perpendicular_weight(mut self, weight: f64) -> Self {
+        self.perpendicular_weight = weight;
+        self
+    }
+
+    pub fn direction_for(&self, key: &KeyEvent) -> Option<NavDirection> {
+        match true {
+            _ if self.left.contains(key) => Some(NavDirection::Left),
+            _ if self.right.contains(key) => Some(NavDirection::Right),
+            _ if self.up.contains(key) => Some(NavDirection::Up),
+            _ if self.down.contains(key) => Some(NavDirection::Down),
+            _ => None,
+        }
+    }
+
+    pub fn receivable_events(&self) -> Vec<ReceivableEvent> {
+        self.left
+            .iter()
+            .chain(self.right.iter())
+            .chain(self.up.iter())
+            .chain(self.down.iter())
+            .map(|k| (*k).into())
+            .collect()
+    }
+
+    /// The weighted distance travelling in the direction from one region to another, None if
+    /// the other region is not in that direction. Regions are (start_x, end_x, start_y, end_y).
+    pub fn distance(
+        &self, dir: NavDirection, from: (i32, i32, i32, i32), to: (i32, i32, i32, i32),
+    ) -> Option<f64> {
+        let center = |s: i32, e: i32| (s + e) as f64 / 2.;
+        let (from_cx, from_cy) = (center(from.0, from.1), center(from.2, from.3));
+        let (to_cx, to_cy) = (center(to.0, to.1), center(to.2, to.3));
+        //                   (primary, perpendicular)
+        let (primary, perp) = match dir {
+            NavDirection::Left if to_cx < from_cx => ((from.0 - to.1).max(0), to_cy - from_cy),
+            NavDirection::Right if to_cx > from_cx => ((to.0 - from.1).max(0), to_cy - from_cy),
+            NavDirection::Up if to_cy < from_cy => ((from.2 - to.3).max(0), to_cx - from_cx),
+            NavDirection::Down if to_cy > from_cy => ((to.2 - from.3).max(0), to_cx - from_cx),
+            _ => return None,
+        };
+        Some(primary as f64 + self.perpendicular_weight * perp.abs())
+    }
+}
+
 /// parent pane but with selectable pane logic
 /// use tabs to
 #[derive(Clone)]
@@ -312,6 +463,8 @@ pub struct ParentPaneOfSelectable {
     pub pane: ParentPane,
     pub selected: Rc<RefCell<Option<ElementID>>>,
     pub selectables: Rc<RefCell<Vec<ElementID>>>,
+    /// if Some, directional focus navigation is enabled
+    pub directional_nav: Rc<RefCell<Option<DirectionalNav>>>,
 }
 
 #[yeehaw_derive::impl_pane_basics_from(pane)]
@@ -319,22 +472,61 @@ impl ParentPaneOfSelectable {
     pub const KIND: &'static str = "parent_pane_of_selectable";
     pub const EV_SET_SELECTABILITY: &'static str = "set_selectability";
 
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![
-            (KB::KEY_ESC.into()),
-            (KB::KEY_TAB.into()),
-            (KB::KEY_BACKTAB.into()),
-        ])
+    pub const ACT_UNSELECT: KeyAction = KeyAction::new(
+        "parent_pane_of_selectable.unselect",
+        &[&[KeyPossibility::Key(KB::KEY_ESC)]],
+    );
+    pub const ACT_NEXT: KeyAction = KeyAction::new(
+        "parent_pane_of_selectable.next",
+        &[&[KeyPossibility::Key(KB::KEY_TAB)]],
+    );
+    pub const ACT_PREV: KeyAction = KeyAction::new(
+        "parent_pane_of_selectable.prev",
+        &[&[KeyPossibility::Key(KB::KEY_BACKTAB)]],
+    );
+
+    pub const KEY_ACTIONS: &'static [KeyAction] =
+        &[Self::ACT_UNSELECT, Self::ACT_NEXT, Self::ACT_PREV];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
 
     pub fn new(ctx: &Context) -> ParentPaneOfSelectable {
         let pane = ParentPane::new(ctx, Self::KIND)
-            .with_focused_receivable_events(Self::default_receivable_events());
+            .with_focused_receivable_events(Self::default_receivable_events(ctx));
         ParentPaneOfSelectable {
             pane,
             selected: Rc::new(RefCell::new(None)),
             selectables: Rc::new(RefCell::new(vec![])),
+            directional_nav: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// enable directional focus navigation between the selectable elements of this pane
+    pub fn with_directional_nav(self, ctx: &Context, nav: DirectionalNav) -> Self {
+        self.set_directional_nav(ctx, Some(nav));
+        self
+    }
+
+    /// set (or disable with None) directional focus navigation, the navigation keys are
+    /// registered as receivable events of this pane
+    pub fn set_directional_nav(&self, ctx: &Context, nav: Option<DirectionalNav>) {
+        let mut rec = self.pane.pane.rec_evs_focused.borrow_mut();
+        if let Some(old) = self.directional_nav.borrow().as_ref() {
+            for ev in old.receivable_events() {
+                rec.remove(ev);
+            }
         }
+        if let Some(nav) = &nav {
+            // preserve the tab and esc keys if they overlap with the navigation keys
+            for ev in Self::default_receivable_events(ctx).0 {
+                rec.remove(ev);
+            }
+            rec.extend(nav.receivable_events());
+            rec.extend(Self::default_receivable_events(ctx).0);
+        }
+        *self.directional_nav.borrow_mut() = nav;
     }
 
     pub fn add_element(&self, el: Box<dyn Element>) {
@@ -564,6 +756,64 @@ impl ParentPaneOfSelectable {
         None
     }
 
+    /// the nearest ready widget in the provided direction from the starting element
+    fn directional_ready_el_id(
+        &self, nav: &DirectionalNav, dir: NavDirection, starting_el_id: Option<ElementID>,
+    ) -> Option<ElementID> {
+        let Some(starting_el_id) = starting_el_id else {
+            return self.next_ready_el_id(None);
+        };
+        let dr = DrawRegion::default().with_size(*self.pane.pane.get_last_size());
+        let region = |el_id: &ElementID| {
+            let loc = self.pane.eo.get_location(el_id)?;
+            Some((
+                loc.get_start_x(&dr),
+                loc.get_end_x(&dr),
+                loc.get_start_y(&dr),
+                loc.get_end_y(&dr),
+            ))
+        };
+        let from = region(&starting_el_id)?;
+
+        let mut best: Option<(f64, ElementID)> = None;
+        for el_id in self.selectables.borrow().iter() {
+            if *el_id == starting_el_id {
+                continue;
+            }
+            let visible = self
+                .pane
+                .eo
+                .get_element_details(el_id)
+                .is_some_and(|d| *d.vis.borrow());
+            let ready = !matches!(
+                self.get_selectability_for_el(el_id),
+                None | Some(Selectability::Unselectable)
+            );
+            if !visible || !ready {
+                continue;
+            }
+            let Some(to) = region(el_id) else {
+                continue;
+            };
+            let Some(dist) = nav.distance(dir, from, to) else {
+                continue;
+            };
+            if best.as_ref().is_none_or(|(d, _)| dist < *d) {
+                best = Some((dist, el_id.clone()));
+            }
+        }
+        best.map(|(_, el_id)| el_id)
+    }
+
+    /// move the selection to the nearest selectable element in the provided direction. Returns
+    /// None if directional navigation is disabled or there is no element in that direction.
+    pub fn switch_in_direction(&self, ctx: &Context, dir: NavDirection) -> Option<EventResponses> {
+        let nav = self.directional_nav.borrow().clone()?;
+        let el_id = self.selected.borrow().clone();
+        let new_el_id = self.directional_ready_el_id(&nav, dir, el_id.clone())?;
+        Some(self.switch_between_els(ctx, el_id, Some(new_el_id)))
+    }
+
     pub fn switch_to_next_widget(&self, ctx: &Context) -> EventResponses {
         let el_id = self.selected.borrow().clone();
         self.switch_between_els(ctx, el_id.clone(), self.next_ready_el_id(el_id))
@@ -584,24 +834,39 @@ impl Element for ParentPaneOfSelectable {
                 let (mut captured, mut resps) = self.pane.receive_event(ctx, ev);
 
                 if !captured && !ke.is_empty() {
+                    let km = &ctx.keymap;
                     match true {
-                        _ if ke[0] == KB::KEY_ESC => {
+                        _ if km.matches(&Self::ACT_UNSELECT, &ke) => {
                             //debug!("esc, about to unselect: {:?}", self.selected.borrow());
                             let resps_ = self.unselect_selected(ctx);
                             resps.extend(resps_);
                             captured = true;
                         }
-                        _ if ke[0] == KB::KEY_TAB => {
+                        _ if km.matches(&Self::ACT_NEXT, &ke) => {
                             let resps_ = self.switch_to_next_widget(ctx);
                             resps.extend(resps_);
                             captured = true;
                         }
-                        _ if ke[0] == KB::KEY_BACKTAB => {
+                        _ if km.matches(&Self::ACT_PREV, &ke) => {
                             let resps_ = self.switch_to_prev_widget(ctx);
                             resps.extend(resps_);
                             captured = true;
                         }
-                        _ => {}
+                        _ => {
+                            let dir = self
+                                .directional_nav
+                                .borrow()
+                                .as_ref()
+                                .and_then(|nav| nav.direction_for(&ke[0]));
+                            // NOTE only capture if the selection moved so that the key may
+                            // continue on to other elements
+                            if let Some(resps_) =
+                                dir.and_then(|dir| self.switch_in_direction(ctx, dir))
+                            {
+                                resps.extend(resps_);
+                                captured = true;
+                            }
+                        }
                     }
                 }
                 (captured, resps)
@@ -4,7 +4,9 @@
 use {
     crate::*,
     compact_str::CompactString,
-    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind},
+    crossterm::event::{
+        KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind,
+    },
     portable_pty::{native_pty_system, ChildKiller, CommandBuilder, MasterPty, PtySize},
     std::{
         io::{BufWriter, Read, Write},
@@ -79,6 +81,7 @@ impl TerminalPane {
 
         let mut reader = pty_pair.master.try_clone_reader()?;
         let parser_ = parser.clone();
+        let ev_tx_ = ctx.ev_tx.clone();
 
         spawn_blocking(move || {
             let mut processed_buf = Vec::new();
@@ -95,9 +98,14 @@ impl TerminalPane {
                     break;
                 };
                 parser.process(&processed_buf);
+                drop(parser);
 
                 // Clear the processed portion of the buffer
                 processed_buf.clear();
+
+                // wake the tui to draw the output, should the channel be full the tui is already
+                // awake and will draw it regardless
+                let _ = ev_tx_.try_send(Event::Redraw);
             }
             Ok::<(), Error>(())
         });
@@ -127,7 +135,7 @@ impl TerminalPane {
     }
 
     pub fn focused_rec_evs(&self) -> ReceivableEvents {
-        vec![(KeyPossibility::Anything.into())].into()
+        vec![KeyPossibility::Anything.into(), ReceivableEvent::Paste].into()
     }
 
     pub fn always_rec_evs(&self) -> ReceivableEvents {
@@ -197,57 +205,15 @@ impl TerminalPane {
     }
 
     pub fn handle_pane_key_event(&self, key: &KeyEvent) -> bool {
-        let input_bz = match key.code {
-            KeyCode::Char(ch) => {
-                let mut send = vec![ch as u8];
-                let upper = ch.to_ascii_uppercase();
-                if key.modifiers == KeyModifiers::CONTROL {
-                    match upper {
-                        // https:///github.com/fyne-io/terminal/blob/master/input.go
-                        // https:///gist.github.com/ConnerWill/d4b6c776b509add763e17f9f113fd25b
-                        '2' | '@' | ' ' => send = vec![0],
-                        '3' | '[' => send = vec![27],
-                        '4' | '\\' => send = vec![28],
-                        '5' | ']' => send = vec![29],
-                        '6' | '^' => send = vec![30],
-                        '7' | '-' | '_' => send = vec![31],
-                        char if ('A'..='_').contains(&char) => {
-                            // Since A == 65,
-                            // we can safely subtract 64 to get the corresponding control character
-                            let ascii_val = char as u8;
-                            let ascii_to_send = ascii_val - 64;
-                            send = vec![ascii_to_send];
-                        }
-                        _ => {}
-                    }
-                }
-                send
+        let app_cursor = match self.parser.read() {
+            Ok(parser) => parser.screen().application_cursor(),
+            Err(e) => {
+                log_err!("TerminalPane: failed to read parser: {}", e);
+                false
             }
-
-            #[cfg(unix)]
-            KeyCode::Enter => vec![b'\r'],
-            #[cfg(windows)]
-            KeyCode::Enter => vec![b'\r', b'\n'],
-
-            //KeyCode::Backspace => vec![8],
-            // 0x7F is the ASCII code for delete, but seems to work as backspace for crossterm
-            // TODO possibly use 8 for windows?
-            KeyCode::Backspace => vec![0x7F],
-
-            KeyCode::Left => vec![27, 91, 68],
-            KeyCode::Right => vec![27, 91, 67],
-            KeyCode::Up => vec![27, 91, 65],
-            KeyCode::Down => vec![27, 91, 66],
-            KeyCode::Tab => vec![9],
-            KeyCode::Home => vec![27, 91, 72],
-            KeyCode::End => vec![27, 91, 70],
-            KeyCode::PageUp => vec![27, 91, 53, 126],
-            KeyCode::PageDown => vec![27, 91, 54, 126],
-            KeyCode::BackTab => vec![27, 91, 90],
-            KeyCode::Delete => vec![27, 91, 51, 126],
-            KeyCode::Insert => vec![27, 91, 50, 126],
-            KeyCode::Esc => vec![27],
-            _ => return true, // ignore key but still capture
+        };
+        let Some(input_bz) = encode_key(key, app_cursor) else {
+            return true; // ignore key but still capture
         };
 
         // if there is an error here, the pty has been closed, therefor do not capture the event. this
@@ -261,6 +227,28 @@ impl TerminalPane {
         }
         true
     }
+
+    /// forward pasted text to the pty in one write, wrapped in the bracketed paste markers when
+    /// the child has requested bracketed paste
+    pub fn handle_pane_paste(&self, text: &str) -> bool {
+        let bracketed = match self.parser.read() {
+            Ok(parser) => parser.screen().bracketed_paste(),
+            Err(e) => {
+                log_err!("TerminalPane: failed to read parser: {}", e);
+                return false;
+            }
+        };
+        let input = if bracketed { format!("\x1b[200~{text}\x1b[201~") } else { text.to_string() };
+        if self
+            .writer
+            .borrow_mut()
+            .write_all(input.as_bytes())
+            .is_err()
+        {
+            return false;
+        }
+        self.writer.borrow_mut().flush().is_ok()
+    }
 }
 
 #[yeehaw_derive::impl_element_from(pane)]
@@ -275,6 +263,7 @@ impl Element for TerminalPane {
                 let captured = self.handle_pane_mouse_event(mouse);
                 (captured, EventResponses::default())
             }
+            Event::Paste(ref text) => (self.handle_pane_paste(text), EventResponses::default()),
             Event::Resize => {
                 // resize will be handled in the drawing function
                 (false, EventResponses::default())
@@ -405,6 +394,97 @@ impl Element for TerminalPane {
     }
 }
 
+/// Encode a key event as the bytes a terminal would send to its child (xterm style). Key releases
+/// and keys without a legacy encoding return None. Keys which are only distinguishable with the
+/// kitty keyboard protocol (ctrl+i and tab, ctrl+backspace) are re-encoded as a legacy terminal
+/// would send them. app_cursor is whether the child has enabled application cursor keys (DECCKM).
+pub fn encode_key(key: &KeyEvent, app_cursor: bool) -> Option<Vec<u8>> {
+    if key.kind == KeyEventKind::Release {
+        return None;
+    }
+    let mods = key.modifiers;
+    let ctrl = mods.contains(KeyModifiers::CONTROL);
+    let alt = mods.contains(KeyModifiers::ALT);
+    let shift = mods.contains(KeyModifiers::SHIFT);
+
+    // xterm modifier parameter
+    let m = 1 + shift as u8 + 2 * alt as u8 + 4 * ctrl as u8;
+    // CSI sequences ending in a letter (cursor keys, home/end, f1-f4)
+    let letter = |l: char, ss3: bool| {
+        if m > 1 {
+            format!("\x1b[1;{m}{l}")
+        } else if ss3 {
+            format!("\x1bO{l}")
+        } else {
+            format!("\x1b[{l}")
+        }
+        .into_bytes()
+    };
+    // CSI sequences ending in a tilde (pgup, delete, f5 and above)
+    let tilde =
+        |n: u8| if m > 1 { format!("\x1b[{n};{m}~") } else { format!("\x1b[{n}~") }.into_bytes();
+    // the alt modifier of legacy keys is sent as an escape prefix
+    let alt_prefixed = |mut bz: Vec<u8>| {
+        if alt {
+            bz.insert(0, 27);
+        }
+        bz
+    };
+
+    let bz = match key.code {
+        KeyCode::Char(ch) => {
+            let mut send = ch.to_string().into_bytes();
+            if ctrl {
+                // https:///github.com/fyne-io/terminal/blob/master/input.go
+                // https:///gist.github.com/ConnerWill/d4b6c776b509add763e17f9f113fd25b
+                match ch.to_ascii_uppercase() {
+                    '2' | '@' | ' ' => send = vec![0],
+                    '3' | '[' => send = vec![27],
+                    '4' | '\\' => send = vec![28],
+                    '5' | ']' => send = vec![29],
+                    '6' | '^' => send = vec![30],
+                    '7' | '-' | '_' => send = vec![31],
+                    '8' | '?' => send = vec![0x7F],
+                    // Since A == 65, we can safely subtract 64 to get the corresponding control
+                    // character
+                    c if ('A'..='_').contains(&c) => send = vec![c as u8 - 64],
+                    _ => {}
+                }
+            }
+            alt_prefixed(send)
+        }
+
+        #[cfg(unix)]
+        KeyCode::Enter => alt_prefixed(vec![b'\r']),
+        #[cfg(windows)]
+        KeyCode::Enter => alt_prefixed(vec![b'\r', b'\n']),
+
+        // 0x7F is the ASCII code for delete, but seems to work as backspace for crossterm, a
+        // control backspace is sent as ctrl+h
+        KeyCode::Backspace if ctrl => alt_prefixed(vec![8]),
+        KeyCode::Backspace => alt_prefixed(vec![0x7F]),
+        KeyCode::Tab if shift => b"\x1b[Z".to_vec(),
+        KeyCode::Tab => alt_prefixed(vec![9]),
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Esc => alt_prefixed(vec![27]),
+
+        KeyCode::Up => letter('A', app_cursor),
+        KeyCode::Down => letter('B', app_cursor),
+        KeyCode::Right => letter('C', app_cursor),
+        KeyCode::Left => letter('D', app_cursor),
+        KeyCode::Home => letter('H', app_cursor),
+        KeyCode::End => letter('F', app_cursor),
+        KeyCode::Insert => tilde(2),
+        KeyCode::Delete => tilde(3),
+        KeyCode::PageUp => tilde(5),
+        KeyCode::PageDown => tilde(6),
+        KeyCode::F(n @ 1..=4) => letter((b'P' + n - 1) as char, true),
+        KeyCode::F(n @ 5..=12) => tilde([15, 17, 18, 19, 20, 21, 23, 24][n as usize - 5]),
+        _ => return None,
+    };
+    Some(bz)
+}
+
 // this function takes a MouseEvent and returns the bytes that represent the mouse input.
 pub fn create_csi_sgr_mouse(ev: &MouseEvent) -> Vec<u8> {
     let mut kind = ev.kind;
@@ -88,13 +88,25 @@ pub type SelectFn = Box<dyn FnMut(Context, &ArbSelector, usize) -> EventResponse
 impl ArbSelector {
     const KIND: &'static str = "arb_selector";
 
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![
-            (KB::KEY_LEFT.into()),
-            (KB::KEY_RIGHT.into()),
-            (KB::KEY_H.into()),
-            (KB::KEY_L.into()),
-        ])
+    pub const ACT_LEFT: KeyAction = KeyAction::new(
+        "arb_selector.left",
+        &[
+            &[KeyPossibility::Key(KB::KEY_LEFT)],
+            &[KeyPossibility::Key(KB::KEY_H)],
+        ],
+    );
+    pub const ACT_RIGHT: KeyAction = KeyAction::new(
+        "arb_selector.right",
+        &[
+            &[KeyPossibility::Key(KB::KEY_RIGHT)],
+            &[KeyPossibility::Key(KB::KEY_L)],
+        ],
+    );
+
+    pub const KEY_ACTIONS: &'static [KeyAction] = &[Self::ACT_LEFT, Self::ACT_RIGHT];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
 
     /// Create a new ArbSelector with the given drawing style and base drawing
@@ -190,8 +202,8 @@ impl ArbSelector {
         let min_pos = min_pos.unwrap_or(0);
 
         let pane = SelectablePane::new(ctx, Self::KIND)
-            .with_focused_receivable_events(Self::default_receivable_events())
-            .with_styles(SelStyles::opaque(ctx))
+            .with_focused_receivable_events(Self::default_receivable_events(ctx))
+            .with_theme_styles(ctx, SelStyles::opaque(ctx))
             .with_dyn_width(DynVal::new_fixed(base_width as i32))
             .with_dyn_height(DynVal::new_fixed(base_height as i32));
 
@@ -375,13 +387,14 @@ impl Element for ArbSelector {
                 if sel != Selectability::Selected || ke.is_empty() {
                     return (false, resps);
                 }
+                let km = &ctx.keymap;
                 match true {
-                    _ if ke[0] == KB::KEY_LEFT || ke[0] == KB::KEY_H => {
+                    _ if km.matches(&Self::ACT_LEFT, &ke) => {
                         let resps_ = self.decrement_position(ctx);
                         resps.extend(resps_);
                         return (true, resps);
                     }
-                    _ if ke[0] == KB::KEY_RIGHT || ke[0] == KB::KEY_L => {
+                    _ if km.matches(&Self::ACT_RIGHT, &ke) => {
                         let resps_ = self.increment_position(ctx);
                         resps.extend(resps_);
                         return (true, resps);
@@ -437,7 +450,8 @@ impl Element for ArbSelector {
         (false, resps)
     }
     fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
-        if self.is_dirty.replace(false) || force_update {
+        let restyled = self.pane.refresh_theme();
+        if self.is_dirty.replace(false) || restyled || force_update {
             self.update_content(ctx);
         }
         self.pane.drawing(ctx, dr, force_update)
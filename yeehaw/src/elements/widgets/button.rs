@@ -1,6 +1,7 @@
 use {
     crate::{Keyboard as KB, *},
     crossterm::event::{MouseButton, MouseEventKind},
+    std::time::Duration,
 };
 
 /// TODO DynVal button width
@@ -15,6 +16,10 @@ pub struct Button {
     /// function which executes when button moves from pressed -> unpressed
     #[allow(clippy::type_complexity)]
     pub clicked_fn: Rc<RefCell<ButtonFn>>,
+    /// how long the button is displayed as depressed after being clicked with Enter
+    pub enter_flash: Rc<RefCell<Duration>>,
+    /// the pending scheduled event which releases the button after an Enter flash
+    pub flash_handle: Rc<RefCell<Option<ScheduleHandle>>>,
 }
 
 pub type ButtonFn = Box<dyn FnMut(Button, Context) -> EventResponses>;
@@ -114,20 +119,31 @@ impl ButtonMicroShadow {
 impl Button {
     const KIND: &'static str = "button";
 
+    /// the event key of the scheduled event which releases the button after an Enter flash
+    pub const EV_RELEASE: &'static str = "button_release";
+
+    pub const DEFAULT_ENTER_FLASH: Duration = Duration::from_millis(150);
+
     const STYLE: SelStyles = SelStyles {
         selected_style: Style::new_const(Color::BLACK, Color::LIGHT_YELLOW2),
         ready_style: Style::new_const(Color::BLACK, Color::WHITE),
         unselectable_style: Style::new_const(Color::BLACK, Color::GREY15),
     };
 
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![(KB::KEY_ENTER.into())]) // when "active" hitting enter will click the button
+    /// when "active" hitting enter will click the button
+    pub const ACT_CLICK: KeyAction =
+        KeyAction::new("button.click", &[&[KeyPossibility::Key(KB::KEY_ENTER)]]);
+
+    pub const KEY_ACTIONS: &'static [KeyAction] = &[Self::ACT_CLICK];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
 
     pub fn new(ctx: &Context, text: &str) -> Self {
         let pane = SelectablePane::new(ctx, Self::KIND)
-            .with_focused_receivable_events(Self::default_receivable_events())
-            .with_styles(Self::STYLE);
+            .with_focused_receivable_events(Self::default_receivable_events(ctx))
+            .with_theme_styles(ctx, Self::STYLE);
 
         let b = Button {
             pane,
@@ -135,6 +151,8 @@ impl Button {
             button_style: Rc::new(RefCell::new(ButtonStyle::Shadow(ButtonShadow::default()))),
             clicked_down: Rc::new(RefCell::new(false)),
             clicked_fn: Rc::new(RefCell::new(Box::new(|_, _| EventResponses::default()))),
+            enter_flash: Rc::new(RefCell::new(Self::DEFAULT_ENTER_FLASH)),
+            flash_handle: Rc::new(RefCell::new(None)),
         };
 
         let d = b.button_drawing(ctx);
@@ -285,6 +303,17 @@ impl Button {
         *self.clicked_fn.borrow_mut() = f;
     }
 
+    /// set how long the button is displayed as depressed after being clicked with Enter, a zero
+    /// duration disables the flash
+    pub fn with_enter_flash(self, dur: Duration) -> Self {
+        self.set_enter_flash(dur);
+        self
+    }
+
+    pub fn set_enter_flash(&self, dur: Duration) {
+        *self.enter_flash.borrow_mut() = dur;
+    }
+
     pub fn with_styles(self, ctx: &Context, styles: SelStyles) -> Self {
         self.pane.set_styles(styles);
         self.pane.set_content(self.button_drawing(ctx));
@@ -351,6 +380,29 @@ impl Button {
     pub fn click(&self, ctx: &Context) -> EventResponses {
         (self.clicked_fn.borrow_mut())(self.clone(), ctx.clone())
     }
+
+    /// display the button as depressed and schedule its release
+    fn flash(&self, ctx: &Context) -> EventResponses {
+        let dur = *self.enter_flash.borrow();
+        if dur.is_zero() {
+            return EventResponses::default();
+        }
+        if let Some(h) = self.flash_handle.borrow_mut().take() {
+            h.cancel();
+        }
+        *self.clicked_down.borrow_mut() = true;
+        self.pane.set_content(self.button_drawing(ctx));
+        let se = ScheduledEvent::after(dur, Event::Custom(Self::EV_RELEASE.to_string(), vec![]));
+        *self.flash_handle.borrow_mut() = Some(se.handle());
+        se.into()
+    }
+
+    /// release the button after an Enter flash
+    fn release(&self, ctx: &Context) {
+        self.flash_handle.borrow_mut().take();
+        *self.clicked_down.borrow_mut() = false;
+        self.pane.set_content(self.button_drawing(ctx));
+    }
 }
 
 #[yeehaw_derive::impl_element_from(pane)]
@@ -360,6 +412,19 @@ impl Element for Button {
         if captured {
             return (true, resps);
         }
+        match ev {
+            Event::Custom(ref key, _) if key == Self::EV_RELEASE => {
+                self.release(ctx);
+                return (true, resps);
+            }
+            Event::Exit => {
+                if let Some(h) = self.flash_handle.borrow_mut().take() {
+                    h.cancel();
+                }
+                return (false, resps);
+            }
+            _ => {}
+        }
         if self.pane.get_selectability() == Selectability::Unselectable {
             return (false, resps);
         }
@@ -368,7 +433,8 @@ impl Element for Button {
                 if self.pane.get_selectability() != Selectability::Selected || ke.is_empty() {
                     return (false, resps);
                 }
-                if ke[0] == KB::KEY_ENTER {
+                if ctx.keymap.matches(&Self::ACT_CLICK, &ke) {
+                    resps.extend(self.flash(ctx));
                     let resps_ = self.click(ctx);
                     resps.extend(resps_);
                     self.pane.set_content(self.button_drawing(ctx));
@@ -34,14 +34,20 @@ impl Checkbox {
             .with_attrs(Attributes::new().with_bold()),
     };
 
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![(KB::KEY_ENTER.into())]) // when "active" hitting enter will click the button
+    /// when "active" hitting enter will click the checkbox
+    pub const ACT_CLICK: KeyAction =
+        KeyAction::new("checkbox.click", &[&[KeyPossibility::Key(KB::KEY_ENTER)]]);
+
+    pub const KEY_ACTIONS: &'static [KeyAction] = &[Self::ACT_CLICK];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
 
     pub fn new(ctx: &Context) -> Self {
         let pane = SelectablePane::new(ctx, Self::KIND)
-            .with_focused_receivable_events(Self::default_receivable_events())
-            .with_styles(Self::STYLE)
+            .with_focused_receivable_events(Self::default_receivable_events(ctx))
+            .with_theme_styles(ctx, Self::STYLE)
             .with_dyn_width(DynVal::new_fixed(1))
             .with_dyn_height(DynVal::new_fixed(1));
         let cb = Checkbox {
@@ -122,7 +128,7 @@ impl Element for Checkbox {
                 if self.pane.get_selectability() != Selectability::Selected || ke.is_empty() {
                     return (false, EventResponses::default());
                 }
-                if ke[0] == KB::KEY_ENTER {
+                if ctx.keymap.matches(&Self::ACT_CLICK, &ke) {
                     let resps_ = self.click(ctx);
                     resps.extend(resps_);
                     return (true, resps);
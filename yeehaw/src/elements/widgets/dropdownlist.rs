@@ -32,9 +32,6 @@ pub struct DropdownList {
     pub selection_made_fn: Rc<RefCell<DropdownListFn>>,
     /// embedded scrollbar in dropdown list
     pub scrollbar: VerticalScrollbar,
-
-    /// if true, then the content should be updated on next drawing
-    pub dirty: Rc<RefCell<bool>>,
 }
 
 pub type DropdownListFn = Box<dyn FnMut(Context, String) -> EventResponses>;
@@ -58,15 +55,42 @@ impl DropdownList {
     /// if widgets overlap
     const Z_INDEX: ZIndex = 101;
 
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![
-            (KB::KEY_ENTER.into()),
-            (KB::KEY_DOWN.into()),
-            (KB::KEY_UP.into()),
-            (KB::KEY_K.into()),
-            (KB::KEY_J.into()),
-            (KB::KEY_SPACE.into()),
-        ])
+    /// open the dropdown, or select the entry under the cursor when open
+    pub const ACT_SELECT: KeyAction = KeyAction::new(
+        "dropdownlist.select",
+        &[&[KeyPossibility::Key(KB::KEY_ENTER)]],
+    );
+    /// open the dropdown, or move the cursor down when open
+    pub const ACT_CURSOR_DOWN: KeyAction = KeyAction::new(
+        "dropdownlist.cursor_down",
+        &[
+            &[KeyPossibility::Key(KB::KEY_DOWN)],
+            &[KeyPossibility::Key(KB::KEY_J)],
+        ],
+    );
+    /// open the dropdown, or move the cursor up when open
+    pub const ACT_CURSOR_UP: KeyAction = KeyAction::new(
+        "dropdownlist.cursor_up",
+        &[
+            &[KeyPossibility::Key(KB::KEY_UP)],
+            &[KeyPossibility::Key(KB::KEY_K)],
+        ],
+    );
+    /// jump the scrollbar forward a page when open
+    pub const ACT_PAGE_DOWN: KeyAction = KeyAction::new(
+        "dropdownlist.page_down",
+        &[&[KeyPossibility::Key(KB::KEY_SPACE)]],
+    );
+
+    pub const KEY_ACTIONS: &'static [KeyAction] = &[
+        Self::ACT_SELECT,
+        Self::ACT_CURSOR_DOWN,
+        Self::ACT_CURSOR_UP,
+        Self::ACT_PAGE_DOWN,
+    ];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
 
     pub fn new<S: Into<String>>(
@@ -74,8 +98,8 @@ impl DropdownList {
         selection_made_fn: Box<dyn FnMut(Context, String) -> EventResponses>,
     ) -> Self {
         let pane = SelectablePane::new(ctx, Self::KIND)
-            .with_focused_receivable_events(Self::default_receivable_events())
-            .with_styles(Self::STYLE)
+            .with_focused_receivable_events(Self::default_receivable_events(ctx))
+            .with_theme_styles(ctx, Self::STYLE)
             .with_dyn_height(DynVal::new_fixed(1))
             .with_z(Self::Z_INDEX);
 
@@ -88,6 +112,9 @@ impl DropdownList {
         *sb.position_changed_hook.borrow_mut() = Some(hook);
 
         let entries = entries.into_iter().map(|s| s.into()).collect();
+        let cursor_style = pane
+            .theme_styles()
+            .style("dropdownlist.cursor", Self::STYLE_DD_CURSOR);
 
         let d = DropdownList {
             pane,
@@ -99,11 +126,10 @@ impl DropdownList {
             open: Rc::new(RefCell::new(false)),
             max_expanded_height: Rc::new(RefCell::new(10)),
             dropdown_arrow: Rc::new(RefCell::new(Self::DEFAULT_DROPDOWN_ARROW)),
-            cursor_style: Rc::new(RefCell::new(Self::STYLE_DD_CURSOR)),
+            cursor_style,
             clicked_down: Rc::new(RefCell::new(false)),
             selection_made_fn: Rc::new(RefCell::new(selection_made_fn)),
             scrollbar: sb,
-            dirty: Rc::new(RefCell::new(true)),
         };
         d.pane.set_dyn_width(d.calculate_dyn_width());
 
@@ -113,9 +139,11 @@ impl DropdownList {
                 if d_.pane.get_selectability() != Selectability::Selected && *d_.open.borrow() {
                     d_.perform_close_escape();
                 }
-                d_.dirty.replace(true);
+                d_.pane.set_dirty();
             }));
-
+        let d_ = d.clone();
+        d.pane
+            .set_update_dirty_content(Box::new(move |_, dr| d_.update_content(dr)));
         d
     }
 
@@ -170,7 +198,7 @@ impl DropdownList {
 
     #[must_use]
     pub fn set_selected(&self, ctx: &Context, idx: usize) -> EventResponses {
-        self.dirty.replace(true);
+        self.pane.set_dirty();
         *self.selected.borrow_mut() = idx;
         (self.selection_made_fn.borrow_mut())(
             ctx.clone(),
@@ -180,7 +208,7 @@ impl DropdownList {
 
     #[must_use]
     pub fn set_selected_str(&self, ctx: &Context, s: &str) -> EventResponses {
-        self.dirty.replace(true);
+        self.pane.set_dirty();
         let idx = self.entries.borrow().iter().position(|r| r == s);
         if let Some(idx) = idx {
             *self.selected.borrow_mut() = idx;
@@ -203,7 +231,7 @@ impl DropdownList {
 
     pub fn set_entries<S: Into<String>>(&self, entries: Vec<S>) {
         *self.entries.borrow_mut() = entries.into_iter().map(|s| s.into()).collect();
-        self.dirty.replace(true);
+        self.pane.set_dirty();
     }
 
     // ----------------------------------------------
@@ -277,7 +305,7 @@ impl DropdownList {
     }
 
     pub fn perform_open(&self) {
-        self.dirty.replace(true);
+        self.pane.set_dirty();
         *self.open.borrow_mut() = true;
         *self.cursor.borrow_mut() = *self.selected.borrow();
         let h = self.expanded_height();
@@ -289,7 +317,7 @@ impl DropdownList {
     }
 
     pub fn perform_close_escape(&self) {
-        self.dirty.replace(true);
+        self.pane.set_dirty();
         *self.open.borrow_mut() = false;
         // NOTE we are using the default context here, as we know
         // that a content_y_offset of 0 is safe. a lil' hacky
@@ -300,7 +328,7 @@ impl DropdownList {
     }
 
     pub fn perform_close(&self, ctx: &Context, escaped: bool) -> EventResponses {
-        self.dirty.replace(true);
+        self.pane.set_dirty();
         *self.open.borrow_mut() = false;
         self.pane.set_content_y_offset(None, 0);
         self.scrollbar
@@ -318,14 +346,14 @@ impl DropdownList {
     }
 
     pub fn cursor_up(&self) {
-        self.dirty.replace(true);
+        self.pane.set_dirty();
         if *self.cursor.borrow() > 0 {
             *self.cursor.borrow_mut() -= 1;
         }
     }
 
     pub fn cursor_down(&self) {
-        self.dirty.replace(true);
+        self.pane.set_dirty();
         if *self.cursor.borrow() < self.entries.borrow().len().saturating_sub(1) {
             *self.cursor.borrow_mut() += 1;
         }
@@ -389,32 +417,30 @@ impl Element for DropdownList {
                     return (false, resps);
                 }
                 let open = *self.open.borrow();
+                let km = &ctx.keymap;
+                let is = |act: &KeyAction| km.matches(act, &ke);
                 match true {
                     _ if !open
-                        && (ke[0] == KB::KEY_ENTER
-                            || ke[0] == KB::KEY_DOWN
-                            || ke[0] == KB::KEY_J
-                            || ke[0] == KB::KEY_UP
-                            || ke[0] == KB::KEY_K) =>
+                        && (is(&Self::ACT_SELECT)
+                            || is(&Self::ACT_CURSOR_DOWN)
+                            || is(&Self::ACT_CURSOR_UP)) =>
                     {
                         self.perform_open();
                         return (true, resps);
                     }
-                    _ if open && ke[0] == KB::KEY_ENTER => (true, self.perform_close(ctx, false)),
-                    _ if open && ke[0] == KB::KEY_DOWN || ke[0] == KB::KEY_J => {
+                    _ if open && is(&Self::ACT_SELECT) => (true, self.perform_close(ctx, false)),
+                    _ if open && is(&Self::ACT_CURSOR_DOWN) => {
                         self.cursor_down();
                         return (true, resps);
                     }
-                    _ if open && ke[0] == KB::KEY_UP || ke[0] == KB::KEY_K => {
+                    _ if open && is(&Self::ACT_CURSOR_UP) => {
                         self.cursor_up();
                         return (true, resps);
                     }
-                    _ if open && ke[0] == KB::KEY_SPACE => {
-                        let (captured, resps_) =
-                            self.scrollbar.receive_event(ctx, Event::KeyCombo(ke));
-                        resps.extend(resps_);
-                        self.dirty.replace(true);
-                        return (captured, resps);
+                    _ if open && is(&Self::ACT_PAGE_DOWN) => {
+                        self.scrollbar.page_forwards();
+                        self.pane.set_dirty();
+                        return (true, resps);
                     }
                     _ => return (false, resps),
                 };
@@ -464,7 +490,7 @@ impl Element for DropdownList {
 
                         // on arrow
                         if y == 0 && x == self.pane.get_width(&me.dr).saturating_sub(1) {
-                            self.dirty.replace(true);
+                            self.pane.set_dirty();
                             return (true, resps);
 
                         // on scrollbar
@@ -482,12 +508,12 @@ impl Element for DropdownList {
                                 resps.extend(resps_);
                                 return (captured, resps);
                             }
-                            self.dirty.replace(true);
+                            self.pane.set_dirty();
                             return (true, resps);
                         } else {
                             *self.cursor.borrow_mut() = y + self.pane.get_content_y_offset();
                         }
-                        self.dirty.replace(true);
+                        self.pane.set_dirty();
                         return (true, resps);
                     }
                     _ if open && clicked => {
@@ -503,7 +529,7 @@ impl Element for DropdownList {
                             let (captured, resps_) =
                                 self.scrollbar.receive_event(ctx, Event::Mouse(me_));
                             resps.extend(resps_);
-                            self.dirty.replace(true);
+                            self.pane.set_dirty();
                             return (captured, resps);
                         }
 
@@ -523,19 +549,11 @@ impl Element for DropdownList {
                 };
             }
             Event::Resize => {
-                self.dirty.replace(true);
+                self.pane.set_dirty();
                 return (true, resps);
             }
             _ => {}
         }
         (false, resps)
     }
-
-    fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
-        if *self.dirty.borrow() || force_update {
-            self.update_content(dr);
-            *self.dirty.borrow_mut() = false;
-        }
-        self.pane.drawing(ctx, dr, force_update)
-    }
 }
@@ -28,6 +28,7 @@ pub struct ListControl {
     pub shifting_allowed: Rc<RefCell<bool>>,
     pub duplicating_allowed: Rc<RefCell<bool>>,
     pub renaming_allowed: Rc<RefCell<bool>>,
+    pub copying_allowed: Rc<RefCell<bool>>,
     pub right_click_menu: Rc<RefCell<Option<RightClickMenu>>>,
     /// When true, list items are bottom‑justified within the allocated height.
     pub bottom_justified: Rc<RefCell<bool>>,
@@ -97,7 +98,7 @@ impl ListControl {
         let inner = ListControlInner::new(ctx, entries);
 
         let pane = SelectablePane::new(ctx, Self::KIND)
-            .with_styles(ListControlInner::STYLE)
+            .with_theme_styles(ctx, ListControlInner::STYLE)
             .with_dyn_width(DynVal::new_fixed(max_entry_width as i32))
             .with_dyn_height(DynVal::new_fixed(line_count));
         let parent = ParentPaneOfSelectable::new(ctx);
@@ -113,6 +114,7 @@ impl ListControl {
             shifting_allowed: Rc::new(RefCell::new(false)),
             duplicating_allowed: Rc::new(RefCell::new(false)),
             renaming_allowed: Rc::new(RefCell::new(false)),
+            copying_allowed: Rc::new(RefCell::new(false)),
             bottom_justified: Rc::new(RefCell::new(false)),
             right_click_menu: Rc::new(RefCell::new(None)),
             double_click_enabled: Rc::new(RefCell::new(false)),
@@ -198,6 +200,12 @@ impl ListControl {
         self
     }
 
+    /// adds a "Copy" item to the right click menu, see copy_to_clipboard
+    pub fn with_copying_allowed(self) -> Self {
+        *self.copying_allowed.borrow_mut() = true;
+        self
+    }
+
     /// When enabled, list items are rendered bottom‑justified within the allocated height.
     pub fn with_bottom_justified(self) -> Self {
         *self.bottom_justified.borrow_mut() = true;
@@ -316,6 +324,26 @@ impl ListControl {
                 ))),
             );
         }
+        if *self.copying_allowed.borrow() {
+            let inner = self.inner.clone();
+            rcm_entries.push(
+                MenuItem::new(ctx, MenuPath("Copy".to_string())).with_fn(Some(Box::new(
+                    move |ctx_inner| {
+                        let mut entry_i = None;
+                        let pos_bz = ctx_inner.get_metadata(RightClickMenu::MENU_POSITION_MD_KEY);
+                        if let Some(pos_bz) = pos_bz {
+                            if let Ok(pos) = serde_json::from_slice::<Point>(&pos_bz) {
+                                // adjust for listbox scrolling
+                                let y = pos.y + inner.borrow().pane.get_content_y_offset() as i32;
+                                entry_i = Some(y as usize);
+                            }
+                        }
+                        inner.borrow().copy_to_clipboard(&ctx_inner, entry_i);
+                        EventResponses::default()
+                    },
+                ))),
+            );
+        }
         // Add rename menu entry
         if *self.renaming_allowed.borrow() {
             let self_ = self.clone();
@@ -341,7 +369,8 @@ impl ListControl {
             );
         }
 
-        let rcm = RightClickMenu::new(ctx, MenuStyle::default()).with_menu_items(ctx, rcm_entries);
+        let rcm =
+            RightClickMenu::new(ctx, MenuStyle::themed(ctx)).with_menu_items(ctx, rcm_entries);
         *self.right_click_menu.borrow_mut() = Some(rcm);
     }
 
@@ -540,26 +569,62 @@ impl ListControlInner {
     const STYLE_CURSOR_OVER_UNSELECTED: Style = Style::new_const(Color::BLACK, Color::LIGHT_BLUE);
     const STYLE_CURSOR_OVER_SELECTED: Style = Style::new_const(Color::WHITE, Color::BLUE);
 
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![
-            (KB::KEY_ENTER.into()),
-            (KB::KEY_DOWN.into()),
-            (KB::KEY_UP.into()),
-            (KB::KEY_K.into()),
-            (KB::KEY_J.into()),
-            (KB::KEY_SPACE.into()),
-        ])
+    pub const ACT_TOGGLE_SELECTED: KeyAction = KeyAction::new(
+        "list_control.toggle_selected",
+        &[&[KeyPossibility::Key(KB::KEY_ENTER)]],
+    );
+    pub const ACT_CURSOR_DOWN: KeyAction = KeyAction::new(
+        "list_control.cursor_down",
+        &[
+            &[KeyPossibility::Key(KB::KEY_DOWN)],
+            &[KeyPossibility::Key(KB::KEY_J)],
+        ],
+    );
+    pub const ACT_CURSOR_UP: KeyAction = KeyAction::new(
+        "list_control.cursor_up",
+        &[
+            &[KeyPossibility::Key(KB::KEY_UP)],
+            &[KeyPossibility::Key(KB::KEY_K)],
+        ],
+    );
+    /// jump the scrollbar forward a page
+    pub const ACT_PAGE_DOWN: KeyAction = KeyAction::new(
+        "list_control.page_down",
+        &[&[KeyPossibility::Key(KB::KEY_SPACE)]],
+    );
+
+    pub const KEY_ACTIONS: &'static [KeyAction] = &[
+        Self::ACT_TOGGLE_SELECTED,
+        Self::ACT_CURSOR_DOWN,
+        Self::ACT_CURSOR_UP,
+        Self::ACT_PAGE_DOWN,
+    ];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
 
     pub fn new(init_ctx: &Context, entries: Vec<String>) -> Self {
         let max_lines_per_entry = entries.iter().map(|r| r.lines().count()).max().unwrap_or(0);
 
         let pane = SelectablePane::new(init_ctx, Self::KIND)
-            .with_focused_receivable_events(Self::default_receivable_events())
+            .with_focused_receivable_events(Self::default_receivable_events(init_ctx))
             .with_dyn_width(DynVal::FULL)
             .with_dyn_height(DynVal::FULL)
             .with_focused(true);
 
+        let item_selected_style = pane
+            .theme_styles()
+            .style("listcontrol.item_selected", Self::STYLE_ITEM_SELECTED);
+        let cursor_over_unselected_style = pane.theme_styles().style(
+            "listcontrol.cursor_over_unselected",
+            Self::STYLE_CURSOR_OVER_UNSELECTED,
+        );
+        let cursor_over_selected_style = pane.theme_styles().style(
+            "listcontrol.cursor_over_selected",
+            Self::STYLE_CURSOR_OVER_SELECTED,
+        );
+
         ListControlInner {
             pane,
             current_sty: Rc::new(RefCell::new(Style::default())),
@@ -570,9 +635,9 @@ impl ListControlInner {
             cursor: Rc::new(RefCell::new(None)),
             last_clicked_position: Rc::new(RefCell::new(None)),
             clicked_down: Rc::new(RefCell::new(false)),
-            item_selected_style: Rc::new(RefCell::new(Self::STYLE_ITEM_SELECTED)),
-            cursor_over_unselected_style: Rc::new(RefCell::new(Self::STYLE_CURSOR_OVER_UNSELECTED)),
-            cursor_over_selected_style: Rc::new(RefCell::new(Self::STYLE_CURSOR_OVER_SELECTED)),
+            item_selected_style,
+            cursor_over_unselected_style,
+            cursor_over_selected_style,
             selection_made_fn: Rc::new(RefCell::new(Box::new(|_, _| EventResponses::default()))),
             on_delete_fn: Rc::new(RefCell::new(Box::new(|_, _| EventResponses::default()))),
             on_create_entry_fn: Rc::new(RefCell::new(Box::new(|_, _| EventResponses::default()))),
@@ -954,6 +1019,28 @@ impl ListControlInner {
         out
     }
 
+    /// copy the selected entries (one per line) to the clipboard, or should there be no selection
+    /// the provided entry
+    pub fn copy_to_clipboard(&self, ctx: &Context, entry_i: Option<usize>) {
+        let entries = self.entries.borrow();
+        let mut selected = self.selected.borrow().clone();
+        if selected.is_empty() {
+            selected.extend(entry_i);
+        }
+        selected.sort();
+        let text = selected
+            .iter()
+            .filter_map(|i| entries.get(*i).cloned())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if text.is_empty() {
+            return;
+        }
+        if let Err(e) = ctx.clipboard.set_text(&text) {
+            log_err!("failed to set text to clipboard: {}", e);
+        }
+    }
+
     pub fn toggle_entry_selected_at_i(&self, ctx: &Context, i: usize) -> EventResponses {
         self.is_dirty.replace(true);
         let already_selected = self.selected.borrow().contains(&i);
@@ -1022,26 +1109,24 @@ impl Element for ListControlInner {
                 if ke.is_empty() {
                     return (false, resps);
                 }
+                let km = &ctx.keymap;
                 match true {
-                    _ if ke[0] == KB::KEY_SPACE => {
+                    _ if km.matches(&Self::ACT_PAGE_DOWN, &ke) => {
                         if let Some(sb) = self.scrollbar.borrow().as_ref() {
-                            let (captured, resps_) = sb.receive_event(ctx, Event::KeyCombo(ke));
-                            resps.extend(resps_);
+                            sb.page_forwards();
                             self.is_dirty.replace(true);
-                            return (captured, resps);
-                        } else {
-                            return (true, resps);
                         }
+                        return (true, resps);
                     }
-                    _ if ke[0] == KB::KEY_DOWN || ke[0] == KB::KEY_J => {
+                    _ if km.matches(&Self::ACT_CURSOR_DOWN, &ke) => {
                         let _ = self.cursor_down();
                         return (true, resps);
                     }
-                    _ if ke[0] == KB::KEY_UP || ke[0] == KB::KEY_K => {
+                    _ if km.matches(&Self::ACT_CURSOR_UP, &ke) => {
                         let _ = self.cursor_up();
                         return (true, resps);
                     }
-                    _ if ke[0] == KB::KEY_ENTER => {
+                    _ if km.matches(&Self::ACT_TOGGLE_SELECTED, &ke) => {
                         let Some(cursor) = *self.cursor.borrow() else {
                             return (true, resps);
                         };
@@ -1151,7 +1236,8 @@ impl Element for ListControlInner {
     }
 
     fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
-        if self.is_dirty.replace(false) || force_update {
+        let restyled = self.pane.refresh_theme();
+        if self.is_dirty.replace(false) || restyled || force_update {
             self.update_highlighting(dr);
             self.update_content(dr);
         }
@@ -44,7 +44,6 @@ pub struct ListBoxInner {
     pub cursor_over_unselected_style: Rc<RefCell<Style>>,
     pub cursor_over_selected_style: Rc<RefCell<Style>>,
     pub scrollbar: Rc<RefCell<Option<VerticalScrollbar>>>,
-    pub is_dirty: Rc<RefCell<bool>>,
 }
 
 pub type ListBoxFn = Box<dyn FnMut(Context, Vec<String>, Option<String>) -> EventResponses>;
@@ -89,7 +88,7 @@ impl ListBox {
         let inner = ListBoxInner::new(ctx, entries);
 
         let pane = SelectablePane::new(ctx, Self::KIND)
-            .with_styles(ListBoxInner::STYLE)
+            .with_theme_styles(ctx, ListBoxInner::STYLE)
             .with_dyn_width(DynVal::new_fixed(max_entry_width as i32))
             .with_dyn_height(DynVal::new_fixed(line_count));
         pane.pane.add_element(Box::new(inner.clone()));
@@ -107,7 +106,7 @@ impl ListBox {
                 }
                 *lb_.inner.borrow().selectedness.borrow_mut() = sel;
                 *lb_.inner.borrow().current_sty.borrow_mut() = lb_.pane.get_current_style();
-                *lb_.inner.borrow().is_dirty.borrow_mut() = true;
+                lb_.inner.borrow().pane.set_dirty();
             }));
         *lb.inner.borrow().current_sty.borrow_mut() = lb.pane.get_current_style();
         lb.inner.borrow().update_content(&DrawRegion::default()); // needed for the sb
@@ -128,7 +127,7 @@ impl ListBox {
 
     pub fn with_styles(self, styles: SelStyles) -> Self {
         self.pane.set_styles(styles);
-        self.inner.borrow().is_dirty.replace(true);
+        self.inner.borrow().pane.set_dirty();
         self
     }
 
@@ -189,13 +188,13 @@ impl ListBox {
         self.pane.set_dyn_height(DynVal::new_fixed(
             self.inner.borrow().entries.borrow().len() as i32 * lines as i32,
         ));
-        self.inner.borrow().is_dirty.replace(true);
+        self.inner.borrow().pane.set_dirty();
         self
     }
 
     pub fn with_selection_mode(self, mode: SelectionMode) -> Self {
         *self.inner.borrow().selection_mode.borrow_mut() = mode;
-        self.inner.borrow().is_dirty.replace(true);
+        self.inner.borrow().pane.set_dirty();
         self
     }
 
@@ -210,18 +209,18 @@ impl ListBox {
 
     pub fn with_dyn_width(self, width: DynVal) -> Self {
         self.pane.set_dyn_width(width);
-        self.inner.borrow().is_dirty.replace(true);
+        self.inner.borrow().pane.set_dirty();
         self
     }
     pub fn with_dyn_height(self, height: DynVal) -> Self {
         self.pane.set_dyn_height(height);
-        self.inner.borrow().is_dirty.replace(true);
+        self.inner.borrow().pane.set_dirty();
         self
     }
     pub fn with_size(self, width: DynVal, height: DynVal) -> Self {
         self.pane.set_dyn_width(width);
         self.pane.set_dyn_height(height);
-        self.inner.borrow().is_dirty.replace(true);
+        self.inner.borrow().pane.set_dirty();
         self
     }
 
@@ -244,27 +243,63 @@ impl ListBoxInner {
     const STYLE_CURSOR_OVER_UNSELECTED: Style = Style::new_const(Color::BLACK, Color::LIGHT_BLUE);
     const STYLE_CURSOR_OVER_SELECTED: Style = Style::new_const(Color::WHITE, Color::BLUE);
 
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![
-            (KB::KEY_ENTER.into()),
-            (KB::KEY_DOWN.into()),
-            (KB::KEY_UP.into()),
-            (KB::KEY_K.into()),
-            (KB::KEY_J.into()),
-            (KB::KEY_SPACE.into()),
-        ])
+    pub const ACT_TOGGLE_SELECTED: KeyAction = KeyAction::new(
+        "listbox.toggle_selected",
+        &[&[KeyPossibility::Key(KB::KEY_ENTER)]],
+    );
+    pub const ACT_CURSOR_DOWN: KeyAction = KeyAction::new(
+        "listbox.cursor_down",
+        &[
+            &[KeyPossibility::Key(KB::KEY_DOWN)],
+            &[KeyPossibility::Key(KB::KEY_J)],
+        ],
+    );
+    pub const ACT_CURSOR_UP: KeyAction = KeyAction::new(
+        "listbox.cursor_up",
+        &[
+            &[KeyPossibility::Key(KB::KEY_UP)],
+            &[KeyPossibility::Key(KB::KEY_K)],
+        ],
+    );
+    /// jump the scrollbar forward a page
+    pub const ACT_PAGE_DOWN: KeyAction = KeyAction::new(
+        "listbox.page_down",
+        &[&[KeyPossibility::Key(KB::KEY_SPACE)]],
+    );
+
+    pub const KEY_ACTIONS: &'static [KeyAction] = &[
+        Self::ACT_TOGGLE_SELECTED,
+        Self::ACT_CURSOR_DOWN,
+        Self::ACT_CURSOR_UP,
+        Self::ACT_PAGE_DOWN,
+    ];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
 
     pub fn new(init_ctx: &Context, entries: Vec<String>) -> Self {
         let max_lines_per_entry = entries.iter().map(|r| r.lines().count()).max().unwrap_or(0);
 
         let pane = Pane::new(init_ctx, Self::KIND)
-            .with_focused_receivable_events(Self::default_receivable_events())
+            .with_focused_receivable_events(Self::default_receivable_events(init_ctx))
             .with_dyn_width(DynVal::FULL)
             .with_dyn_height(DynVal::FULL)
             .with_focused(true);
 
-        ListBoxInner {
+        let item_selected_style = pane
+            .theme_styles()
+            .style("listbox.item_selected", Self::STYLE_ITEM_SELECTED);
+        let cursor_over_unselected_style = pane.theme_styles().style(
+            "listbox.cursor_over_unselected",
+            Self::STYLE_CURSOR_OVER_UNSELECTED,
+        );
+        let cursor_over_selected_style = pane.theme_styles().style(
+            "listbox.cursor_over_selected",
+            Self::STYLE_CURSOR_OVER_SELECTED,
+        );
+
+        let lb = ListBoxInner {
             pane,
             current_sty: Rc::new(RefCell::new(Style::default())),
             selectedness: Rc::new(RefCell::new(Selectability::Ready)),
@@ -276,13 +311,16 @@ impl ListBoxInner {
             clicked_down: Rc::new(RefCell::new(false)),
             selection_mode: Rc::new(RefCell::new(SelectionMode::NoLimit)),
             click_behavior: Rc::new(RefCell::new(ClickBehavior::default())),
-            item_selected_style: Rc::new(RefCell::new(Self::STYLE_ITEM_SELECTED)),
-            cursor_over_unselected_style: Rc::new(RefCell::new(Self::STYLE_CURSOR_OVER_UNSELECTED)),
-            cursor_over_selected_style: Rc::new(RefCell::new(Self::STYLE_CURSOR_OVER_SELECTED)),
+            item_selected_style,
+            cursor_over_unselected_style,
+            cursor_over_selected_style,
             selection_made_fn: Rc::new(RefCell::new(Box::new(|_, _, _| EventResponses::default()))),
             scrollbar: Rc::new(RefCell::new(None)),
-            is_dirty: Rc::new(RefCell::new(true)),
-        }
+        };
+        let lb_ = lb.clone();
+        lb.pane
+            .set_update_dirty_content(Box::new(move |_, dr| lb_.update_content(dr)));
+        lb
     }
 
     // ----------------------------------------------
@@ -335,7 +373,6 @@ impl ListBoxInner {
         if let Some(sb) = self.scrollbar.borrow().as_ref() {
             sb.external_change(y_offset, self.pane.content_height(), dr.size);
         }
-        self.is_dirty.replace(true);
     }
 
     pub fn get_item_index_for_view_y(&self, y: usize) -> usize {
@@ -352,7 +389,7 @@ impl ListBoxInner {
 
     pub fn set_entries(&self, entries: Vec<String>) {
         *self.entries.borrow_mut() = entries;
-        self.is_dirty.replace(true);
+        self.pane.set_dirty();
     }
 
     pub fn update_content(&self, dr: &DrawRegion) {
@@ -413,7 +450,7 @@ impl ListBoxInner {
 
     /// returns if the cursor was moved
     pub fn cursor_up(&self, ctx: &Context) -> bool {
-        self.is_dirty.replace(true);
+        self.pane.set_dirty();
         let cursor = *self.cursor.borrow();
         match cursor {
             Some(cursor) if cursor > 0 => {
@@ -439,7 +476,7 @@ impl ListBoxInner {
 
     /// returns if the cursor was moved
     pub fn cursor_down(&self, ctx: &Context) -> bool {
-        self.is_dirty.replace(true);
+        self.pane.set_dirty();
         let cursor = *self.cursor.borrow();
         match cursor {
             Some(cursor) if cursor < self.entries.borrow().len() - 1 => {
@@ -479,7 +516,7 @@ impl ListBoxInner {
     }
 
     pub fn toggle_entry_selected_at_i(&self, ctx: &Context, i: usize) -> EventResponses {
-        self.is_dirty.replace(true);
+        self.pane.set_dirty();
         let already_selected = self.selected.borrow().contains(&i);
 
         match *self.selection_mode.borrow() {
@@ -533,26 +570,24 @@ impl Element for ListBoxInner {
                 if ke.is_empty() {
                     return (false, resps);
                 }
+                let km = &ctx.keymap;
                 match true {
-                    _ if ke[0] == KB::KEY_SPACE => {
+                    _ if km.matches(&Self::ACT_PAGE_DOWN, &ke) => {
                         if let Some(sb) = self.scrollbar.borrow().as_ref() {
-                            let (captured, resps_) = sb.receive_event(ctx, Event::KeyCombo(ke));
-                            resps.extend(resps_);
-                            self.is_dirty.replace(true);
-                            return (captured, resps);
-                        } else {
-                            return (true, resps);
+                            sb.page_forwards();
+                            self.pane.set_dirty();
                         }
+                        return (true, resps);
                     }
-                    _ if ke[0] == KB::KEY_DOWN || ke[0] == KB::KEY_J => {
+                    _ if km.matches(&Self::ACT_CURSOR_DOWN, &ke) => {
                         let _ = self.cursor_down(ctx);
                         return (true, resps);
                     }
-                    _ if ke[0] == KB::KEY_UP || ke[0] == KB::KEY_K => {
+                    _ if km.matches(&Self::ACT_CURSOR_UP, &ke) => {
                         let _ = self.cursor_up(ctx);
                         return (true, resps);
                     }
-                    _ if ke[0] == KB::KEY_ENTER => {
+                    _ if km.matches(&Self::ACT_TOGGLE_SELECTED, &ke) => {
                         let Some(cursor) = *self.cursor.borrow() else {
                             return (true, resps);
                         };
@@ -612,7 +647,7 @@ impl Element for ListBoxInner {
                                     me_.row = y as i32 - 1;
                                     let (captured, resps_) =
                                         sb.receive_event(ctx, Event::Mouse(me_));
-                                    self.is_dirty.replace(true);
+                                    self.pane.set_dirty();
                                     resps.extend(resps_);
                                     return (captured, resps);
                                 }
@@ -636,7 +671,7 @@ impl Element for ListBoxInner {
                             ClickBehavior::SoftSelectFirst => {
                                 let current_cursor = *self.cursor.borrow();
                                 *self.cursor.borrow_mut() = Some(item_i);
-                                self.is_dirty.replace(true);
+                                self.pane.set_dirty();
                                 if current_cursor == Some(item_i) {
                                     let resps_ = self.toggle_entry_selected_at_i(ctx, item_i);
                                     resps.extend(resps_);
@@ -655,12 +690,4 @@ impl Element for ListBoxInner {
         }
         (false, resps)
     }
-
-    fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
-        if self.is_dirty.replace(false) || force_update {
-            self.update_highlighting(dr);
-            self.update_content(dr);
-        }
-        self.pane.drawing(ctx, dr, force_update)
-    }
 }
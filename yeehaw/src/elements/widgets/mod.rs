@@ -17,6 +17,8 @@ pub mod list_control;
 #[cfg(feature = "textbox")]
 pub mod textbox;
 #[cfg(feature = "textbox")]
+pub mod textbox_history;
+#[cfg(feature = "textbox")]
 pub mod textbox_numbers;
 #[cfg(feature = "textbox")]
 pub mod textbox_single_line;
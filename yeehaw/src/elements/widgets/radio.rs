@@ -41,20 +41,32 @@ impl RadioButtons {
         unselectable_style: Style::new_const(Color::GREY13, Color::TRANSPARENT),
     };
 
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![
-            (KB::KEY_UP.into()),
-            (KB::KEY_DOWN.into()),
-            (KB::KEY_J.into()),
-            (KB::KEY_K.into()),
-        ])
+    pub const ACT_SELECT_UP: KeyAction = KeyAction::new(
+        "radio.select_up",
+        &[
+            &[KeyPossibility::Key(KB::KEY_UP)],
+            &[KeyPossibility::Key(KB::KEY_K)],
+        ],
+    );
+    pub const ACT_SELECT_DOWN: KeyAction = KeyAction::new(
+        "radio.select_down",
+        &[
+            &[KeyPossibility::Key(KB::KEY_DOWN)],
+            &[KeyPossibility::Key(KB::KEY_J)],
+        ],
+    );
+
+    pub const KEY_ACTIONS: &'static [KeyAction] = &[Self::ACT_SELECT_UP, Self::ACT_SELECT_DOWN];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
 
     pub fn new(ctx: &Context, radios: Vec<String>) -> Self {
         let max_width = radios.iter().map(|r| r.chars().count()).max().unwrap_or(0) as i32 + 1; // +1 for the radio button
         let pane = SelectablePane::new(ctx, Self::KIND)
-            .with_focused_receivable_events(Self::default_receivable_events())
-            .with_styles(Self::STYLE)
+            .with_focused_receivable_events(Self::default_receivable_events(ctx))
+            .with_theme_styles(ctx, Self::STYLE)
             .with_dyn_width(DynVal::new_fixed(max_width))
             .with_dyn_height(DynVal::new_fixed(radios.len() as i32));
 
@@ -142,20 +154,21 @@ impl Element for RadioButtons {
                     return (false, resps);
                 }
 
+                let km = &ctx.keymap;
                 match true {
-                    _ if ke[0] == KB::KEY_DOWN || ke[0] == KB::KEY_J => {
-                        if *self.selected.borrow() < self.radios.borrow().len() - 1 {
-                            *self.selected.borrow_mut() += 1;
-                            let sel_i = *self.selected.borrow();
-                            let sel_str = self.radios.borrow()[sel_i].clone();
-                            let resps_ =
-                                self.radio_selected_fn.borrow_mut()(ctx.clone(), sel_i, sel_str);
-                            resps.extend(resps_);
-                            self.update_content();
-                            return (true, resps);
-                        }
+                    _ if km.matches(&Self::ACT_SELECT_DOWN, &ke)
+                        && *self.selected.borrow() < self.radios.borrow().len() - 1 =>
+                    {
+                        *self.selected.borrow_mut() += 1;
+                        let sel_i = *self.selected.borrow();
+                        let sel_str = self.radios.borrow()[sel_i].clone();
+                        let resps_ =
+                            self.radio_selected_fn.borrow_mut()(ctx.clone(), sel_i, sel_str);
+                        resps.extend(resps_);
+                        self.update_content();
+                        return (true, resps);
                     }
-                    _ if (ke[0] == KB::KEY_UP || ke[0] == KB::KEY_K) && *self.selected.borrow() > 0 => {
+                    _ if km.matches(&Self::ACT_SELECT_UP, &ke) && *self.selected.borrow() > 0 => {
                         *self.selected.borrow_mut() -= 1;
                         let sel_i = *self.selected.borrow();
                         let sel_str = self.radios.borrow()[sel_i].clone();
@@ -59,37 +59,57 @@ pub enum HorizontalSBPositions {
 
 impl VerticalScrollbar {
     const KIND: &'static str = "vertical_scrollbar";
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![
-            (KB::KEY_UP.into()),
-            (KB::KEY_DOWN.into()),
-            (KB::KEY_SPACE.into()),
-        ])
+
+    pub const ACT_SCROLL_UP: KeyAction = KeyAction::new(
+        "vertical_scrollbar.scroll_up",
+        &[&[KeyPossibility::Key(KB::KEY_UP)]],
+    );
+    pub const ACT_SCROLL_DOWN: KeyAction = KeyAction::new(
+        "vertical_scrollbar.scroll_down",
+        &[&[KeyPossibility::Key(KB::KEY_DOWN)]],
+    );
+    pub const ACT_PAGE_DOWN: KeyAction = KeyAction::new(
+        "vertical_scrollbar.page_down",
+        &[&[KeyPossibility::Key(KB::KEY_SPACE)]],
+    );
+
+    pub const KEY_ACTIONS: &'static [KeyAction] = &[
+        Self::ACT_SCROLL_UP,
+        Self::ACT_SCROLL_DOWN,
+        Self::ACT_PAGE_DOWN,
+    ];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
     pub fn new(
         ctx: &Context, scrollable_view_height: DynVal, scrollable_view_size: Size,
         scrollable_height: usize,
     ) -> Self {
         let pane = Pane::new(ctx, Self::KIND)
-            .with_focused_receivable_events(Self::default_receivable_events())
-            .with_style(Scrollbar::STYLE)
+            .with_focused_receivable_events(Self::default_receivable_events(ctx))
+            .with_style_from_theme("scrollbar", Scrollbar::STYLE)
             .with_dyn_width(DynVal::new_fixed(1))
             .with_dyn_height(scrollable_view_height.clone());
 
-        VerticalScrollbar(Scrollbar {
+        let sb = VerticalScrollbar(Scrollbar {
             pane,
             scrollable_domain_chs: Rc::new(RefCell::new(scrollable_height)),
             scrollable_view_chs: Rc::new(RefCell::new(scrollable_view_height.clone())),
             scrollable_view_size: Rc::new(RefCell::new(scrollable_view_size)),
             scrollable_position: Rc::new(RefCell::new(0)),
             has_arrows: Rc::new(RefCell::new(true)),
-            sb_sty: Rc::new(RefCell::new(ScrollbarSty::vertical_block(Scrollbar::STYLE))),
+            sb_sty: Rc::new(RefCell::new(ScrollbarSty::vertical_block(Style::default()))),
             position_changed_hook: Rc::new(RefCell::new(None)),
             currently_dragging: Rc::new(RefCell::new(false)),
             start_drag_position: Rc::new(RefCell::new(0)),
             jump_scroll_percent: Rc::new(RefCell::new(10)),
             jump_scroll_min_amount: Rc::new(RefCell::new(3)),
-        })
+        });
+        sb.pane.theme_styles().bind(&sb.sb_sty, |t| {
+            ScrollbarSty::vertical_block(t.style("scrollbar", Scrollbar::STYLE))
+        });
+        sb
     }
 
     /// is scrollable_height is None, then it is considererd unchanged
@@ -141,20 +161,31 @@ impl VerticalScrollbar {
 
 impl HorizontalScrollbar {
     const KIND: &'static str = "horizontal_scrollbar";
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![(KB::KEY_LEFT.into()), (KB::KEY_RIGHT.into())])
+    pub const ACT_SCROLL_LEFT: KeyAction = KeyAction::new(
+        "horizontal_scrollbar.scroll_left",
+        &[&[KeyPossibility::Key(KB::KEY_LEFT)]],
+    );
+    pub const ACT_SCROLL_RIGHT: KeyAction = KeyAction::new(
+        "horizontal_scrollbar.scroll_right",
+        &[&[KeyPossibility::Key(KB::KEY_RIGHT)]],
+    );
+
+    pub const KEY_ACTIONS: &'static [KeyAction] = &[Self::ACT_SCROLL_LEFT, Self::ACT_SCROLL_RIGHT];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
     pub fn new(
         ctx: &Context, scrollable_view_width: DynVal, scrollable_view_size: Size,
         scrollable_width: usize,
     ) -> Self {
         let pane = Pane::new(ctx, Self::KIND)
-            .with_focused_receivable_events(Self::default_receivable_events())
-            .with_style(Scrollbar::STYLE)
+            .with_focused_receivable_events(Self::default_receivable_events(ctx))
+            .with_style_from_theme("scrollbar", Scrollbar::STYLE)
             .with_dyn_height(DynVal::new_fixed(1))
             .with_dyn_width(scrollable_view_width.clone());
 
-        HorizontalScrollbar(Scrollbar {
+        let sb = HorizontalScrollbar(Scrollbar {
             pane,
             scrollable_domain_chs: Rc::new(RefCell::new(scrollable_width)),
             scrollable_view_chs: Rc::new(RefCell::new(scrollable_view_width.clone())),
@@ -162,14 +193,18 @@ impl HorizontalScrollbar {
             scrollable_position: Rc::new(RefCell::new(0)),
             has_arrows: Rc::new(RefCell::new(true)),
             sb_sty: Rc::new(RefCell::new(ScrollbarSty::horizontal_block(
-                Scrollbar::STYLE,
+                Style::default(),
             ))),
             position_changed_hook: Rc::new(RefCell::new(None)),
             currently_dragging: Rc::new(RefCell::new(false)),
             start_drag_position: Rc::new(RefCell::new(0)),
             jump_scroll_percent: Rc::new(RefCell::new(10)),
             jump_scroll_min_amount: Rc::new(RefCell::new(3)),
-        })
+        });
+        sb.pane.theme_styles().bind(&sb.sb_sty, |t| {
+            ScrollbarSty::horizontal_block(t.style("scrollbar", Scrollbar::STYLE))
+        });
+        sb
     }
 
     /// is scrollable_width is None, then it is considererd unchanged
@@ -274,6 +309,7 @@ pub struct Scrollbar {
     pub jump_scroll_min_amount: Rc<RefCell<usize>>,
 }
 
+#[derive(Clone, PartialEq)]
 pub struct ScrollbarSty {
     pub backwards_arrow: DrawCh,
     pub forwards_arrow: DrawCh,
@@ -719,24 +755,30 @@ impl HorizontalScrollbar {
 }
 
 impl VerticalScrollbar {
-    pub fn receive_key_event(&self, ev: Vec<KeyEvent>) -> (bool, EventResponses) {
+    /// jump scroll forwards by the size of the scrollbar as last drawn
+    pub fn page_forwards(&self) {
+        let h = self.pane.get_last_size().height;
+        self.jump_scroll_forwards(h.into());
+    }
+
+    pub fn receive_key_event(&self, ctx: &Context, ev: Vec<KeyEvent>) -> (bool, EventResponses) {
         if ev.is_empty() {
             return (false, EventResponses::default());
         }
 
+        let km = &ctx.keymap;
         match true {
-            _ if ev[0] == KB::KEY_UP => {
+            _ if km.matches(&Self::ACT_SCROLL_UP, &ev) => {
                 self.scroll_backwards();
                 (true, EventResponses::default())
             }
-            _ if ev[0] == KB::KEY_DOWN => {
+            _ if km.matches(&Self::ACT_SCROLL_DOWN, &ev) => {
                 let h = self.pane.get_last_size().height;
                 self.scroll_forwards(h.into());
                 (true, EventResponses::default())
             }
-            _ if ev[0] == KB::KEY_SPACE => {
-                let h = self.pane.get_last_size().height;
-                self.jump_scroll_forwards(h.into());
+            _ if km.matches(&Self::ACT_PAGE_DOWN, &ev) => {
+                self.page_forwards();
                 (true, EventResponses::default())
             }
             _ => (false, EventResponses::default()),
@@ -745,17 +787,18 @@ impl VerticalScrollbar {
 }
 
 impl HorizontalScrollbar {
-    pub fn receive_key_event(&self, ev: Vec<KeyEvent>) -> (bool, EventResponses) {
+    pub fn receive_key_event(&self, ctx: &Context, ev: Vec<KeyEvent>) -> (bool, EventResponses) {
         if ev.is_empty() {
             return (false, EventResponses::default());
         }
 
+        let km = &ctx.keymap;
         match true {
-            _ if ev[0] == KB::KEY_LEFT => {
+            _ if km.matches(&Self::ACT_SCROLL_LEFT, &ev) => {
                 self.scroll_backwards();
                 (true, EventResponses::default())
             }
-            _ if ev[0] == KB::KEY_RIGHT => {
+            _ if km.matches(&Self::ACT_SCROLL_RIGHT, &ev) => {
                 let w = self.pane.get_last_size().width;
                 self.scroll_forwards(w.into());
                 (true, EventResponses::default())
@@ -1004,13 +1047,14 @@ impl HorizontalScrollbar {
 impl Element for VerticalScrollbar {
     fn receive_event(&self, ctx: &Context, ev: Event) -> (bool, EventResponses) {
         match ev {
-            Event::KeyCombo(ke) => self.receive_key_event(ke),
+            Event::KeyCombo(ke) => self.receive_key_event(ctx, ke),
             Event::Mouse(me) => self.receive_mouse_event(ctx, me),
             Event::ExternalMouse(me) => self.receive_external_mouse_event(ctx, me),
             _ => (false, EventResponses::default()),
         }
     }
     fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
+        self.pane.refresh_theme();
         let content = self.get_content(dr);
         self.pane.set_content_if_diff(content);
         self.pane.drawing(ctx, dr, force_update)
@@ -1021,13 +1065,14 @@ impl Element for VerticalScrollbar {
 impl Element for HorizontalScrollbar {
     fn receive_event(&self, ctx: &Context, ev: Event) -> (bool, EventResponses) {
         match ev {
-            Event::KeyCombo(ke) => self.receive_key_event(ke),
+            Event::KeyCombo(ke) => self.receive_key_event(ctx, ke),
             Event::Mouse(me) => self.receive_mouse_event(ctx, me),
             Event::ExternalMouse(me) => self.receive_external_mouse_event(ctx, me),
             _ => (false, EventResponses::default()),
         }
     }
     fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
+        self.pane.refresh_theme();
         let content = self.get_content(dr);
         self.pane.set_content_if_diff(content);
         self.pane.drawing(ctx, dr, force_update)
@@ -1047,7 +1092,14 @@ mod tests {
         let color_store = crate::ColorStore::default();
 
         let (ev_tx, _) = tokio::sync::mpsc::channel(1);
-        let ctx = Context::new_context_no_dur(&hat, ev_tx, &color_store);
+        let ctx = Context::new_context_no_dur(
+            &hat,
+            ev_tx,
+            &color_store,
+            &Default::default(),
+            &Default::default(),
+            &Default::default(),
+        );
         let dr = DrawRegion::default().with_size(Size::new(w, 1));
 
         let width = DynVal::FULL.minus(sub.into());
@@ -7,8 +7,6 @@ use {
 pub struct Slider {
     pub pane: SelectablePane,
 
-    pub is_dirty: Rc<RefCell<bool>>,
-
     /// position of the slider from 0.0 to 1.0
     pub position: Rc<RefCell<f64>>,
 
@@ -29,13 +27,25 @@ pub type AdjustFn = Box<dyn FnMut(Context, &Slider) -> EventResponses>;
 impl Slider {
     const KIND: &'static str = "slider";
 
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![
-            (KB::KEY_LEFT.into()),
-            (KB::KEY_RIGHT.into()),
-            (KB::KEY_H.into()),
-            (KB::KEY_L.into()),
-        ])
+    pub const ACT_DECREMENT: KeyAction = KeyAction::new(
+        "slider.decrement",
+        &[
+            &[KeyPossibility::Key(KB::KEY_LEFT)],
+            &[KeyPossibility::Key(KB::KEY_H)],
+        ],
+    );
+    pub const ACT_INCREMENT: KeyAction = KeyAction::new(
+        "slider.increment",
+        &[
+            &[KeyPossibility::Key(KB::KEY_RIGHT)],
+            &[KeyPossibility::Key(KB::KEY_L)],
+        ],
+    );
+
+    pub const KEY_ACTIONS: &'static [KeyAction] = &[Self::ACT_DECREMENT, Self::ACT_INCREMENT];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
 
     pub fn new_basic_block(ctx: &Context) -> Self {
@@ -84,14 +94,13 @@ impl Slider {
     /// ```
     pub fn new(ctx: &Context, filled: DrawCh, empty: DrawCh, head: DrawCh) -> Self {
         let pane = SelectablePane::new(ctx, Self::KIND)
-            .with_focused_receivable_events(Self::default_receivable_events())
-            .with_styles(SelStyles::opaque(ctx))
+            .with_focused_receivable_events(Self::default_receivable_events(ctx))
+            .with_theme_styles(ctx, SelStyles::opaque(ctx))
             .with_dyn_width(DynVal::FULL)
             .with_dyn_height(DynVal::new_fixed(1));
 
         let t = Slider {
             pane,
-            is_dirty: Rc::new(RefCell::new(true)),
             position: Rc::new(RefCell::new(0.0)),
             filled: Rc::new(RefCell::new(filled)),
             empty: Rc::new(RefCell::new(empty)),
@@ -103,8 +112,11 @@ impl Slider {
         let t_ = t.clone();
         t.pane
             .set_post_hook_for_set_selectability(Box::new(move |_, _| {
-                t_.is_dirty.replace(true);
+                t_.pane.set_dirty();
             }));
+        let t_ = t.clone();
+        t.pane
+            .set_update_dirty_content(Box::new(move |ctx, dr| t_.update_content(ctx, dr)));
         t
     }
 
@@ -157,7 +169,7 @@ impl Slider {
     pub fn set_position(&self, mut pos: f64) {
         pos = pos.clamp(0.0, 1.0);
         *self.position.borrow_mut() = pos;
-        self.is_dirty.replace(true);
+        self.pane.set_dirty();
     }
 
     pub fn perform_adjustment(&self, ctx: &Context, pos: f64) -> EventResponses {
@@ -235,13 +247,14 @@ impl Element for Slider {
                 if self.pane.get_selectability() != Selectability::Selected || ke.is_empty() {
                     return (false, resps);
                 }
+                let km = &ctx.keymap;
                 match true {
-                    _ if ke[0] == KB::KEY_LEFT || ke[0] == KB::KEY_H => {
+                    _ if km.matches(&Self::ACT_DECREMENT, &ke) => {
                         let resps_ = self.decrement_position(ctx);
                         resps.extend(resps_);
                         return (true, resps);
                     }
-                    _ if ke[0] == KB::KEY_RIGHT || ke[0] == KB::KEY_L => {
+                    _ if km.matches(&Self::ACT_INCREMENT, &ke) => {
                         let resps_ = self.increment_position(ctx);
                         resps.extend(resps_);
                         return (true, resps);
@@ -306,18 +319,8 @@ impl Element for Slider {
                     _ => {}
                 }
             }
-            Event::Resize => {
-                self.is_dirty.replace(true);
-                return (false, resps);
-            }
             _ => {}
         }
         (false, resps)
     }
-    fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
-        if self.is_dirty.replace(false) || force_update {
-            self.update_content(ctx, dr);
-        }
-        self.pane.drawing(ctx, dr, force_update)
-    }
 }
@@ -1,9 +1,11 @@
 use {
     crate::{
         elements::menu::{MenuItem, MenuPath, MenuStyle},
+        textbox_history::{EditHistory, EditKind, TextBoxState},
         Keyboard as KB, *,
     },
     crossterm::event::{KeyEvent, KeyModifiers, MouseButton, MouseEventKind},
+    std::time::Duration,
 };
 
 // TODO cache WrChs for efficiency. Also get_wrapped should return a Ref<WrChs>
@@ -32,7 +34,8 @@ impl TextBox {
         let pane = SelectablePane::new(init_ctx, Self::KIND)
             .with_dyn_width(DynVal::new_fixed(s.width as i32))
             .with_dyn_height(DynVal::new_fixed(s.height as i32))
-            .with_styles(TextBoxInner::STYLE);
+            .with_theme_styles(init_ctx, TextBoxInner::STYLE)
+            .with_always_receivable_events(MousePossibility::all_but_scroll().into());
         let inner = TextBoxInner::new(init_ctx, text);
 
         pane.pane.add_element(Box::new(inner.clone()));
@@ -70,6 +73,29 @@ impl TextBox {
         *self.inner.borrow().is_dirty.borrow_mut() = true;
     }
 
+    /// always accept mouse scroll events, by default scrolling over a textbox without scrollbars
+    /// whose text fits within it is passed to the elements underneath (such as a scrollable pane)
+    pub fn with_mouse_scroll(self) -> Self {
+        self.set_mouse_scroll();
+        self
+    }
+
+    pub fn set_mouse_scroll(&self) {
+        let scroll = ReceivableEvent::Mouse(MousePossibility::Scroll);
+        let mut evs = self.pane.pane.pane.rec_evs_always.borrow_mut();
+        if !evs.contains(&scroll) {
+            evs.push(scroll);
+        }
+    }
+
+    /// whether the text overflows the textbox as of the last draw
+    pub fn is_overflowing(&self) -> bool {
+        let inner = self.inner.borrow();
+        let size = *inner.pane.get_last_size();
+        inner.pane.content_height() > size.height as usize
+            || inner.pane.content_width() > size.width as usize
+    }
+
     pub fn with_scrollbars(self, init_ctx: &Context) -> Self {
         self.set_x_scrollbar_inner(init_ctx, HorizontalSBPositions::Below);
         self.set_y_scrollbar_inner(init_ctx, VerticalSBPositions::ToTheRight);
@@ -126,6 +152,8 @@ impl TextBox {
             x_sb.set_scrollable_view_size(size);
         }
 
+        self.set_mouse_scroll();
+
         // wire the scrollbar to the textbox
         let pane_ = self.inner.borrow().pane.clone();
         let is_dirty = self.inner.borrow().is_dirty.clone();
@@ -200,6 +228,8 @@ impl TextBox {
             }
         }
 
+        self.set_mouse_scroll();
+
         // wire the scrollbar to the textbox
         let pane_ = self.inner.borrow().pane.clone();
         let is_dirty = self.inner.borrow().is_dirty.clone();
@@ -456,6 +486,24 @@ impl TextBox {
         self.set_dirty();
     }
 
+    /// record set_text as an undo unit rather than clearing the undo history
+    pub fn with_undoable_set_text(self) -> Self {
+        *self.inner.borrow().set_text_undoable.borrow_mut() = true;
+        self
+    }
+
+    pub fn undo(&self, ctx: &Context) -> EventResponses {
+        let resps = self.inner.borrow().undo(ctx);
+        self.set_dirty();
+        resps
+    }
+
+    pub fn redo(&self, ctx: &Context) -> EventResponses {
+        let resps = self.inner.borrow().redo(ctx);
+        self.set_dirty();
+        resps
+    }
+
     pub fn set_cursor_pos_to_end(&self) {
         self.inner.borrow().set_cursor_pos_to_end();
         self.set_dirty();
@@ -470,6 +518,35 @@ impl Element for TextBox {
         }
         self.pane.receive_event(ctx, ev)
     }
+
+    /// scroll events are also accepted while the text overflows the textbox so that it may be
+    /// scrolled without scrollbars
+    fn accepts_mouse(&self, ev: &Event) -> bool {
+        if self.pane.accepts_mouse(ev) {
+            return true;
+        }
+        match ev {
+            Event::Mouse(me) if MousePossibility::Scroll.matches(&me.kind) => self.is_overflowing(),
+            _ => false,
+        }
+    }
+
+    fn save_state(&self, state: &mut SessionState) {
+        self.pane.save_state(state);
+        if let Some(name) = self.get_state_name() {
+            state.set(&name, SessionState::KEY_TEXT, &self.get_text());
+        }
+    }
+
+    fn restore_state(&self, state: &SessionState) {
+        self.pane.restore_state(state);
+        let Some(name) = self.get_state_name() else {
+            return;
+        };
+        if let Some(text) = state.get(&name, SessionState::KEY_TEXT) {
+            self.set_text(text);
+        }
+    }
 }
 
 #[allow(clippy::type_complexity)]
@@ -509,6 +586,11 @@ pub struct TextBoxInner {
     pub mouse_dragging: Rc<RefCell<bool>>,
     pub visual_mode_start_pos: Rc<RefCell<usize>>,
 
+    /// the undo/redo history of the edits
+    pub history: Rc<RefCell<EditHistory>>,
+    /// whether set_text is recorded as an undo unit, otherwise set_text clears the history
+    pub set_text_undoable: Rc<RefCell<bool>>,
+
     /// this hook is called each time the text changes (for each letter)
     pub text_changed_hook: Rc<RefCell<Option<Box<dyn FnMut(Context, String) -> EventResponses>>>>,
 
@@ -547,36 +629,134 @@ impl TextBoxInner {
 
     const DEFAULT_CURSOR_STYLE: Style = Style::new_const(Color::WHITE, Color::BLUE);
 
+    // key actions of editable textboxes
+    pub const ACT_SELECT_LEFT: KeyAction = KeyAction::new(
+        "textbox.select_left",
+        &[&[KeyPossibility::Key(KB::KEY_SHIFT_LEFT)]],
+    );
+    pub const ACT_SELECT_RIGHT: KeyAction = KeyAction::new(
+        "textbox.select_right",
+        &[&[KeyPossibility::Key(KB::KEY_SHIFT_RIGHT)]],
+    );
+    pub const ACT_SELECT_UP: KeyAction = KeyAction::new(
+        "textbox.select_up",
+        &[&[KeyPossibility::Key(KB::KEY_SHIFT_UP)]],
+    );
+    pub const ACT_SELECT_DOWN: KeyAction = KeyAction::new(
+        "textbox.select_down",
+        &[&[KeyPossibility::Key(KB::KEY_SHIFT_DOWN)]],
+    );
+    pub const ACT_CURSOR_LEFT: KeyAction = KeyAction::new(
+        "textbox.cursor_left",
+        &[&[KeyPossibility::Key(KB::KEY_LEFT)]],
+    );
+    pub const ACT_CURSOR_RIGHT: KeyAction = KeyAction::new(
+        "textbox.cursor_right",
+        &[&[KeyPossibility::Key(KB::KEY_RIGHT)]],
+    );
+    pub const ACT_CURSOR_UP: KeyAction =
+        KeyAction::new("textbox.cursor_up", &[&[KeyPossibility::Key(KB::KEY_UP)]]);
+    pub const ACT_CURSOR_DOWN: KeyAction = KeyAction::new(
+        "textbox.cursor_down",
+        &[&[KeyPossibility::Key(KB::KEY_DOWN)]],
+    );
+    /// NOTE without keyboard enhancement terminals send ctrl+backspace as ctrl+h
+    pub const ACT_BACKSPACE: KeyAction = KeyAction::new(
+        "textbox.backspace",
+        &[
+            &[KeyPossibility::Key(KB::KEY_BACKSPACE)],
+            &[KeyPossibility::Key(KB::KEY_CTRL_BACKSPACE)],
+            &[KeyPossibility::Key(KB::KEY_CTRL_H)],
+        ],
+    );
+    /// delete the word before the cursor, unbound by default
+    pub const ACT_DELETE_WORD: KeyAction = KeyAction::new("textbox.delete_word", &[]);
+    pub const ACT_UNDO: KeyAction =
+        KeyAction::new("textbox.undo", &[&[KeyPossibility::Key(KB::KEY_CTRL_Z)]]);
+    pub const ACT_REDO: KeyAction = KeyAction::new(
+        "textbox.redo",
+        &[
+            &[KeyPossibility::Key(KB::KEY_CTRL_SHIFT_Z)],
+            &[KeyPossibility::Key(KB::KEY_CTRL_Y)],
+        ],
+    );
+    pub const ACT_NEWLINE: KeyAction =
+        KeyAction::new("textbox.newline", &[&[KeyPossibility::Key(KB::KEY_ENTER)]]);
+    /// captured without an edit so that wrapping elements (such as the NumbersTextBox) may act
+    /// on it
+    pub const ACT_SHIFT_ENTER: KeyAction = KeyAction::new(
+        "textbox.shift_enter",
+        &[&[KeyPossibility::Key(KB::KEY_SHIFT_ENTER)]],
+    );
+    pub const ACT_INSERT_CHAR: KeyAction =
+        KeyAction::new("textbox.insert_char", &[&[KeyPossibility::Chars]]);
+
+    // key actions of non-editable textboxes
+    pub const ACT_VIEW_LEFT: KeyAction = KeyAction::new(
+        "textbox.view_left",
+        &[
+            &[KeyPossibility::Key(KB::KEY_LEFT)],
+            &[KeyPossibility::Key(KB::KEY_H)],
+        ],
+    );
+    pub const ACT_VIEW_RIGHT: KeyAction = KeyAction::new(
+        "textbox.view_right",
+        &[
+            &[KeyPossibility::Key(KB::KEY_RIGHT)],
+            &[KeyPossibility::Key(KB::KEY_L)],
+        ],
+    );
+    pub const ACT_VIEW_UP: KeyAction = KeyAction::new(
+        "textbox.view_up",
+        &[
+            &[KeyPossibility::Key(KB::KEY_UP)],
+            &[KeyPossibility::Key(KB::KEY_K)],
+        ],
+    );
+    pub const ACT_VIEW_DOWN: KeyAction = KeyAction::new(
+        "textbox.view_down",
+        &[
+            &[KeyPossibility::Key(KB::KEY_DOWN)],
+            &[KeyPossibility::Key(KB::KEY_J)],
+        ],
+    );
+
+    /// NOTE the actions are checked in order, the insert char action must be last as it matches
+    /// any char including those with modifiers
+    pub const EDITABLE_KEY_ACTIONS: &'static [KeyAction] = &[
+        Self::ACT_SELECT_LEFT,
+        Self::ACT_SELECT_RIGHT,
+        Self::ACT_SELECT_UP,
+        Self::ACT_SELECT_DOWN,
+        Self::ACT_CURSOR_LEFT,
+        Self::ACT_CURSOR_RIGHT,
+        Self::ACT_CURSOR_UP,
+        Self::ACT_CURSOR_DOWN,
+        Self::ACT_BACKSPACE,
+        Self::ACT_DELETE_WORD,
+        Self::ACT_UNDO,
+        Self::ACT_REDO,
+        Self::ACT_NEWLINE,
+        Self::ACT_SHIFT_ENTER,
+        Self::ACT_INSERT_CHAR,
+    ];
+    pub const NON_EDITABLE_KEY_ACTIONS: &'static [KeyAction] = &[
+        Self::ACT_VIEW_LEFT,
+        Self::ACT_VIEW_RIGHT,
+        Self::ACT_VIEW_UP,
+        Self::ACT_VIEW_DOWN,
+    ];
+
     /// for textboxes which are editable
-    pub fn editable_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![
-            (KeyPossibility::Chars.into()),
-            (KB::KEY_BACKSPACE.into()),
-            (KB::KEY_ENTER.into()),
-            (KB::KEY_SHIFT_ENTER.into()),
-            (KB::KEY_LEFT.into()),
-            (KB::KEY_RIGHT.into()),
-            (KB::KEY_UP.into()),
-            (KB::KEY_DOWN.into()),
-            (KB::KEY_SHIFT_LEFT.into()),
-            (KB::KEY_SHIFT_RIGHT.into()),
-            (KB::KEY_SHIFT_UP.into()),
-            (KB::KEY_SHIFT_DOWN.into()),
-        ])
+    pub fn editable_receivable_events(ctx: &Context) -> ReceivableEvents {
+        let mut rec = ctx.keymap.receivable_events(Self::EDITABLE_KEY_ACTIONS);
+        rec.push(ReceivableEvent::Paste);
+        rec
     }
 
     /// non-editable textboxes can still scroll
-    pub fn non_editable_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![
-            (KB::KEY_LEFT.into()),
-            (KB::KEY_RIGHT.into()),
-            (KB::KEY_UP.into()),
-            (KB::KEY_DOWN.into()),
-            (KB::KEY_H.into()),
-            (KB::KEY_J.into()),
-            (KB::KEY_K.into()),
-            (KB::KEY_L.into()),
-        ])
+    pub fn non_editable_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::NON_EDITABLE_KEY_ACTIONS)
     }
 
     pub fn new<S: Into<String>>(ctx: &Context, text: S) -> Self {
@@ -584,8 +764,11 @@ impl TextBoxInner {
         let pane = Pane::new(ctx, Self::KIND)
             .with_dyn_width(DynVal::FULL)
             .with_dyn_height(DynVal::FULL)
-            .with_focused_receivable_events(Self::editable_receivable_events())
+            .with_focused_receivable_events(Self::editable_receivable_events(ctx))
             .with_focused(true);
+        let cursor_style = pane
+            .theme_styles()
+            .style("textbox.cursor", Self::DEFAULT_CURSOR_STYLE);
 
         let tb = TextBoxInner {
             pane,
@@ -601,10 +784,12 @@ impl TextBoxInner {
             ch_cursor: Rc::new(RefCell::new(true)),
             editable: Rc::new(RefCell::new(true)),
             cursor_pos: Rc::new(RefCell::new(0)),
-            cursor_style: Rc::new(RefCell::new(Self::DEFAULT_CURSOR_STYLE)),
+            cursor_style,
             visual_mode: Rc::new(RefCell::new(false)),
             mouse_dragging: Rc::new(RefCell::new(false)),
             visual_mode_start_pos: Rc::new(RefCell::new(0)),
+            history: Rc::new(RefCell::new(EditHistory::default())),
+            set_text_undoable: Rc::new(RefCell::new(false)),
             text_changed_hook: Rc::new(RefCell::new(None)),
             position_style_hook: Rc::new(RefCell::new(None)),
             cursor_changed_hook: Rc::new(RefCell::new(None)),
@@ -620,7 +805,8 @@ impl TextBoxInner {
 
     pub fn set_editable_right_click_menu(&self, ctx: &Context) {
         let (tb1, tb2, tb3) = (self.clone(), self.clone(), self.clone());
-        let rcm = RightClickMenu::new(ctx, MenuStyle::default()).with_menu_items(
+        let (tb4, tb5) = (self.clone(), self.clone());
+        let rcm = RightClickMenu::new(ctx, MenuStyle::themed(ctx)).with_menu_items(
             ctx,
             vec![
                 MenuItem::new(ctx, MenuPath("Cut".to_string())).with_fn(Some(Box::new(
@@ -630,9 +816,9 @@ impl TextBoxInner {
                     },
                 ))),
                 MenuItem::new(ctx, MenuPath("Copy".to_string())).with_fn(Some(Box::new(
-                    move |_ctx| {
+                    move |ctx| {
                         tb2.is_dirty.replace(true);
-                        tb2.copy_to_clipboard();
+                        tb2.copy_to_clipboard(&ctx);
                         EventResponses::default()
                     },
                 ))),
@@ -642,6 +828,10 @@ impl TextBoxInner {
                         tb3.paste_from_clipboard(&ctx)
                     },
                 ))),
+                MenuItem::new(ctx, MenuPath("Undo".to_string()))
+                    .with_fn(Some(Box::new(move |ctx| tb4.undo(&ctx)))),
+                MenuItem::new(ctx, MenuPath("Redo".to_string()))
+                    .with_fn(Some(Box::new(move |ctx| tb5.redo(&ctx)))),
             ],
         );
         *self.right_click_menu.borrow_mut() = Some(rcm);
@@ -649,13 +839,13 @@ impl TextBoxInner {
 
     pub fn set_non_editable_right_click_menu(&self, ctx: &Context) {
         let tb = self.clone();
-        let rcm = RightClickMenu::new(ctx, MenuStyle::default()).with_menu_items(
+        let rcm = RightClickMenu::new(ctx, MenuStyle::themed(ctx)).with_menu_items(
             ctx,
             vec![
                 MenuItem::new(ctx, MenuPath("Copy".to_string())).with_fn(Some(Box::new(
-                    move |_ctx| {
+                    move |ctx| {
                         tb.is_dirty.replace(true);
-                        tb.copy_to_clipboard();
+                        tb.copy_to_clipboard(&ctx);
                         EventResponses::default()
                     },
                 ))),
@@ -692,14 +882,14 @@ impl TextBoxInner {
     pub fn set_editable(&self, ctx: &Context) {
         *self.editable.borrow_mut() = true;
         self.pane
-            .set_focused_receivable_events(TextBoxInner::editable_receivable_events());
+            .set_focused_receivable_events(TextBoxInner::editable_receivable_events(ctx));
         self.set_editable_right_click_menu(ctx);
     }
 
     pub fn set_non_editable(&self, ctx: &Context) {
         *self.editable.borrow_mut() = false;
         self.pane
-            .set_focused_receivable_events(TextBoxInner::non_editable_receivable_events());
+            .set_focused_receivable_events(TextBoxInner::non_editable_receivable_events(ctx));
         self.set_non_editable_right_click_menu(ctx);
     }
 
@@ -724,10 +914,62 @@ impl TextBoxInner {
     }
 
     pub fn set_text(&self, text: String) {
+        if *self.set_text_undoable.borrow() {
+            self.record_edit(EditKind::Other, Duration::ZERO);
+        } else {
+            self.history.borrow_mut().clear();
+        }
         *self.text.borrow_mut() = text.chars().collect();
         self.is_dirty.replace(true);
     }
 
+    // ---------------------------------------------------------
+    // undo/redo
+
+    /// the current text and cursor position
+    pub fn state(&self) -> TextBoxState {
+        TextBoxState {
+            text: self.text.borrow().clone(),
+            cursor_pos: self.get_cursor_pos(),
+        }
+    }
+
+    /// record the current state in the history before an edit is made
+    fn record_edit(&self, kind: EditKind, now: Duration) {
+        let before = self.state();
+        self.history.borrow_mut().record(before, kind, now);
+    }
+
+    /// restore a state from the history
+    fn apply_history_state(&self, ctx: &Context, st: TextBoxState) -> EventResponses {
+        *self.text.borrow_mut() = st.text;
+        *self.visual_mode.borrow_mut() = false;
+        let w = self.get_wrapped(None);
+        self.pane.set_content_from_string(w.wrapped_string()); // See NOTE-1
+        let mut resps = self.set_cursor_pos(st.cursor_pos);
+        self.is_dirty.replace(true);
+        if let Some(hook) = &mut *self.text_changed_hook.borrow_mut() {
+            resps.extend(hook(ctx.clone(), self.get_text()));
+        }
+        resps
+    }
+
+    pub fn undo(&self, ctx: &Context) -> EventResponses {
+        let prev = self.history.borrow_mut().undo(self.state());
+        match prev {
+            Some(st) => self.apply_history_state(ctx, st),
+            None => EventResponses::default(),
+        }
+    }
+
+    pub fn redo(&self, ctx: &Context) -> EventResponses {
+        let next = self.history.borrow_mut().redo(self.state());
+        match next {
+            Some(st) => self.apply_history_state(ctx, st),
+            None => EventResponses::default(),
+        }
+    }
+
     // ---------------------------------------------------------
 
     pub fn get_cursor_pos(&self) -> usize {
@@ -952,6 +1194,15 @@ impl TextBoxInner {
         if !*self.visual_mode.borrow() {
             return EventResponses::default();
         }
+        self.record_edit(EditKind::Other, ctx.dur_since_launch);
+        self.delete_visual_selection_unrecorded(ctx)
+    }
+
+    /// delete the visual selection without recording it in the history
+    fn delete_visual_selection_unrecorded(&self, ctx: &Context) -> EventResponses {
+        if !*self.visual_mode.borrow() {
+            return EventResponses::default();
+        }
 
         // delete everything in the visual selection
         let mut rs = self.text.borrow().clone();
@@ -975,43 +1226,67 @@ impl TextBoxInner {
         resps
     }
 
-    pub fn copy_to_clipboard(&self) {
+    /// delete the word before the cursor along with any whitespace between it and the cursor
+    pub fn delete_word_before_cursor(&self, ctx: &Context) -> EventResponses {
+        let cursor_pos = self.get_cursor_pos();
+        let mut rs = self.text.borrow().clone();
+        let mut start_pos = cursor_pos.min(rs.len());
+        while start_pos > 0 && rs[start_pos - 1].is_whitespace() {
+            start_pos -= 1;
+        }
+        while start_pos > 0 && !rs[start_pos - 1].is_whitespace() {
+            start_pos -= 1;
+        }
+        if start_pos == cursor_pos {
+            return EventResponses::default();
+        }
+        self.record_edit(EditKind::Other, ctx.dur_since_launch);
+        rs.drain(start_pos..cursor_pos);
+        self.set_cursor_pos(start_pos);
+        *self.text.borrow_mut() = rs;
+        let w = self.get_wrapped(None);
+        self.pane.set_content_from_string(w.wrapped_string()); // See NOTE-1
+        if let Some(hook) = &mut *self.text_changed_hook.borrow_mut() {
+            return hook(ctx.clone(), self.get_text());
+        }
+        EventResponses::default()
+    }
+
+    pub fn copy_to_clipboard(&self, ctx: &Context) {
         let text = self.visual_selected_text();
-        let Ok(mut cb) = arboard::Clipboard::new() else {
-            log_err!("failed to get clipboard");
-            return;
-        };
-        if let Err(e) = cb.set_text(text) {
+        if let Err(e) = ctx.clipboard.set_text(&text) {
             log_err!("failed to set text to clipboard: {}", e);
         }
     }
 
     pub fn cut_to_clipboard(&self, ctx: &Context) -> EventResponses {
-        self.copy_to_clipboard();
+        self.copy_to_clipboard(ctx);
         self.delete_visual_selection(ctx)
     }
 
     pub fn paste_from_clipboard(&self, ctx: &Context) -> EventResponses {
-        let mut resps = self.delete_visual_selection(ctx);
-
-        let Ok(mut cb) = arboard::Clipboard::new() else {
-            log_err!("failed to get clipboard");
-            return EventResponses::default();
-        };
-        let Ok(cliptext) = cb.get_text() else {
+        let Ok(cliptext) = ctx.clipboard.get_text() else {
             log_err!("failed to get text from clipboard");
             return EventResponses::default();
         };
-        if cliptext.is_empty() {
+        self.insert_text(ctx, &cliptext)
+    }
+
+    /// insert text at the cursor, replacing any visual selection, as a single undo unit
+    pub fn insert_text(&self, ctx: &Context, text: &str) -> EventResponses {
+        // the replaced selection and the inserted text are a single undo unit
+        self.record_edit(EditKind::Other, ctx.dur_since_launch);
+        let mut resps = self.delete_visual_selection_unrecorded(ctx);
+        if text.is_empty() {
             return resps;
         }
-        let cliprunes = cliptext.chars().collect::<Vec<char>>();
+        let runes = text.chars().collect::<Vec<char>>();
         let mut rs = self.text.borrow().clone();
         let cur_pos = self.get_cursor_pos();
-        rs.splice(cur_pos..cur_pos, cliprunes.iter().cloned());
+        rs.splice(cur_pos..cur_pos, runes.iter().cloned());
         *self.text.borrow_mut() = rs;
 
-        self.incr_cursor_pos(cliprunes.len() as isize);
+        self.incr_cursor_pos(runes.len() as isize);
         let w = self.get_wrapped(None);
         self.pane.set_content_from_string(w.wrapped_string()); // See NOTE-1
 
@@ -1029,19 +1304,22 @@ impl TextBoxInner {
         }
 
         self.is_dirty.replace(true);
+        let km = &ctx.keymap;
+        let is = |act: &KeyAction| km.matches(act, &ev);
+        let editable = *self.editable.borrow();
 
         if !*self.ch_cursor.borrow() {
             match true {
-                _ if ev[0] == KB::KEY_LEFT || ev[0] == KB::KEY_H => {
+                _ if is(&Self::ACT_CURSOR_LEFT) || is(&Self::ACT_VIEW_LEFT) => {
                     self.pane.scroll_left(None);
                 }
-                _ if ev[0] == KB::KEY_RIGHT || ev[0] == KB::KEY_L => {
+                _ if is(&Self::ACT_CURSOR_RIGHT) || is(&Self::ACT_VIEW_RIGHT) => {
                     self.pane.scroll_right(None);
                 }
-                _ if ev[0] == KB::KEY_DOWN || ev[0] == KB::KEY_J => {
+                _ if is(&Self::ACT_CURSOR_DOWN) || is(&Self::ACT_VIEW_DOWN) => {
                     self.pane.scroll_down(None);
                 }
-                _ if ev[0] == KB::KEY_UP || ev[0] == KB::KEY_K => {
+                _ if is(&Self::ACT_CURSOR_UP) || is(&Self::ACT_VIEW_UP) => {
                     self.pane.scroll_up(None);
                 }
                 _ => {}
@@ -1056,7 +1334,7 @@ impl TextBoxInner {
 
         let mut resps = EventResponses::default();
         match true {
-            _ if ev[0] == KB::KEY_SHIFT_LEFT => {
+            _ if is(&Self::ACT_SELECT_LEFT) => {
                 visual_mode_event = true;
                 if !visual_mode {
                     *self.visual_mode.borrow_mut() = true;
@@ -1067,7 +1345,7 @@ impl TextBoxInner {
                 }
             }
 
-            _ if ev[0] == KB::KEY_SHIFT_RIGHT => {
+            _ if is(&Self::ACT_SELECT_RIGHT) => {
                 visual_mode_event = true;
                 if !visual_mode {
                     *self.visual_mode.borrow_mut() = true;
@@ -1078,7 +1356,7 @@ impl TextBoxInner {
                 }
             }
 
-            _ if ev[0] == KB::KEY_SHIFT_UP => {
+            _ if is(&Self::ACT_SELECT_UP) => {
                 visual_mode_event = true;
                 if !visual_mode {
                     *self.visual_mode.borrow_mut() = true;
@@ -1090,7 +1368,7 @@ impl TextBoxInner {
                 }
             }
 
-            _ if ev[0] == KB::KEY_SHIFT_DOWN => {
+            _ if is(&Self::ACT_SELECT_DOWN) => {
                 visual_mode_event = true;
                 if !visual_mode {
                     *self.visual_mode.borrow_mut() = true;
@@ -1102,40 +1380,45 @@ impl TextBoxInner {
                 }
             }
 
-            _ if ev[0] == KB::KEY_LEFT || (!*self.editable.borrow() && ev[0] == KB::KEY_H) => {
-                if cursor_pos > 0 && cursor_pos <= self.text.borrow().len() {
-                    // do not move left if at the beginning of a line
-                    if self.text.borrow()[cursor_pos - 1] != '\n' {
-                        self.incr_cursor_pos(-1);
-                    }
+            _ if is(&Self::ACT_CURSOR_LEFT) || (!editable && is(&Self::ACT_VIEW_LEFT)) => {
+                // do not move left if at the beginning of a line
+                let text_len = self.text.borrow().len();
+                let at_line_start = cursor_pos == 0
+                    || cursor_pos > text_len
+                    || self.text.borrow()[cursor_pos - 1] == '\n';
+                if !at_line_start {
+                    self.incr_cursor_pos(-1);
                 }
             }
 
-            _ if ev[0] == KB::KEY_RIGHT || (!*self.editable.borrow() && ev[0] == KB::KEY_L) => {
+            _ if is(&Self::ACT_CURSOR_RIGHT) || (!editable && is(&Self::ACT_VIEW_RIGHT)) => {
                 // don't allow moving to the next line
-                if cursor_pos < self.text.borrow().len() && self.text.borrow()[cursor_pos] != '\n' {
+                let at_line_end = cursor_pos >= self.text.borrow().len()
+                    || self.text.borrow()[cursor_pos] == '\n';
+                if !at_line_end {
                     self.incr_cursor_pos(1);
                 }
             }
 
-            _ if ev[0] == KB::KEY_UP || (!*self.editable.borrow() && ev[0] == KB::KEY_K) => {
+            _ if is(&Self::ACT_CURSOR_UP) || (!editable && is(&Self::ACT_VIEW_UP)) => {
                 let w = self.get_wrapped(None);
                 if let Some(new_pos) = w.get_cursor_above_position(cursor_pos) {
                     self.set_cursor_pos(new_pos);
                 }
             }
 
-            _ if ev[0] == KB::KEY_DOWN || (!*self.editable.borrow() && ev[0] == KB::KEY_J) => {
+            _ if is(&Self::ACT_CURSOR_DOWN) || (!editable && is(&Self::ACT_VIEW_DOWN)) => {
                 let w = self.get_wrapped(None);
                 if let Some(new_pos) = w.get_cursor_below_position(cursor_pos) {
                     self.set_cursor_pos(new_pos);
                 }
             }
 
-            _ if *self.editable.borrow() && (ev[0] == KB::KEY_BACKSPACE) => {
+            _ if editable && is(&Self::ACT_BACKSPACE) => {
                 if visual_mode {
                     resps = self.delete_visual_selection(ctx);
                 } else if cursor_pos > 0 {
+                    self.record_edit(EditKind::Delete, ctx.dur_since_launch);
                     let mut rs = self.text.borrow().clone();
                     rs.remove(cursor_pos - 1);
                     self.incr_cursor_pos(-1);
@@ -1148,7 +1431,24 @@ impl TextBoxInner {
                 }
             }
 
-            _ if *self.editable.borrow() && ev[0] == KB::KEY_ENTER => {
+            _ if editable && is(&Self::ACT_DELETE_WORD) => {
+                resps = if visual_mode {
+                    self.delete_visual_selection(ctx)
+                } else {
+                    self.delete_word_before_cursor(ctx)
+                };
+            }
+
+            _ if editable && is(&Self::ACT_UNDO) => {
+                resps = self.undo(ctx);
+            }
+
+            _ if editable && is(&Self::ACT_REDO) => {
+                resps = self.redo(ctx);
+            }
+
+            _ if editable && is(&Self::ACT_NEWLINE) => {
+                self.record_edit(EditKind::Insert('\n'), ctx.dur_since_launch);
                 let mut rs = self.text.borrow().clone();
                 rs.splice(cursor_pos..cursor_pos, std::iter::once('\n'));
                 *self.text.borrow_mut() = rs;
@@ -1160,8 +1460,9 @@ impl TextBoxInner {
                 }
             }
 
-            _ if *self.editable.borrow() && KeyPossibility::Chars.matches_key(&ev[0]) => {
+            _ if editable && is(&Self::ACT_INSERT_CHAR) => {
                 if let crossterm::event::KeyCode::Char(r) = ev[0].code {
+                    self.record_edit(EditKind::Insert(r), ctx.dur_since_launch);
                     let mut rs = self.text.borrow().clone();
                     rs.insert(cursor_pos, r);
                     *self.text.borrow_mut() = rs;
@@ -1497,12 +1798,25 @@ impl Element for TextBoxInner {
         match ev {
             Event::KeyCombo(ke) => self.receive_key_event(ctx, ke),
             Event::Mouse(me) => self.receive_mouse_event(ctx, me),
+            Event::Paste(text) => {
+                if *self.selectedness.borrow() != Selectability::Selected
+                    || !*self.editable.borrow()
+                {
+                    return (false, EventResponses::default());
+                }
+                (true, self.insert_text(ctx, &text))
+            }
             _ => (false, EventResponses::default()),
         }
     }
 
     fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
-        if self.is_dirty.replace(false) || *self.last_size.borrow() != dr.size || force_update {
+        let restyled = self.pane.refresh_theme();
+        if self.is_dirty.replace(false)
+            || restyled
+            || *self.last_size.borrow() != dr.size
+            || force_update
+        {
             self.update_content(ctx, dr);
             self.last_size.replace(dr.size);
         }
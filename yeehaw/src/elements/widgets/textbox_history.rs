@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+/// the text and cursor position of a textbox at some point in its history
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextBoxState {
+    pub text: Vec<char>,
+    pub cursor_pos: usize,
+}
+
+/// the kind of an edit, used to group consecutive edits into a single undo unit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditKind {
+    /// typing a character
+    Insert(char),
+    /// deleting a single character
+    Delete,
+    /// any other edit (pasting, cutting, replacing the text), never grouped
+    Other,
+}
+
+/// The undo/redo history of a textbox. Each undo unit is stored as the state of the textbox
+/// before the unit was made. Consecutive inserts (or deletes) are grouped into a single unit
+/// unless they are separated by a pause longer than the group timeout, or for inserts, a
+/// whitespace character which begins a new word.
+#[derive(Clone, Debug)]
+pub struct EditHistory {
+    undo: Vec<TextBoxState>,
+    redo: Vec<TextBoxState>,
+    /// the kind and time of the most recent edit
+    last: Option<(EditKind, Duration)>,
+    /// the maximum number of undo units kept
+    pub max_len: usize,
+    pub group_timeout: Duration,
+}
+
+impl Default for EditHistory {
+    fn default() -> Self {
+        EditHistory {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            last: None,
+            max_len: Self::DEFAULT_MAX_LEN,
+            group_timeout: Self::DEFAULT_GROUP_TIMEOUT,
+        }
+    }
+}
+
+impl EditHistory {
+    pub const DEFAULT_MAX_LEN: usize = 200;
+    pub const DEFAULT_GROUP_TIMEOUT: Duration = Duration::from_millis(1000);
+
+    /// record an edit which is about to be made to the textbox in the state `before`, `now` is
+    /// the time of the edit (typically the duration since launch of the context)
+    pub fn record(&mut self, before: TextBoxState, kind: EditKind, now: Duration) {
+        let grouped = match (self.last, kind) {
+            (Some((EditKind::Insert(_), t)), EditKind::Insert(c)) => {
+                !c.is_whitespace() && now.saturating_sub(t) <= self.group_timeout
+            }
+            (Some((EditKind::Delete, t)), EditKind::Delete) => {
+                now.saturating_sub(t) <= self.group_timeout
+            }
+            _ => false,
+        };
+        self.last = Some((kind, now));
+        self.redo.clear();
+        if grouped {
+            return;
+        }
+        self.undo.push(before);
+        if self.undo.len() > self.max_len {
+            self.undo.remove(0);
+        }
+    }
+
+    /// the state to restore for an undo, `current` is kept for redoing
+    pub fn undo(&mut self, current: TextBoxState) -> Option<TextBoxState> {
+        let prev = self.undo.pop()?;
+        self.redo.push(current);
+        self.last = None;
+        Some(prev)
+    }
+
+    /// the state to restore for a redo, `current` is kept for undoing
+    pub fn redo(&mut self, current: TextBoxState) -> Option<TextBoxState> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        self.last = None;
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn st(s: &str) -> TextBoxState {
+        TextBoxState {
+            text: s.chars().collect(),
+            cursor_pos: s.chars().count(),
+        }
+    }
+
+    #[test]
+    fn test_grouping() {
+        let mut h = EditHistory::default();
+        let ms = Duration::from_millis;
+        // typing "ab cd" quickly is two units, "ab" and " cd"
+        let mut text = String::new();
+        for (i, c) in "ab cd".chars().enumerate() {
+            h.record(st(&text), EditKind::Insert(c), ms(i as u64 * 100));
+            text.push(c);
+        }
+        // a pause starts a new unit
+        h.record(st(&text), EditKind::Insert('e'), ms(5000));
+        text.push('e');
+
+        assert_eq!(h.undo(st(&text)), Some(st("ab cd")));
+        assert_eq!(h.undo(st("ab cd")), Some(st("ab")));
+        assert_eq!(h.undo(st("ab")), Some(st("")));
+        assert_eq!(h.undo(st("")), None);
+
+        assert_eq!(h.redo(st("")), Some(st("ab")));
+        assert!(h.can_undo());
+        // a new edit clears the redo history
+        h.record(st("ab"), EditKind::Other, ms(6000));
+        assert!(!h.can_redo());
+    }
+}
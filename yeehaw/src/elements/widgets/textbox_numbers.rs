@@ -1,5 +1,5 @@
 use {
-    crate::{Keyboard as KB, *},
+    crate::{textbox::TextBoxInner, *},
     std::{cmp::PartialOrd, fmt::Display, str::FromStr},
 };
 
@@ -187,14 +187,25 @@ impl<N: Display + Clone + Copy + FromStr + PartialOrd + 'static> Element for Num
                     //    self.set_value(ctx, old_value - *self.button_increment.borrow());
                     //    (true, EventResponses::default())
                     //}
-                    _ if ke[0] == KB::KEY_ENTER => {
+                    _ if ctx.keymap.matches(&TextBoxInner::ACT_NEWLINE, ke) => {
                         self.update_value_from_tb();
                         (true, EventResponses::default())
                     }
-                    _ if ke[0] == KB::KEY_SHIFT_ENTER => (true, EventResponses::default()),
+                    _ if ctx.keymap.matches(&TextBoxInner::ACT_SHIFT_ENTER, ke) => {
+                        (true, EventResponses::default())
+                    }
                     _ => self.tb.receive_event(ctx, ev),
                 }
             }
+            // pastes are parsed as entered values are, anything but a number is dropped
+            Event::Paste(ref text) => {
+                let text = text.trim();
+                if text.parse::<N>().is_err() {
+                    let captured = self.tb.pane.get_selectability() == Selectability::Selected;
+                    return (captured, EventResponses::default());
+                }
+                self.tb.receive_event(ctx, Event::Paste(text.to_string()))
+            }
             _ => self.tb.receive_event(ctx, ev),
         }
     }
@@ -14,17 +14,32 @@ type ValueChangedHook = Box<dyn FnMut(Context, bool, String) -> EventResponses>;
 
 #[yeehaw_derive::impl_pane_basics_from(tb)]
 impl SingleLineTextBox {
+    /// calls the hook with the current text
+    pub const ACT_SUBMIT: KeyAction = KeyAction::new(
+        "single_line_textbox.submit",
+        &[&[KeyPossibility::Key(KB::KEY_ENTER)]],
+    );
+    /// calls the hook with the current text, flagged as escaped
+    pub const ACT_ESCAPE: KeyAction = KeyAction::new(
+        "single_line_textbox.escape",
+        &[&[KeyPossibility::Key(KB::KEY_ESC)]],
+    );
+
     /// for textboxes which are editable
-    pub fn editable_receivable_events() -> ReceivableEvents {
-        let mut rec = TextBoxInner::editable_receivable_events();
-        rec.push(KB::KEY_ESC.into());
+    pub fn editable_receivable_events(ctx: &Context) -> ReceivableEvents {
+        let mut rec = TextBoxInner::editable_receivable_events(ctx);
+        rec.extend(
+            ctx.keymap
+                .receivable_events(&[Self::ACT_SUBMIT, Self::ACT_ESCAPE])
+                .0,
+        );
         rec
     }
 
     pub fn new(ctx: &Context) -> Self {
         let tb = TextBox::new(ctx, "".to_string()).with_no_wordwrap(ctx);
         tb.pane
-            .set_focused_receivable_events(Self::editable_receivable_events());
+            .set_focused_receivable_events(Self::editable_receivable_events(ctx));
         Self {
             tb,
             hook: Rc::new(RefCell::new(None)),
@@ -99,7 +114,7 @@ impl Element for SingleLineTextBox {
                 }
 
                 match true {
-                    _ if ke[0] == KB::KEY_ESC => {
+                    _ if ctx.keymap.matches(&Self::ACT_ESCAPE, ke) => {
                         if let Some(hook) = &mut *self.hook.borrow_mut() {
                             let resps = hook(ctx.clone(), true, self.tb.get_text());
                             self.tb.pane.set_selectability(Selectability::Ready, false);
@@ -107,7 +122,7 @@ impl Element for SingleLineTextBox {
                         }
                         (false, EventResponses::default())
                     }
-                    _ if ke[0] == KB::KEY_ENTER => {
+                    _ if ctx.keymap.matches(&Self::ACT_SUBMIT, ke) => {
                         if let Some(hook) = &mut *self.hook.borrow_mut() {
                             let resps = hook(ctx.clone(), false, self.tb.get_text());
                             self.tb.pane.set_selectability(Selectability::Ready, false);
@@ -118,6 +133,11 @@ impl Element for SingleLineTextBox {
                     _ => self.tb.receive_event(ctx, ev),
                 }
             }
+            // newlines are dropped so that the pasted text remains on a single line
+            Event::Paste(text) => {
+                let text = text.chars().filter(|c| *c != '\n' && *c != '\r').collect();
+                self.tb.receive_event(ctx, Event::Paste(text))
+            }
             //Event::ExternalMouse(ref _me) => (false, EventResponses::default()),
             Event::ExternalMouse(ref me) => {
                 // destroy on every external mouse event besides a move
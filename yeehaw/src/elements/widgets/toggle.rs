@@ -32,21 +32,38 @@ impl Toggle {
     /// for the selected toggle
     const DEFAULT_SELECTED_STY: Style = Style::new_const(Color::BLACK, Color::LIGHT_BLUE);
 
-    pub fn default_receivable_events() -> ReceivableEvents {
-        ReceivableEvents(vec![
-            (KB::KEY_ENTER.into()),
-            (KB::KEY_LEFT.into()),
-            (KB::KEY_RIGHT.into()),
-            (KB::KEY_H.into()),
-            (KB::KEY_L.into()),
-        ])
+    pub const ACT_TOGGLE: KeyAction =
+        KeyAction::new("toggle.toggle", &[&[KeyPossibility::Key(KB::KEY_ENTER)]]);
+    pub const ACT_SELECT_LEFT: KeyAction = KeyAction::new(
+        "toggle.select_left",
+        &[
+            &[KeyPossibility::Key(KB::KEY_LEFT)],
+            &[KeyPossibility::Key(KB::KEY_H)],
+        ],
+    );
+    pub const ACT_SELECT_RIGHT: KeyAction = KeyAction::new(
+        "toggle.select_right",
+        &[
+            &[KeyPossibility::Key(KB::KEY_RIGHT)],
+            &[KeyPossibility::Key(KB::KEY_L)],
+        ],
+    );
+
+    pub const KEY_ACTIONS: &'static [KeyAction] = &[
+        Self::ACT_TOGGLE,
+        Self::ACT_SELECT_LEFT,
+        Self::ACT_SELECT_RIGHT,
+    ];
+
+    pub fn default_receivable_events(ctx: &Context) -> ReceivableEvents {
+        ctx.keymap.receivable_events(Self::KEY_ACTIONS)
     }
 
     pub fn new<S: Into<String>>(ctx: &Context, left: S, right: S) -> Self {
         let (left, right) = (left.into(), right.into());
         let pane = SelectablePane::new(ctx, Self::KIND)
-            .with_focused_receivable_events(Self::default_receivable_events())
-            .with_styles(Self::STYLE)
+            .with_focused_receivable_events(Self::default_receivable_events(ctx))
+            .with_theme_styles(ctx, Self::STYLE)
             .with_dyn_width(DynVal::new_fixed(
                 left.chars().count() as i32 + right.chars().count() as i32,
             ))
@@ -167,13 +184,14 @@ impl Element for Toggle {
                 if self.pane.get_selectability() != Selectability::Selected || ke.is_empty() {
                     return (false, resps);
                 }
+                let km = &ctx.keymap;
                 match true {
-                    _ if ke[0] == KB::KEY_ENTER => {
+                    _ if km.matches(&Self::ACT_TOGGLE, &ke) => {
                         let resps_ = self.perform_toggle(ctx);
                         resps.extend(resps_);
                         return (true, resps);
                     }
-                    _ if ke[0] == KB::KEY_LEFT || ke[0] == KB::KEY_H => {
+                    _ if km.matches(&Self::ACT_SELECT_LEFT, &ke) => {
                         if !*self.left_selected.borrow() {
                             let resps_ = self.perform_toggle(ctx);
                             resps.extend(resps_);
@@ -181,7 +199,7 @@ impl Element for Toggle {
                         }
                         return (true, resps);
                     }
-                    _ if ke[0] == KB::KEY_RIGHT || ke[0] == KB::KEY_L => {
+                    _ if km.matches(&Self::ACT_SELECT_RIGHT, &ke) => {
                         if *self.left_selected.borrow() {
                             let resps_ = self.perform_toggle(ctx);
                             resps.extend(resps_);
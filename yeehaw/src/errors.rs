@@ -25,6 +25,12 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    #[snafu(context(false))]
+    SerdeJsonError {
+        source: serde_json::Error,
+        backtrace: Backtrace,
+    },
+
     #[snafu(context(false))]
     TokioError {
         source: tokio::sync::watch::error::SendError<bool>,
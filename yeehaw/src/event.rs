@@ -1,15 +1,19 @@
 use {
-    crate::{DrawRegion, Element, ElementID},
+    crate::{DrawRegion, Element, ElementID, ScheduledEvent, SpawnedTask, TaskOutput, TitleChange},
     std::ops::{Deref, DerefMut},
 };
 
-// TODO build in mouse events here
-
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum ReceivableEvent {
     KeyCombo(Vec<KeyPossibility>),
     /// custom event name
     Custom(String),
+    /// a kind of mouse event. NOTE mouse events are always routed by position and z-index, these
+    /// are used by elements to declare which kinds of mouse events they accept (see
+    /// Element::accepts_mouse)
+    Mouse(MousePossibility),
+    /// pasted text (see Event::Paste)
+    Paste,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -25,6 +29,12 @@ pub enum Event {
     /// then adjust all of its children based on the given context
     Resize,
 
+    /// Sent to all elements when the terminal window gains focus.
+    FocusGained,
+
+    /// Sent to all elements when the terminal window loses focus.
+    FocusLost,
+
     KeyCombo(Vec<crossterm::event::KeyEvent>),
 
     Mouse(MouseEvent),
@@ -40,8 +50,26 @@ pub enum Event {
     /// to the element receiving the event, hence they may be negative.
     ExternalMouse(MouseEvent),
 
+    /// Text pasted into the terminal (with bracketed paste), sent to the focused element which
+    /// receives ReceivableEvent::Paste as a whole rather than as individual key events. Pastes
+    /// no element receives are dropped.
+    Paste(String),
+
     /// custom event type with a name and a payload
     Custom(String, Vec<u8>),
+
+    /// An event which is routed down the element tree along the provided path of element ids
+    /// (excluding any elements already passed) irrespective of the receivable events, once the
+    /// end of the path is reached the inner event is delivered to the final element. This is
+    /// used to deliver scheduled events and task outputs back to the element which requested them.
+    Routed(Vec<ElementID>, Box<Event>),
+
+    /// The output of a task spawned by the receiving element (see Context::spawn)
+    TaskDone(TaskOutput),
+
+    /// Sent through Context::ev_tx from outside of the element tree (for instance from a
+    /// background thread) to have the tui re-render, it is not delivered to any element.
+    Redraw,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -87,6 +115,12 @@ impl From<KeyPossibility> for ReceivableEvent {
     }
 }
 
+impl From<MousePossibility> for ReceivableEvent {
+    fn from(m: MousePossibility) -> Self {
+        ReceivableEvent::Mouse(m)
+    }
+}
+
 impl From<crossterm::event::KeyEvent> for Event {
     fn from(key: crossterm::event::KeyEvent) -> Self {
         Event::KeyCombo(vec![key])
@@ -116,10 +150,16 @@ impl Event {
                 format!("KEY_COMBO=[{}]", out)
             }
             Event::ExternalMouse(_) => "EXTERNAL_MOUSE".to_string(),
+            Event::Paste(_) => "PASTE".to_string(),
             Event::Resize => "RESIZE".to_string(),
+            Event::FocusGained => "FOCUS_GAINED".to_string(),
+            Event::FocusLost => "FOCUS_LOST".to_string(),
             Event::Initialize => "REFRESH".to_string(),
             Event::Exit => "EXIT".to_string(),
             Event::Custom(name, _) => "CUSTOM=".to_string() + name,
+            Event::Routed(_, ev) => "ROUTED=".to_string() + &ev.identifier(),
+            Event::TaskDone(out) => format!("TASK_DONE={}", out.task),
+            Event::Redraw => "REDRAW".to_string(),
         }
     }
 }
@@ -139,6 +179,8 @@ impl ReceivableEvent {
                 true
             }
             (ReceivableEvent::Custom(kind1), Event::Custom(kind2, _)) => kind1 == kind2,
+            (ReceivableEvent::Mouse(m), Event::Mouse(me)) => m.matches(&me.kind),
+            (ReceivableEvent::Paste, Event::Paste(_)) => true,
             _ => false,
         }
     }
@@ -151,16 +193,99 @@ pub struct CommandEvent {
     pub args: Vec<String>,
 }
 
+impl std::fmt::Display for KeyPossibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyPossibility::Key(k) => write!(f, "{}", crate::Keyboard::key_to_string(k)),
+            KeyPossibility::Chars => write!(f, "<char>"),
+            KeyPossibility::Digits => write!(f, "<digit>"),
+            KeyPossibility::Anything => write!(f, "<any>"),
+            KeyPossibility::AnyOfKind(kind) => {
+                write!(f, "<{}>", format!("{kind:?}").to_lowercase())
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for KeyPossibility {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use crossterm::event::KeyEventKind as K;
+        match s {
+            "<char>" => Ok(KeyPossibility::Chars),
+            "<digit>" => Ok(KeyPossibility::Digits),
+            "<any>" => Ok(KeyPossibility::Anything),
+            "<press>" => Ok(KeyPossibility::AnyOfKind(K::Press)),
+            "<repeat>" => Ok(KeyPossibility::AnyOfKind(K::Repeat)),
+            "<release>" => Ok(KeyPossibility::AnyOfKind(K::Release)),
+            _ => Ok(KeyPossibility::Key(crate::Keyboard::key_from_str(s)?)),
+        }
+    }
+}
+
+/// MousePossibility is used to match a mouse event kind
+/// with a specific kind or a group of kinds
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum MousePossibility {
+    /// a press or release of the button (any button if None)
+    Click(Option<crossterm::event::MouseButton>),
+    /// a drag with the button held (any button if None)
+    Drag(Option<crossterm::event::MouseButton>),
+    /// mouse movement without any button held
+    Moved,
+    /// scrolling in any direction
+    Scroll,
+    Anything,
+}
+
+impl MousePossibility {
+    pub fn matches(&self, kind: &crossterm::event::MouseEventKind) -> bool {
+        use crossterm::event::MouseEventKind as K;
+        match self {
+            MousePossibility::Click(b) => match kind {
+                K::Down(b2) | K::Up(b2) => b.is_none_or(|b| b == *b2),
+                _ => false,
+            },
+            MousePossibility::Drag(b) => match kind {
+                K::Drag(b2) => b.is_none_or(|b| b == *b2),
+                _ => false,
+            },
+            MousePossibility::Moved => matches!(kind, K::Moved),
+            MousePossibility::Scroll => matches!(
+                kind,
+                K::ScrollUp | K::ScrollDown | K::ScrollLeft | K::ScrollRight
+            ),
+            MousePossibility::Anything => true,
+        }
+    }
+
+    /// clicks and drags of any button as well as movement, everything but scrolling
+    pub fn all_but_scroll() -> Vec<ReceivableEvent> {
+        vec![
+            MousePossibility::Click(None).into(),
+            MousePossibility::Drag(None).into(),
+            MousePossibility::Moved.into(),
+        ]
+    }
+}
+
 /// KeyPossibility is used to match a key event
 /// with a specific key or a group of keys
+///
+/// Key presses also match the repeats of the key being held, key releases and repeats are only
+/// reported by the terminal with keyboard enhancement (see Tui::with_keyboard_enhancement).
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum KeyPossibility {
+    /// the key of the kind of the key event (see KeyPossibility::released)
     Key(crossterm::event::KeyEvent),
     Chars,
     /// any char
     Digits,
     /// any digit
     Anything,
+    /// any key of the provided kind
+    AnyOfKind(crossterm::event::KeyEventKind),
 }
 
 impl From<crossterm::event::KeyEvent> for KeyPossibility {
@@ -170,6 +295,42 @@ impl From<crossterm::event::KeyEvent> for KeyPossibility {
 }
 
 impl KeyPossibility {
+    /// the release of the key
+    pub const fn released(k: crossterm::event::KeyEvent) -> Self {
+        KeyPossibility::Key(crossterm::event::KeyEvent {
+            kind: crossterm::event::KeyEventKind::Release,
+            ..k
+        })
+    }
+
+    /// only the repeats of the key being held (presses match repeats as well)
+    pub const fn repeated(k: crossterm::event::KeyEvent) -> Self {
+        KeyPossibility::Key(crossterm::event::KeyEvent {
+            kind: crossterm::event::KeyEventKind::Repeat,
+            ..k
+        })
+    }
+
+    /// whether a key event of the kind matches a possibility of the pattern kind, presses also
+    /// match repeats
+    pub fn kind_matches(
+        pattern: crossterm::event::KeyEventKind, kind: crossterm::event::KeyEventKind,
+    ) -> bool {
+        use crossterm::event::KeyEventKind as K;
+        pattern == kind || (pattern == K::Press && kind == K::Repeat)
+    }
+
+    /// whether key repeats matched by this possibility should be delivered as repeats rather than
+    /// as presses
+    pub fn wants_repeats(&self) -> bool {
+        use crossterm::event::KeyEventKind as K;
+        match self {
+            KeyPossibility::Key(k) => k.kind == K::Repeat,
+            KeyPossibility::AnyOfKind(kind) => *kind == K::Repeat,
+            _ => false,
+        }
+    }
+
     pub fn get_key(&self) -> Option<crossterm::event::KeyEvent> {
         match self {
             KeyPossibility::Key(k) => Some(*k),
@@ -192,12 +353,29 @@ impl KeyPossibility {
             }
             KeyPossibility::Digits => matches!(key_p, KeyPossibility::Digits),
             KeyPossibility::Anything => true,
+            KeyPossibility::AnyOfKind(kind) => match key_p {
+                KeyPossibility::Key(k) => Self::kind_matches(*kind, k.kind),
+                KeyPossibility::AnyOfKind(kind2) => kind == kind2,
+                _ => false,
+            },
         }
     }
 
     pub fn matches_key(&self, ct_key: &crossterm::event::KeyEvent) -> bool {
+        use crossterm::event::KeyEventKind as K;
+        let pressed = Self::kind_matches(K::Press, ct_key.kind);
         match self {
-            KeyPossibility::Key(k) => k == ct_key,
+            // NOTE the state (keypad, caps lock, etc.) of the key is ignored
+            KeyPossibility::Key(k) => {
+                Self::kind_matches(k.kind, ct_key.kind)
+                    && *k
+                        == crossterm::event::KeyEvent {
+                            kind: k.kind,
+                            state: k.state,
+                            ..*ct_key
+                        }
+            }
+            _ if !pressed && !matches!(self, KeyPossibility::AnyOfKind(_)) => false,
             KeyPossibility::Chars => {
                 matches!(ct_key.code, crossterm::event::KeyCode::Char(_))
             }
@@ -208,9 +386,31 @@ impl KeyPossibility {
                 c.is_ascii_digit()
             }
             KeyPossibility::Anything => true,
+            KeyPossibility::AnyOfKind(kind) => Self::kind_matches(*kind, ct_key.kind),
         }
     }
 
+    /// space separated human readable representation of a key combo, for instance "ctrl+w g"
+    pub fn combo_to_string(combo: &[KeyPossibility]) -> String {
+        combo
+            .iter()
+            .map(|k| k.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// parse a key combo from its string representation (see combo_to_string)
+    pub fn combo_from_str(s: &str) -> Result<Vec<KeyPossibility>, crate::Error> {
+        let combo = s
+            .split_whitespace()
+            .map(|k| k.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+        if combo.is_empty() {
+            return Err(crate::Error::new("empty key combo"));
+        }
+        Ok(combo)
+    }
+
     pub fn get_char(&self) -> Option<char> {
         match self {
             KeyPossibility::Key(k) => {
@@ -223,6 +423,7 @@ impl KeyPossibility {
             KeyPossibility::Chars => None,
             KeyPossibility::Digits => None,
             KeyPossibility::Anything => None,
+            KeyPossibility::AnyOfKind(_) => None,
         }
     }
 }
@@ -280,6 +481,15 @@ pub enum EventResponse {
     /// arbitrary custom value which can be passed back to the parent
     ///       key,   , value
     Custom(String, Vec<u8>),
+
+    /// request for an event to be delivered back to this element in the future
+    ScheduleEvent(ScheduledEvent),
+
+    /// change the title of the host terminal
+    Title(TitleChange),
+
+    /// a task was spawned by this element, records the path back down to it (see Context::spawn)
+    TaskSpawned(SpawnedTask),
 }
 
 #[derive(Clone, Debug)]
@@ -320,6 +530,9 @@ impl std::fmt::Debug for EventResponse {
             EventResponse::Move(m) => write!(f, "EventResponse::Move({:?})", m),
             EventResponse::Resize(r) => write!(f, "EventResponse::Resize({:?})", r),
             EventResponse::Custom(k, v) => write!(f, "EventResponse::Custom({}, {:?})", k, v),
+            EventResponse::ScheduleEvent(se) => write!(f, "EventResponse::ScheduleEvent({:?})", se),
+            EventResponse::Title(t) => write!(f, "EventResponse::Title({:?})", t),
+            EventResponse::TaskSpawned(st) => write!(f, "EventResponse::TaskSpawned({:?})", st),
         }
     }
 }
@@ -432,4 +645,11 @@ impl ReceivableEvents {
         }
         false
     }
+
+    /// whether any kinds of mouse events have been declared
+    pub fn has_mouse(&self) -> bool {
+        self.0
+            .iter()
+            .any(|e| matches!(e, ReceivableEvent::Mouse(_)))
+    }
 }
@@ -0,0 +1,232 @@
+use {
+    crate::{ChPlus, Size},
+    crossterm::style::{ContentStyle, StyledContent},
+    std::time::Duration,
+};
+
+/// Environment variable which when set causes snapshot assertions to (re)write the snapshot file
+/// instead of comparing against it.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "YEEHAW_UPDATE_SNAPSHOTS";
+
+/// HeadlessScreen is an in-memory stand-in for the terminal. When a Tui is created with
+/// `Tui::new_headless` all rendering is written into this grid of cells rather than to stdout,
+/// which allows for whole screens to be tested without a real terminal.
+///
+/// The headless screen also keeps a virtual clock which is used as the duration since launch for
+/// the Tui, this allows for time dependant drawing (such as time gradients) to be deterministic.
+#[derive(Clone, Debug)]
+pub struct HeadlessScreen {
+    size: Size,
+
+    /// the rendered cells of the screen
+    //         rows(y)
+    //          │  col(x)
+    //          │   │
+    cells: Vec<Vec<StyledContent<ChPlus>>>,
+
+    /// the cursor position used when acting as a Backend
+    pub(crate) cursor: (u16, u16),
+
+    /// the number of bytes of drawing output, see bytes_written
+    bytes_written: usize,
+
+    /// the text of the rows scrolled off the top of the screen, oldest first
+    scrollback: Vec<String>,
+
+    /// the virtual time elapsed since the launch of the tui
+    pub elapsed: Duration,
+}
+
+impl HeadlessScreen {
+    pub fn new(size: Size) -> Self {
+        let mut s = HeadlessScreen {
+            size,
+            cells: Vec::new(),
+            cursor: (0, 0),
+            bytes_written: 0,
+            scrollback: Vec::new(),
+            elapsed: Duration::default(),
+        };
+        s.clear();
+        s
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// resize the screen, all cells are cleared
+    pub fn resize(&mut self, size: Size) {
+        self.size = size;
+        self.clear();
+    }
+
+    /// reset all the cells to blank
+    pub fn clear(&mut self) {
+        let blank = Self::blank_cell();
+        self.cells = vec![vec![blank; self.size.width as usize]; self.size.height as usize];
+    }
+
+    /// scroll the rows up, the rows scrolled off the top are kept as the scrollback
+    pub fn scroll_up(&mut self, rows: usize) {
+        let rows = rows.min(self.cells.len());
+        for y in 0..rows {
+            let line = self.row_text(y).trim_end().to_string();
+            self.scrollback.push(line);
+        }
+        self.cells.drain(..rows);
+        let blank = vec![Self::blank_cell(); self.size.width as usize];
+        self.cells.resize(self.size.height as usize, blank);
+    }
+
+    /// scroll the rows down, the rows scrolled off the bottom are lost
+    pub fn scroll_down(&mut self, rows: usize) {
+        let rows = rows.min(self.cells.len());
+        self.cells.truncate(self.cells.len() - rows);
+        let blank = vec![Self::blank_cell(); self.size.width as usize];
+        self.cells.splice(0..0, std::iter::repeat_n(blank, rows));
+    }
+
+    /// the text of the rows which have been scrolled off the top of the screen, oldest first
+    pub fn scrollback(&self) -> &[String] {
+        &self.scrollback
+    }
+
+    /// advance the virtual clock
+    pub fn advance(&mut self, dur: Duration) {
+        self.elapsed += dur;
+    }
+
+    /// the number of bytes the CrosstermBackend would have written for the drawing output (the
+    /// cursor moves, printed content, scrolling and synchronized updates) so far, useful for
+    /// measuring the output size of a render
+    pub fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+
+    pub fn reset_bytes_written(&mut self) {
+        self.bytes_written = 0;
+    }
+
+    /// count the bytes of the command as written to a terminal
+    pub(crate) fn count_bytes<C: crossterm::Command>(&mut self, cmd: C) {
+        let mut ansi = String::new();
+        if cmd.write_ansi(&mut ansi).is_ok() {
+            self.bytes_written += ansi.len();
+        }
+    }
+
+    fn blank_cell() -> StyledContent<ChPlus> {
+        StyledContent::new(ContentStyle::default(), ChPlus::Char(' '))
+    }
+
+    /// set the content of a cell, positions outside of the screen are ignored
+    pub fn set_cell(&mut self, x: usize, y: usize, content: StyledContent<ChPlus>) {
+        if let Some(cell) = self.cells.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *cell = content;
+        }
+    }
+
+    pub fn cell(&self, x: usize, y: usize) -> Option<&StyledContent<ChPlus>> {
+        self.cells.get(y).and_then(|row| row.get(x))
+    }
+
+    pub fn style_at(&self, x: usize, y: usize) -> Option<ContentStyle> {
+        self.cell(x, y).map(|c| *c.style())
+    }
+
+    /// the text of a single row of the screen (including trailing whitespace)
+    pub fn row_text(&self, y: usize) -> String {
+        let Some(row) = self.cells.get(y) else {
+            return String::new();
+        };
+        row.iter()
+            .map(|c| match c.content() {
+                ChPlus::Char(ch) => ch.to_string(),
+                ChPlus::Str(s) => s.to_string(),
+                ChPlus::Transparent | ChPlus::Skip => " ".to_string(),
+            })
+            .collect()
+    }
+
+    /// the text of the whole screen, trailing whitespace is trimmed from each row
+    pub fn text(&self) -> String {
+        (0..self.cells.len())
+            .map(|y| self.row_text(y).trim_end().to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn contains(&self, s: &str) -> bool {
+        self.find(s).is_some()
+    }
+
+    /// find the position of the first occurrence of the provided text on the screen
+    //                                   (x    , y    )
+    pub fn find(&self, s: &str) -> Option<(usize, usize)> {
+        for y in 0..self.cells.len() {
+            let row = self.row_text(y);
+            if let Some(byte_i) = row.find(s) {
+                return Some((row[..byte_i].chars().count(), y));
+            }
+        }
+        None
+    }
+
+    /// A textual snapshot of the screen used for regression testing. The snapshot contains the
+    /// screen text followed by each horizontal run of identically styled non-default cells.
+    pub fn snapshot(&self) -> String {
+        let mut out = format!("size: {}x{}\n", self.size.width, self.size.height);
+        out += "--- text ---\n";
+        out += &self.text();
+        out += "\n--- styles ---\n";
+        let default_sty = ContentStyle::default();
+        for (y, row) in self.cells.iter().enumerate() {
+            let mut x = 0;
+            while x < row.len() {
+                let sty = *row[x].style();
+                let start_x = x;
+                while x < row.len() && *row[x].style() == sty {
+                    x += 1;
+                }
+                if sty == default_sty {
+                    continue;
+                }
+                out += &format!(
+                    "{y}:{start_x}-{} fg={:?} bg={:?} ul={:?} attrs={:?}\n",
+                    x - 1,
+                    sty.foreground_color,
+                    sty.background_color,
+                    sty.underline_color,
+                    sty.attributes
+                );
+            }
+        }
+        out
+    }
+
+    /// Compare the screen against the snapshot file at the provided path. If the file does not
+    /// exist (or the YEEHAW_UPDATE_SNAPSHOTS environment variable is set) then the snapshot is
+    /// written to the file instead.
+    pub fn assert_snapshot<P: AsRef<std::path::Path>>(&self, path: P) {
+        let path = path.as_ref();
+        let snap = self.snapshot();
+        if std::env::var(UPDATE_SNAPSHOTS_ENV).is_ok() || !path.exists() {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).expect("failed to create snapshot directory");
+            }
+            std::fs::write(path, snap).expect("failed to write snapshot");
+            return;
+        }
+        let existing = std::fs::read_to_string(path).expect("failed to read snapshot");
+        if existing != snap {
+            panic!(
+                "snapshot mismatch for {}\n(set {} to update)\n--- expected ---\n{}\n--- actual ---\n{}",
+                path.display(),
+                UPDATE_SNAPSHOTS_ENV,
+                existing,
+                snap
+            );
+        }
+    }
+}
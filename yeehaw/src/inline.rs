@@ -0,0 +1,60 @@
+use {
+    crate::{DrawCh, DrawChs2D, Style},
+    std::{cell::RefCell, rc::Rc},
+};
+
+/// InlineControl carries the changes requested by the application of an inline tui (see
+/// Tui::run_in_line). It is carried in the Context and shared between all clones, the tui applies
+/// the changes on the next render. A full screen tui ignores it.
+///
+/// The height reserved for the tui may be changed at runtime, and finished lines may be committed
+/// into the terminal above the tui where they scroll into the scrollback as further lines are
+/// committed (like the log lines printed above a progress bar).
+#[derive(Clone, Default, Debug)]
+pub struct InlineControl {
+    inner: Rc<RefCell<InlineRequests>>,
+}
+
+#[derive(Default, Debug)]
+struct InlineRequests {
+    height: Option<u16>,
+    /// the lines waiting to be committed above the tui
+    lines: Vec<Vec<DrawCh>>,
+}
+
+impl InlineControl {
+    /// change the number of rows reserved for the tui, limited to the screen height
+    pub fn set_height(&self, height: u16) {
+        self.inner.borrow_mut().height = Some(height);
+    }
+
+    /// commit an unstyled line above the tui, lines wider than the screen are wrapped
+    pub fn print_line(&self, line: &str) {
+        self.print_styled_line(line, Style::default());
+    }
+
+    pub fn print_styled_line(&self, line: &str, sty: Style) {
+        let line = line.replace('\t', "    ");
+        for l in line.split('\n') {
+            let chs = DrawCh::str_to_draw_chs(l, sty.clone());
+            self.inner.borrow_mut().lines.push(chs);
+        }
+    }
+
+    /// commit each row of the content as a line above the tui
+    pub fn print_content(&self, content: DrawChs2D) {
+        self.inner.borrow_mut().lines.extend(content.0);
+    }
+
+    /// whether there are changes which have yet to be applied
+    pub fn is_pending(&self) -> bool {
+        let req = self.inner.borrow();
+        req.height.is_some() || !req.lines.is_empty()
+    }
+
+    /// take the requested height and the lines to commit
+    pub(crate) fn take(&self) -> (Option<u16>, Vec<Vec<DrawCh>>) {
+        let mut req = self.inner.borrow_mut();
+        (req.height.take(), std::mem::take(&mut req.lines))
+    }
+}
@@ -1,6 +1,6 @@
 use {
     crate::KeyPossibility,
-    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     std::time::{Duration, Instant},
 };
 
@@ -143,6 +143,15 @@ impl Keyboard {
                 return None;
             }
 
+            // repeats are delivered as presses unless explicitly requested
+            let ev = if ev.kind == KeyEventKind::Repeat && !ec[i].wants_repeats() {
+                KeyEvent {
+                    kind: KeyEventKind::Press,
+                    ..ev
+                }
+            } else {
+                ev
+            };
             out_ev.insert(0, ev);
             j -= 1;
         }
@@ -164,6 +173,135 @@ impl Keyboard {
         Some(out_ev)
     }
 
+    /// The length of the longest partial match of the event combo, that is the greatest number of
+    /// the most recent key events which match the start of the combo without completing it. Zero
+    /// is returned if there is no partial match.
+    pub fn partial_match_len(&mut self, ec: &[KeyPossibility]) -> usize {
+        for n in (1..ec.len()).rev() {
+            if self.matches(&ec[..n], false).is_some() {
+                return n;
+            }
+        }
+        0
+    }
+
+    /// the n most recent key events which have not been consumed (oldest first)
+    pub fn recent_keys(&self, n: usize) -> Vec<KeyEvent> {
+        let mut out = self
+            .evs
+            .iter()
+            .rev()
+            .map_while(|(ev, _)| *ev)
+            .take(n)
+            .collect::<Vec<_>>();
+        out.reverse();
+        out
+    }
+
+    /// a short human readable representation of a key event, for instance "ctrl+w", "G" or "esc"
+    pub fn key_to_string(k: &KeyEvent) -> String {
+        let code = match k.code {
+            KeyCode::Char(' ') => "space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::F(n) => format!("f{n}"),
+            KeyCode::BackTab => "backtab".to_string(),
+            KeyCode::PageUp => "pgup".to_string(),
+            KeyCode::PageDown => "pgdn".to_string(),
+            c => format!("{c:?}").to_lowercase(),
+        };
+        let mut out = String::new();
+        match k.kind {
+            KeyEventKind::Press => {}
+            KeyEventKind::Repeat => out.push_str("repeat+"),
+            KeyEventKind::Release => out.push_str("release+"),
+        }
+        if k.modifiers.contains(KeyModifiers::CONTROL) {
+            out.push_str("ctrl+");
+        }
+        if k.modifiers.contains(KeyModifiers::ALT) {
+            out.push_str("alt+");
+        }
+        // the shift of a char is already represented by its casing
+        if k.modifiers.contains(KeyModifiers::SHIFT)
+            && !matches!(k.code, KeyCode::Char(_) | KeyCode::BackTab)
+        {
+            out.push_str("shift+");
+        }
+        out.push_str(&code);
+        out
+    }
+
+    /// space separated representation of a sequence of key events (see key_to_string)
+    pub fn keys_to_string(keys: &[KeyEvent]) -> String {
+        keys.iter()
+            .map(Self::key_to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// parse a key event from its string representation (see key_to_string)
+    pub fn key_from_str(s: &str) -> Result<KeyEvent, crate::Error> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut kind = KeyEventKind::Press;
+        let mut rest = s;
+        'mods: loop {
+            for (prefix, k) in [
+                ("repeat+", KeyEventKind::Repeat),
+                ("release+", KeyEventKind::Release),
+            ] {
+                if let Some(r) = rest.strip_prefix(prefix).filter(|r| !r.is_empty()) {
+                    kind = k;
+                    rest = r;
+                    continue 'mods;
+                }
+            }
+            for (prefix, m) in [
+                ("ctrl+", KeyModifiers::CONTROL),
+                ("alt+", KeyModifiers::ALT),
+                ("shift+", KeyModifiers::SHIFT),
+            ] {
+                // NOTE the remainder may not be empty so that "ctrl++" is ctrl and the plus key
+                if let Some(r) = rest.strip_prefix(prefix).filter(|r| !r.is_empty()) {
+                    modifiers |= m;
+                    rest = r;
+                    continue 'mods;
+                }
+            }
+            break;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest {
+                "space" => KeyCode::Char(' '),
+                "backtab" => {
+                    modifiers |= KeyModifiers::SHIFT;
+                    KeyCode::BackTab
+                }
+                "pgup" => KeyCode::PageUp,
+                "pgdn" => KeyCode::PageDown,
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "backspace" => KeyCode::Backspace,
+                "tab" => KeyCode::Tab,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "delete" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                _ => match rest.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) => KeyCode::F(n),
+                    None => return Err(crate::Error::new(&format!("unknown key: {s}"))),
+                },
+            },
+        };
+        Ok(KeyEvent::new_with_kind(code, modifiers, kind))
+    }
+
     //---------------------------------------
 
     /// The last number ignoring the previous `ignoring_previous` number of prevRunes
@@ -197,6 +335,9 @@ impl Keyboard {
     //--------------------------------------------
 
     pub const KEY_ESC: KeyEvent = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+    pub const KEY_F1: KeyEvent = KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE);
+    pub const KEY_F9: KeyEvent = KeyEvent::new(KeyCode::F(9), KeyModifiers::NONE);
+    pub const KEY_F12: KeyEvent = KeyEvent::new(KeyCode::F(12), KeyModifiers::NONE);
     pub const KEY_ENTER: KeyEvent = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
     pub const KEY_SHIFT_ENTER: KeyEvent = KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT);
     pub const KEY_ALT_ENTER: KeyEvent = KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT);
@@ -222,11 +363,17 @@ impl Keyboard {
     pub const KEY_RIGHT: KeyEvent = KeyEvent::new(KeyCode::Right, KeyModifiers::NONE);
     pub const KEY_UP: KeyEvent = KeyEvent::new(KeyCode::Up, KeyModifiers::NONE);
     pub const KEY_DOWN: KeyEvent = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+    pub const KEY_PAGE_UP: KeyEvent = KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE);
+    pub const KEY_PAGE_DOWN: KeyEvent = KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE);
+    pub const KEY_HOME: KeyEvent = KeyEvent::new(KeyCode::Home, KeyModifiers::NONE);
+    pub const KEY_END: KeyEvent = KeyEvent::new(KeyCode::End, KeyModifiers::NONE);
 
     pub const KEY_CTRL_LEFT: KeyEvent = KeyEvent::new(KeyCode::Left, KeyModifiers::CONTROL);
     pub const KEY_CTRL_RIGHT: KeyEvent = KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL);
     pub const KEY_CTRL_UP: KeyEvent = KeyEvent::new(KeyCode::Up, KeyModifiers::CONTROL);
     pub const KEY_CTRL_DOWN: KeyEvent = KeyEvent::new(KeyCode::Down, KeyModifiers::CONTROL);
+    pub const KEY_CTRL_BACKSPACE: KeyEvent =
+        KeyEvent::new(KeyCode::Backspace, KeyModifiers::CONTROL);
 
     pub const KEY_SHIFT_LEFT: KeyEvent = KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT);
     pub const KEY_SHIFT_RIGHT: KeyEvent = KeyEvent::new(KeyCode::Right, KeyModifiers::SHIFT);
@@ -356,6 +503,10 @@ impl Keyboard {
     pub const KEY_CTRL_X: KeyEvent = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL);
     pub const KEY_CTRL_Y: KeyEvent = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL);
     pub const KEY_CTRL_Z: KeyEvent = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL);
+    pub const KEY_CTRL_SHIFT_Z: KeyEvent = KeyEvent::new(
+        KeyCode::Char('Z'),
+        KeyModifiers::CONTROL.union(KeyModifiers::SHIFT),
+    );
 
     pub const KEY_ALT_A: KeyEvent = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::ALT);
     pub const KEY_ALT_B: KeyEvent = KeyEvent::new(KeyCode::Char('b'), KeyModifiers::ALT);
@@ -439,3 +590,65 @@ impl Keyboard {
     pub const KEY_OPT_Y: KeyEvent = KeyEvent::new(KeyCode::Char('¥'), KeyModifiers::NONE);
     pub const KEY_OPT_Z: KeyEvent = KeyEvent::new(KeyCode::Char('Ω'), KeyModifiers::NONE);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_match() {
+        let mut kb = Keyboard::default();
+        let gg = [Keyboard::KEY_G.into(), Keyboard::KEY_G.into()];
+        let gdx = [
+            Keyboard::KEY_G.into(),
+            Keyboard::KEY_D.into(),
+            Keyboard::KEY_X.into(),
+        ];
+        kb.add_ev(Keyboard::KEY_G);
+        assert_eq!(kb.partial_match_len(&gg), 1);
+        assert_eq!(kb.partial_match_len(&gdx), 1);
+        kb.add_ev(Keyboard::KEY_D);
+        assert_eq!(kb.partial_match_len(&gg), 0);
+        assert_eq!(kb.partial_match_len(&gdx), 2);
+        assert_eq!(kb.recent_keys(2), vec![Keyboard::KEY_G, Keyboard::KEY_D]);
+        kb.add_ev(Keyboard::KEY_X);
+        assert_eq!(kb.partial_match_len(&gdx), 0);
+        assert!(kb.matches(&gdx, true).is_some());
+        assert!(kb.recent_keys(3).is_empty());
+    }
+
+    #[test]
+    fn test_keys_to_string() {
+        let keys = [
+            Keyboard::KEY_CTRL_W,
+            Keyboard::KEY_G,
+            Keyboard::KEY_BACKTAB,
+            Keyboard::KEY_ESC,
+        ];
+        assert_eq!(Keyboard::keys_to_string(&keys), "ctrl+w g backtab esc");
+    }
+
+    #[test]
+    fn test_key_from_str() {
+        let keys = [
+            Keyboard::KEY_CTRL_W,
+            Keyboard::KEY_SHIFT_G,
+            Keyboard::KEY_BACKTAB,
+            Keyboard::KEY_SPACE,
+            Keyboard::KEY_SHIFT_DOWN,
+            Keyboard::KEY_ALT_MINUS,
+            Keyboard::KEY_F1,
+            Keyboard::KEY_PLUS,
+        ];
+        for k in keys {
+            let s = Keyboard::key_to_string(&k);
+            assert_eq!(Keyboard::key_from_str(&s).unwrap(), k, "{s}");
+        }
+        assert_eq!(
+            Keyboard::key_from_str("ctrl++").unwrap(),
+            KeyEvent::new(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+        assert!(Keyboard::key_from_str("ctrl+").is_err());
+        assert!(Keyboard::key_from_str("nope").is_err());
+    }
+}
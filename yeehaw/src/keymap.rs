@@ -0,0 +1,232 @@
+use {
+    crate::{Error, KeyPossibility, ReceivableEvent, ReceivableEvents},
+    crossterm::event::KeyEvent,
+    std::collections::BTreeMap,
+    std::path::Path,
+    std::{cell::RefCell, rc::Rc},
+};
+
+/// A named action of an element kind along with the key combos which trigger it by default.
+/// Names are by convention of the form "kind.action" (for instance "textbox.delete_word").
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyAction {
+    pub name: &'static str,
+    pub defaults: &'static [&'static [KeyPossibility]],
+}
+
+impl KeyAction {
+    pub const fn new(name: &'static str, defaults: &'static [&'static [KeyPossibility]]) -> Self {
+        KeyAction { name, defaults }
+    }
+}
+
+/// The serializable contents of a keymap, the key combos which replace the defaults of an action
+/// keyed by the action name. Each combo is a space separated sequence of keys as written by
+/// KeyPossibility::combo_to_string (for instance "ctrl+w", "g g" or "<char>").
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct KeyMapEntries {
+    #[serde(default)]
+    pub actions: BTreeMap<String, Vec<String>>,
+}
+
+impl KeyMapEntries {
+    pub fn with_action(mut self, name: &str, combos: Vec<Vec<KeyPossibility>>) -> Self {
+        let combos = combos
+            .iter()
+            .map(|c| KeyPossibility::combo_to_string(c))
+            .collect();
+        self.actions.insert(name.to_string(), combos);
+        self
+    }
+
+    /// errors if any of the key combos cannot be parsed
+    pub fn validate(&self) -> Result<(), Error> {
+        for combo in self.actions.values().flatten() {
+            KeyPossibility::combo_from_str(combo)?;
+        }
+        Ok(())
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        let entries: Self = serde_json::from_str(s)?;
+        entries.validate()?;
+        Ok(entries)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        Ok(std::fs::write(path, self.to_json()?)?)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+}
+
+/// The KeyMap holds the user overrides of the key combos of named actions. Widgets declare their
+/// actions (see KeyAction) and consult the keymap for both their receivable events and to
+/// determine which action a received key combo triggers. It is carried in the Context and shared
+/// between all clones.
+///
+/// NOTE widgets build their receivable events when they are constructed, so overrides should be
+/// set (`ctx.keymap.set_entries(KeyMapEntries::load(path)?)`) before the elements are created.
+#[derive(Clone, Default, Debug)]
+pub struct KeyMap {
+    entries: Rc<RefCell<KeyMapEntries>>,
+    /// the parsed key combos of the entries, kept in sync with the entries so that combos are
+    /// not re-parsed on every key press
+    parsed: Rc<RefCell<BTreeMap<String, Vec<Vec<KeyPossibility>>>>>,
+}
+
+impl KeyMap {
+    pub fn new(entries: KeyMapEntries) -> Self {
+        let km = KeyMap {
+            entries: Rc::new(RefCell::new(entries)),
+            parsed: Rc::new(RefCell::new(BTreeMap::new())),
+        };
+        km.parse_entries();
+        km
+    }
+
+    pub fn with_action(self, name: &str, combos: Vec<Vec<KeyPossibility>>) -> Self {
+        self.set_action(name, combos);
+        self
+    }
+
+    /// replace the key combos of an action
+    pub fn set_action(&self, name: &str, combos: Vec<Vec<KeyPossibility>>) {
+        let mut entries = self.entries.borrow_mut();
+        *entries = std::mem::take(&mut *entries).with_action(name, combos.clone());
+        self.parsed.borrow_mut().insert(name.to_string(), combos);
+    }
+
+    /// remove the override of an action, reverting it to its defaults
+    pub fn reset_action(&self, name: &str) {
+        self.entries.borrow_mut().actions.remove(name);
+        self.parsed.borrow_mut().remove(name);
+    }
+
+    pub fn set_entries(&self, entries: KeyMapEntries) {
+        *self.entries.borrow_mut() = entries;
+        self.parse_entries();
+    }
+
+    pub fn entries(&self) -> KeyMapEntries {
+        self.entries.borrow().clone()
+    }
+
+    /// parse the key combos of all the entries, invalid combos are logged and dropped
+    fn parse_entries(&self) {
+        let parsed = self
+            .entries
+            .borrow()
+            .actions
+            .iter()
+            .map(|(name, combos)| {
+                let combos = combos
+                    .iter()
+                    .filter_map(|c| match KeyPossibility::combo_from_str(c) {
+                        Ok(combo) => Some(combo),
+                        Err(e) => {
+                            log_err!("invalid key combo for {}: {}", name, e);
+                            None
+                        }
+                    })
+                    .collect();
+                (name.clone(), combos)
+            })
+            .collect();
+        *self.parsed.borrow_mut() = parsed;
+    }
+
+    /// the key combos of the action, the override if there is one otherwise the defaults
+    pub fn combos(&self, action: &KeyAction) -> Vec<Vec<KeyPossibility>> {
+        match self.parsed.borrow().get(action.name) {
+            Some(combos) => combos.clone(),
+            None => action.defaults.iter().map(|c| c.to_vec()).collect(),
+        }
+    }
+
+    /// the receivable events for all the key combos of the actions
+    pub fn receivable_events(&self, actions: &[KeyAction]) -> ReceivableEvents {
+        ReceivableEvents(
+            actions
+                .iter()
+                .flat_map(|a| self.combos(a))
+                .map(ReceivableEvent::KeyCombo)
+                .collect(),
+        )
+    }
+
+    /// does the key combo trigger the action
+    pub fn matches(&self, action: &KeyAction, keys: &[KeyEvent]) -> bool {
+        let is_match = |combo: &[KeyPossibility]| {
+            combo.len() == keys.len() && combo.iter().zip(keys).all(|(p, k)| p.matches_key(k))
+        };
+        match self.parsed.borrow().get(action.name) {
+            Some(combos) => combos.iter().any(|c| is_match(c)),
+            None => action.defaults.iter().any(|c| is_match(c)),
+        }
+    }
+
+    /// the first of the actions which is triggered by the key combo
+    pub fn action<'a>(&self, actions: &'a [KeyAction], keys: &[KeyEvent]) -> Option<&'a KeyAction> {
+        actions.iter().find(|a| self.matches(a, keys))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::Keyboard as KB};
+
+    const DOWN: KeyAction = KeyAction::new(
+        "test.down",
+        &[
+            &[KeyPossibility::Key(KB::KEY_DOWN)],
+            &[KeyPossibility::Key(KB::KEY_J)],
+        ],
+    );
+
+    #[test]
+    fn test_override() {
+        let km = KeyMap::default();
+        assert!(km.matches(&DOWN, &[KB::KEY_J]));
+        assert!(!km.matches(&DOWN, &[KB::KEY_CTRL_N]));
+
+        km.set_action(
+            DOWN.name,
+            vec![
+                vec![KB::KEY_CTRL_N.into()],
+                vec![KB::KEY_G.into(), KB::KEY_J.into()],
+            ],
+        );
+        assert!(!km.matches(&DOWN, &[KB::KEY_J]));
+        assert!(km.matches(&DOWN, &[KB::KEY_CTRL_N]));
+        assert!(km.matches(&DOWN, &[KB::KEY_G, KB::KEY_J]));
+        assert_eq!(km.receivable_events(&[DOWN]).0.len(), 2);
+
+        km.reset_action(DOWN.name);
+        assert!(km.matches(&DOWN, &[KB::KEY_DOWN]));
+    }
+
+    #[test]
+    fn test_json() {
+        let entries = KeyMapEntries::from_json(
+            r#"{"actions": {"test.down": ["ctrl+n", "shift+down", "g <char>", "space"]}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            KeyMapEntries::from_json(&entries.to_json().unwrap()).unwrap(),
+            entries
+        );
+        let km = KeyMap::new(entries);
+        assert!(km.matches(&DOWN, &[KB::KEY_SHIFT_DOWN]));
+        assert!(km.matches(&DOWN, &[KB::KEY_G, KB::KEY_X]));
+        assert!(km.matches(&DOWN, &[KB::KEY_SPACE]));
+
+        assert!(KeyMapEntries::from_json(r#"{"actions": {"test.down": ["ctrl+nope"]}}"#).is_err());
+    }
+}
@@ -0,0 +1,381 @@
+use {
+    crate::*,
+    std::{collections::HashMap, path::Path},
+};
+
+/// A declarative description of an element tree of the built-in containers and widgets, usually
+/// loaded from a json file (any serde format may be used by deserializing into a LayoutNode).
+/// For instance:
+///
+/// ```json
+/// { "kind": "pane", "children": [
+///     { "kind": "label", "text": "hello", "x": "50% - 2" },
+///     { "kind": "vertical_stack", "name": "body", "y": 1, "height": "100% - 1", "children": [
+///         { "kind": "pane", "bg": "navy" },
+///         { "kind": "button", "name": "save", "text": "Save" }
+///     ]}
+/// ]}
+/// ```
+///
+/// Positions and sizes are either fixed numbers or strings combining fixed values and percentages
+/// of the parent size (see DynVal::from_str), within stacks only the size along the direction of
+/// the stack is used. Named elements are given the name as their state name (see SessionState)
+/// and are accessible from the built Layout so that callbacks may be attached by name.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct LayoutNode {
+    #[serde(flatten)]
+    pub kind: LayoutKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<LayoutVal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<LayoutVal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<LayoutVal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<LayoutVal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub z: Option<ZIndex>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tooltip: Option<String>,
+    /// the foreground color name of the element (see Color::from_name), colored widgets are no
+    /// longer styled by the theme (see Theme)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fg: Option<String>,
+    /// the background color name of the element (see Color::from_name and fg), selectable
+    /// widgets only use it when they are not focused
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bg: Option<String>,
+}
+
+/// the kind of element described by a LayoutNode along with its kind specific fields
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LayoutKind {
+    /// a ParentPane
+    Pane {
+        #[serde(default)]
+        children: Vec<LayoutNode>,
+    },
+    VerticalStack {
+        #[serde(default)]
+        children: Vec<LayoutNode>,
+    },
+    HorizontalStack {
+        #[serde(default)]
+        children: Vec<LayoutNode>,
+    },
+    Tabs {
+        #[serde(default)]
+        tabs: Vec<LayoutTab>,
+    },
+    Label {
+        text: String,
+    },
+    Button {
+        text: String,
+    },
+    Checkbox,
+    Toggle {
+        left: String,
+        right: String,
+    },
+    Radio {
+        options: Vec<String>,
+    },
+    ListBox {
+        #[serde(default)]
+        entries: Vec<String>,
+    },
+    #[cfg(feature = "textbox")]
+    TextBox {
+        #[serde(default)]
+        text: String,
+    },
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct LayoutTab {
+    pub title: String,
+    pub content: LayoutNode,
+}
+
+/// a position or size within a layout, either a fixed value or a DynVal expression (for
+/// instance "50%" or "100% - 2")
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum LayoutVal {
+    Fixed(i32),
+    Expr(String),
+}
+
+impl LayoutVal {
+    pub fn to_dyn_val(&self) -> Result<DynVal, Error> {
+        match self {
+            LayoutVal::Fixed(v) => Ok(DynVal::new_fixed(*v)),
+            LayoutVal::Expr(s) => s.parse(),
+        }
+    }
+}
+
+/// An element built from a LayoutNode
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum LayoutElement {
+    Pane(ParentPane),
+    VerticalStack(VerticalStack),
+    HorizontalStack(HorizontalStack),
+    Tabs(Tabs),
+    Label(Label),
+    Button(Button),
+    Checkbox(Checkbox),
+    Toggle(Toggle),
+    Radio(RadioButtons),
+    ListBox(ListBox),
+    #[cfg(feature = "textbox")]
+    TextBox(TextBox),
+}
+
+impl LayoutElement {
+    pub fn element(&self) -> Box<dyn Element> {
+        match self {
+            LayoutElement::Pane(el) => Box::new(el.clone()),
+            LayoutElement::VerticalStack(el) => Box::new(el.clone()),
+            LayoutElement::HorizontalStack(el) => Box::new(el.clone()),
+            LayoutElement::Tabs(el) => Box::new(el.clone()),
+            LayoutElement::Label(el) => Box::new(el.clone()),
+            LayoutElement::Button(el) => Box::new(el.clone()),
+            LayoutElement::Checkbox(el) => Box::new(el.clone()),
+            LayoutElement::Toggle(el) => Box::new(el.clone()),
+            LayoutElement::Radio(el) => Box::new(el.clone()),
+            LayoutElement::ListBox(el) => Box::new(el.clone()),
+            #[cfg(feature = "textbox")]
+            LayoutElement::TextBox(el) => Box::new(el.clone()),
+        }
+    }
+
+    /// sets the foreground and background of the element. For selectable widgets the foreground
+    /// is used in every selectability while the background is only used when the widget is
+    /// ready, so that the focused and unselectable highlighting remains visible. The colors
+    /// replace the theme styles of the widget.
+    fn set_colors(&self, ctx: &Context, fg: Option<Color>, bg: Option<Color>) {
+        let recolor = |pane: &SelectablePane| Self::recolor(pane, fg.clone(), bg.clone());
+        let container = match self {
+            LayoutElement::Pane(el) => el.clone(),
+            LayoutElement::VerticalStack(el) => el.pane.clone(),
+            LayoutElement::HorizontalStack(el) => el.pane.clone(),
+            LayoutElement::Tabs(el) => el.pane.pane.clone(),
+            LayoutElement::Label(el) => {
+                let mut sty = el.pane.get_style();
+                if let Some(fg) = fg {
+                    sty.set_fg(fg);
+                }
+                if let Some(bg) = bg {
+                    sty.set_bg(bg);
+                }
+                el.pane.set_content_style(sty.clone());
+                el.set_style(sty);
+                return;
+            }
+            LayoutElement::Button(el) => {
+                let _ = el.clone().with_styles(ctx, recolor(&el.pane));
+                return;
+            }
+            LayoutElement::Checkbox(el) => {
+                let _ = el.clone().with_styles(recolor(&el.pane));
+                return;
+            }
+            LayoutElement::Toggle(el) => {
+                let _ = el.clone().with_styles(recolor(&el.pane));
+                return;
+            }
+            LayoutElement::Radio(el) => {
+                let _ = el.clone().with_styles(recolor(&el.pane));
+                return;
+            }
+            LayoutElement::ListBox(el) => {
+                let _ = el.clone().with_styles(recolor(&el.pane));
+                return;
+            }
+            #[cfg(feature = "textbox")]
+            LayoutElement::TextBox(el) => {
+                let _ = el.clone().with_styles(recolor(&el.pane));
+                return;
+            }
+        };
+        if let Some(fg) = fg {
+            container.set_fg(fg);
+        }
+        if let Some(bg) = bg {
+            container.set_bg(bg);
+        }
+    }
+
+    /// the current styles of the selectable pane with the colors applied (see set_colors)
+    fn recolor(pane: &SelectablePane, fg: Option<Color>, bg: Option<Color>) -> SelStyles {
+        pane.refresh_theme();
+        let mut styles = pane.styles.borrow().clone();
+        if let Some(fg) = fg {
+            styles.selected_style.set_fg(fg.clone());
+            styles.ready_style.set_fg(fg.clone());
+            styles.unselectable_style.set_fg(fg);
+        }
+        if let Some(bg) = bg {
+            styles.ready_style.set_bg(bg);
+        }
+        styles
+    }
+}
+
+/// The element tree built from a LayoutNode
+#[derive(Clone)]
+pub struct Layout {
+    pub root: LayoutElement,
+    /// the named elements of the tree
+    pub named: HashMap<String, LayoutElement>,
+}
+
+impl Layout {
+    /// the named element, for instance to attach a callback:
+    /// `if let Some(LayoutElement::Button(b)) = layout.get("save") { b.set_fn(..) }`
+    pub fn get(&self, name: &str) -> Option<&LayoutElement> {
+        self.named.get(name)
+    }
+
+    pub fn element(&self, name: &str) -> Option<Box<dyn Element>> {
+        self.get(name).map(|el| el.element())
+    }
+}
+
+impl LayoutNode {
+    pub fn new(kind: LayoutKind) -> Self {
+        LayoutNode {
+            kind,
+            name: None,
+            x: None,
+            y: None,
+            width: None,
+            height: None,
+            z: None,
+            tooltip: None,
+            fg: None,
+            bg: None,
+        }
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        Ok(std::fs::write(path, self.to_json()?)?)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// build the element tree, errors on invalid values or duplicate names
+    pub fn build(&self, ctx: &Context) -> Result<Layout, Error> {
+        let mut named = HashMap::new();
+        let root = self.build_el(ctx, &mut named)?;
+        Ok(Layout { root, named })
+    }
+
+    fn build_el(
+        &self, ctx: &Context, named: &mut HashMap<String, LayoutElement>,
+    ) -> Result<LayoutElement, Error> {
+        let out = match &self.kind {
+            LayoutKind::Pane { children } => {
+                let pp = ParentPane::new(ctx, "pane");
+                for child in children {
+                    pp.add_element(child.build_el(ctx, named)?.element());
+                }
+                LayoutElement::Pane(pp)
+            }
+            LayoutKind::VerticalStack { children } => {
+                let st = VerticalStack::new(ctx);
+                for child in children {
+                    st.push(child.build_el(ctx, named)?.element());
+                }
+                LayoutElement::VerticalStack(st)
+            }
+            LayoutKind::HorizontalStack { children } => {
+                let st = HorizontalStack::new(ctx);
+                for child in children {
+                    st.push(child.build_el(ctx, named)?.element());
+                }
+                LayoutElement::HorizontalStack(st)
+            }
+            LayoutKind::Tabs { tabs } => {
+                let t = Tabs::new(ctx);
+                for tab in tabs {
+                    t.push(tab.content.build_el(ctx, named)?.element(), &tab.title);
+                }
+                LayoutElement::Tabs(t)
+            }
+            LayoutKind::Label { text } => LayoutElement::Label(Label::new(ctx, text)),
+            LayoutKind::Button { text } => LayoutElement::Button(Button::new(ctx, text)),
+            LayoutKind::Checkbox => LayoutElement::Checkbox(Checkbox::new(ctx)),
+            LayoutKind::Toggle { left, right } => {
+                LayoutElement::Toggle(Toggle::new(ctx, left.clone(), right.clone()))
+            }
+            LayoutKind::Radio { options } => {
+                LayoutElement::Radio(RadioButtons::new(ctx, options.clone()))
+            }
+            LayoutKind::ListBox { entries } => {
+                LayoutElement::ListBox(ListBox::new(ctx, entries.clone()))
+            }
+            #[cfg(feature = "textbox")]
+            LayoutKind::TextBox { text } => LayoutElement::TextBox(TextBox::new(ctx, text)),
+        };
+
+        if self.fg.is_some() || self.bg.is_some() {
+            let fg = self.fg.as_deref().map(Color::from_name).transpose()?;
+            let bg = self.bg.as_deref().map(Color::from_name).transpose()?;
+            out.set_colors(ctx, fg, bg);
+        }
+
+        let el = out.element();
+        let mut l = el.get_dyn_location_set().l.clone();
+        let x = self.x.as_ref().map(|v| v.to_dyn_val()).transpose()?;
+        let y = self.y.as_ref().map(|v| v.to_dyn_val()).transpose()?;
+        l.set_at(
+            x.unwrap_or_else(|| l.start_x.clone()),
+            y.unwrap_or_else(|| l.start_y.clone()),
+        );
+        if let Some(w) = &self.width {
+            l.set_dyn_width(w.to_dyn_val()?);
+        }
+        if let Some(h) = &self.height {
+            l.set_dyn_height(h.to_dyn_val()?);
+        }
+        el.set_dyn_location(l);
+        if let Some(z) = self.z {
+            let mut ls = el.get_dyn_location_set().clone();
+            ls.set_z(z);
+            el.set_dyn_location_set(ls);
+        }
+        if let Some(tooltip) = &self.tooltip {
+            el.set_tooltip(tooltip);
+        }
+        if let Some(name) = &self.name {
+            if named.contains_key(name) {
+                return Err(Error::new(&format!("duplicate layout name: {name}")));
+            }
+            el.set_state_name(name);
+            named.insert(name.clone(), out.clone());
+        }
+        Ok(out)
+    }
+}
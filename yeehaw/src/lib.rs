@@ -3,7 +3,9 @@
 #[macro_use]
 pub mod log; // note, must be first for the macro to work throughout
 
+pub mod backend;
 pub mod ch;
+pub mod clipboard;
 pub mod color;
 pub mod context;
 pub mod draw_cache;
@@ -14,17 +16,29 @@ pub mod element;
 pub mod elements;
 pub mod errors;
 pub mod event;
+pub mod headless;
+pub mod inline;
 pub mod keyboard;
+pub mod keymap;
+pub mod layout;
 pub mod organizer;
+pub mod profile;
+pub mod schedule;
+pub mod session;
 pub mod sorting_hat;
 pub mod style;
+pub mod task;
+pub mod theme;
+pub mod title;
 pub mod tui;
 
 #[cfg(feature = "bat")]
 pub mod ansi;
 
 pub use {
+    backend::{Backend, CrosstermBackend},
     ch::{ChPlus, DrawCh, DrawChPos, DrawChPosVec, DrawChs2D},
+    clipboard::{Clipboard, ClipboardProvider, InProcessClipboard, Osc52Clipboard},
     color::{Color, ColorStore, Gradient, Pattern, RadialGradient, Rgba, TimeGradient},
     context::Context,
     draw_cache::{CachedPos, DrawingCache},
@@ -36,12 +50,23 @@ pub use {
     errors::Error,
     event::{
         CommandEvent, Event, EventResponse, EventResponses, KeyPossibility, MouseEvent,
-        MoveResponse, ReceivableEvent, ReceivableEvents, ResizeResponse,
+        MousePossibility, MoveResponse, ReceivableEvent, ReceivableEvents, ResizeResponse,
     },
+    headless::HeadlessScreen,
+    inline::InlineControl,
     keyboard::Keyboard,
-    organizer::ElementOrganizer,
+    keymap::{KeyAction, KeyMap, KeyMapEntries},
+    layout::{Layout, LayoutElement, LayoutKind, LayoutNode, LayoutTab, LayoutVal},
+    log::{LogLevel, LogRecord},
+    organizer::{ElementOrganizer, WeakElementOrganizer},
+    profile::{ElementProfile, FrameProfile, FrameStats, Profiler},
+    schedule::{ScheduleHandle, ScheduleTime, ScheduledEvent, Scheduler},
+    session::SessionState,
     sorting_hat::{ElementID, SortingHat},
     style::{Attributes, BgTranspSrc, FgTranspSrc, Style, UlTranspSrc},
+    task::{SpawnedTask, TaskHandle, TaskID, TaskOutput, Tasks},
+    theme::{Theme, ThemeEntries, ThemedStyles},
+    title::{TerminalTitle, TitleChange},
     tui::Tui,
 };
 
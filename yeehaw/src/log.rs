@@ -6,7 +6,15 @@
 /// information will be written.
 /// The debug filepath is specified at the top of the main file of the package
 /// being debugged
-use {once_cell::sync::Lazy, parking_lot::RwLock, std::fs::OpenOptions, std::io::prelude::*};
+///
+/// The most recent records are also kept in memory where they may be viewed live with the
+/// LogPane. With the "log" and "tracing" features the records of the log crate (see
+/// init_log_facade) and the events of tracing (see init_tracing) are fed into the logger as well.
+use {
+    once_cell::sync::Lazy,
+    parking_lot::RwLock,
+    std::{collections::VecDeque, fs::OpenOptions, io::prelude::*, sync::Arc},
+};
 
 #[macro_export]
 macro_rules! debug {
@@ -16,6 +24,15 @@ macro_rules! debug {
     }}
 }
 
+/// logs at the warn level, for recoverable failures which should not panic (unlike log_err!)
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => {{
+        let s = format!($($arg)*);
+        $crate::log::log_warn(s);
+    }}
+}
+
 #[macro_export]
 macro_rules! log_err {
     ($($arg:tt)*) => {{
@@ -24,12 +41,77 @@ macro_rules! log_err {
     }}
 }
 
+/// the level of a record, ordered from the most to the least severe
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 5] = [
+        LogLevel::Error,
+        LogLevel::Warn,
+        LogLevel::Info,
+        LogLevel::Debug,
+        LogLevel::Trace,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn => "WARN",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Trace => "TRACE",
+        }
+    }
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogRecord {
+    /// the position of the record amongst all the records logged
+    pub seq: u64,
+    pub level: LogLevel,
+    /// the origin of the record (for instance the module path), empty for the debug!, warn! and
+    /// log_err! macros
+    pub target: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:<5} ", self.level)?;
+        if !self.target.is_empty() {
+            write!(f, "{}: ", self.target)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// called with each new record, returns false once the listener is no longer interested (after
+/// which it is removed)
+pub type LogListener = Arc<dyn Fn() -> bool + Send + Sync>;
+
 #[derive(Clone)]
 pub struct Logger {
     pub log_file: Option<String>, // if some then output to this file
     pub enabled: bool,
+    /// the number of records kept in memory
     pub max_lines: usize,
-    pub lines: Vec<String>,
+    pub records: VecDeque<LogRecord>,
+    /// the number of records logged so far, including those no longer kept
+    pub count: u64,
+    pub listeners: Vec<LogListener>,
 }
 
 static GLOBAL_LOGGER: Lazy<RwLock<Logger>> = Lazy::new(|| {
@@ -37,20 +119,46 @@ static GLOBAL_LOGGER: Lazy<RwLock<Logger>> = Lazy::new(|| {
         log_file: None,
         enabled: true,
         max_lines: 300,
-        lines: Vec::new(),
+        records: VecDeque::new(),
+        count: 0,
+        listeners: Vec::new(),
     })
 });
 
 pub fn get_content() -> String {
-    GLOBAL_LOGGER.read().lines.join("\n")
+    let logger = GLOBAL_LOGGER.read();
+    let messages: Vec<&str> = logger.records.iter().map(|r| r.message.as_str()).collect();
+    messages.join("\n")
 }
 pub fn get_max_lines() -> usize {
     (GLOBAL_LOGGER.read()).max_lines
 }
+pub fn set_max_lines(max_lines: usize) {
+    let mut logger = GLOBAL_LOGGER.write();
+    logger.max_lines = max_lines;
+    while logger.records.len() > max_lines {
+        logger.records.pop_front();
+    }
+}
 pub fn is_enabled() -> bool {
     (GLOBAL_LOGGER.write()).enabled
 }
 
+/// the records kept in memory, oldest first
+pub fn records() -> Vec<LogRecord> {
+    GLOBAL_LOGGER.read().records.iter().cloned().collect()
+}
+
+/// the number of records logged so far, useful to detect new records
+pub fn count() -> u64 {
+    GLOBAL_LOGGER.read().count
+}
+
+/// register a listener called (from the logging thread) after each new record
+pub fn add_listener(listener: LogListener) {
+    (GLOBAL_LOGGER.write()).listeners.push(listener);
+}
+
 pub fn enable() {
     (GLOBAL_LOGGER.write()).enabled = true;
 }
@@ -70,38 +178,62 @@ pub fn reset_log_file(file: String) {
 
 /// log or panic either logs the content or panics if the build mode is non-release
 pub fn log_or_panic(content: String) {
-    log(content.clone());
+    log_record(LogLevel::Error, "", content.clone());
     #[cfg(debug_assertions)]
     panic!("{}", content);
 }
 
 pub fn log(content: String) {
-    if !GLOBAL_LOGGER.read().enabled {
-        return;
-    }
+    log_record(LogLevel::Debug, "", content);
+}
 
-    let mut lines = GLOBAL_LOGGER.read().lines.clone();
-    let max_lines = GLOBAL_LOGGER.read().max_lines;
-    lines.push(content.clone());
-    if lines.len() > max_lines {
-        lines.remove(0);
-    }
-    (GLOBAL_LOGGER.write()).lines = lines;
+pub fn log_warn(content: String) {
+    log_record(LogLevel::Warn, "", content);
+}
+
+pub fn log_record(level: LogLevel, target: &str, message: String) {
+    let (line, file, listeners) = {
+        let mut logger = GLOBAL_LOGGER.write();
+        if !logger.enabled {
+            return;
+        }
+        let record = LogRecord {
+            seq: logger.count,
+            level,
+            target: target.to_string(),
+            message,
+        };
+        let line = record.to_string();
+        logger.count += 1;
+        logger.records.push_back(record);
+        while logger.records.len() > logger.max_lines {
+            logger.records.pop_front();
+        }
+        (line, logger.log_file.clone(), logger.listeners.clone())
+    };
 
     // push to file
-    if let Some(file) = GLOBAL_LOGGER.read().log_file.clone() {
+    if let Some(file) = file {
         // append content to new line at end of file
         let mut file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(file)
             .expect("could not open log file");
-        writeln!(file, "{content}").expect("could not write to log file");
+        writeln!(file, "{line}").expect("could not write to log file");
+    }
+
+    // called without the lock held so that listeners may themselves use the logger
+    let gone: Vec<_> = listeners.into_iter().filter(|l| !l()).collect();
+    if !gone.is_empty() {
+        (GLOBAL_LOGGER.write())
+            .listeners
+            .retain(|l| !gone.iter().any(|g| Arc::ptr_eq(l, g)));
     }
 }
 
 pub fn clear() {
-    (GLOBAL_LOGGER.write()).lines.clear();
+    (GLOBAL_LOGGER.write()).records.clear();
 
     // clear file
     if let Some(file) = &(GLOBAL_LOGGER.write()).log_file {
@@ -114,3 +246,158 @@ pub fn clear() {
         file.set_len(0).expect("could not truncate log file");
     }
 }
+
+// ------------------------------------------------------------------------------------------------
+// log crate facade
+
+#[cfg(feature = "log")]
+impl From<::log::Level> for LogLevel {
+    fn from(level: ::log::Level) -> Self {
+        match level {
+            ::log::Level::Error => LogLevel::Error,
+            ::log::Level::Warn => LogLevel::Warn,
+            ::log::Level::Info => LogLevel::Info,
+            ::log::Level::Debug => LogLevel::Debug,
+            ::log::Level::Trace => LogLevel::Trace,
+        }
+    }
+}
+
+#[cfg(feature = "log")]
+impl From<LogLevel> for ::log::LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => ::log::LevelFilter::Error,
+            LogLevel::Warn => ::log::LevelFilter::Warn,
+            LogLevel::Info => ::log::LevelFilter::Info,
+            LogLevel::Debug => ::log::LevelFilter::Debug,
+            LogLevel::Trace => ::log::LevelFilter::Trace,
+        }
+    }
+}
+
+/// the logger of the log crate which feeds its records into the yeehaw logger (see
+/// init_log_facade)
+#[cfg(feature = "log")]
+pub struct LogFacade;
+
+#[cfg(feature = "log")]
+impl ::log::Log for LogFacade {
+    fn enabled(&self, metadata: &::log::Metadata) -> bool {
+        metadata.level() <= ::log::max_level()
+    }
+
+    fn log(&self, record: &::log::Record) {
+        if self.enabled(record.metadata()) {
+            log_record(
+                record.level().into(),
+                record.target(),
+                record.args().to_string(),
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// install the yeehaw logger as the logger of the log crate, the records up to max_level are
+/// logged. Fails if a logger has already been installed.
+#[cfg(feature = "log")]
+pub fn init_log_facade(max_level: LogLevel) -> Result<(), crate::Error> {
+    static FACADE: LogFacade = LogFacade;
+    ::log::set_logger(&FACADE).map_err(|e| crate::Error::new(&e.to_string()))?;
+    ::log::set_max_level(max_level.into());
+    Ok(())
+}
+
+// ------------------------------------------------------------------------------------------------
+// tracing subscriber
+
+#[cfg(feature = "tracing")]
+impl From<tracing::Level> for LogLevel {
+    fn from(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::ERROR => LogLevel::Error,
+            tracing::Level::WARN => LogLevel::Warn,
+            tracing::Level::INFO => LogLevel::Info,
+            tracing::Level::DEBUG => LogLevel::Debug,
+            tracing::Level::TRACE => LogLevel::Trace,
+        }
+    }
+}
+
+/// a tracing layer which feeds the events up to max_level into the yeehaw logger, the fields of
+/// an event are appended to its message. Spans are not recorded.
+#[cfg(feature = "tracing")]
+#[derive(Clone, Copy, Debug)]
+pub struct TracingLayer {
+    pub max_level: LogLevel,
+}
+
+#[cfg(feature = "tracing")]
+impl TracingLayer {
+    pub fn new(max_level: LogLevel) -> Self {
+        TracingLayer { max_level }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for TracingLayer {
+    fn enabled(
+        &self, metadata: &tracing::Metadata<'_>, _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) -> bool {
+        LogLevel::from(*metadata.level()) <= self.max_level
+    }
+
+    fn on_event(
+        &self, event: &tracing::Event<'_>, _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut fields = TracingFields::default();
+        event.record(&mut fields);
+        let mut message = fields.message;
+        for field in fields.others {
+            if !message.is_empty() {
+                message.push(' ');
+            }
+            message.push_str(&field);
+        }
+        let meta = event.metadata();
+        log_record((*meta.level()).into(), meta.target(), message);
+    }
+}
+
+#[cfg(feature = "tracing")]
+#[derive(Default)]
+struct TracingFields {
+    message: String,
+    /// formatted as name=value
+    others: Vec<String>,
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::field::Visit for TracingFields {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        match field.name() {
+            "message" => self.message = value.to_string(),
+            name => self.others.push(format!("{name}={value}")),
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        match field.name() {
+            "message" => self.message = format!("{value:?}"),
+            name => self.others.push(format!("{name}={value:?}")),
+        }
+    }
+}
+
+/// install a global tracing subscriber which feeds the events up to max_level into the yeehaw
+/// logger. To combine yeehaw with other layers use the TracingLayer directly. Fails if a global
+/// subscriber has already been installed.
+#[cfg(feature = "tracing")]
+pub fn init_tracing(max_level: LogLevel) -> Result<(), crate::Error> {
+    use tracing_subscriber::layer::SubscriberExt;
+    let subscriber = tracing_subscriber::registry().with(TracingLayer::new(max_level));
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(|e| crate::Error::new(&e.to_string()))
+}
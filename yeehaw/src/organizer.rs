@@ -1,12 +1,15 @@
 use {
     crate::{
         Context, DrawAction, DrawCh, DrawRegion, DrawUpdate, DynLocation, DynLocationSet, Element,
-        ElementID, Event, EventResponse, EventResponses, Keyboard, MouseEvent, Parent,
-        ReceivableEvent, ReceivableEvents, ZIndex,
+        ElementID, Event, EventResponse, EventResponses, InspectNode, KeyBindings, KeyPossibility,
+        Keyboard, MouseEvent, Parent, ReceivableEvent, ReceivableEvents, SessionState, ZIndex,
     },
     rayon::prelude::*,
     std::collections::HashMap,
-    std::{cell::RefCell, rc::Rc},
+    std::{
+        cell::RefCell,
+        rc::{Rc, Weak},
+    },
 };
 
 /// TODO description
@@ -25,6 +28,27 @@ pub struct ElementOrganizer {
     removed_element_queue: Rc<RefCell<Vec<ElementID>>>,
 }
 
+/// A non-owning handle to an ElementOrganizer, for elements which refer to the organizer they
+/// are themselves held in without creating a reference cycle.
+#[derive(Clone, Default)]
+pub struct WeakElementOrganizer {
+    els: Weak<RefCell<HashMap<ElementID, ElDetails>>>,
+    #[allow(clippy::type_complexity)]
+    last_draw_details: Weak<RefCell<Vec<(ElementID, (DynLocationSet, bool, bool))>>>,
+    removed_element_queue: Weak<RefCell<Vec<ElementID>>>,
+}
+
+impl WeakElementOrganizer {
+    /// the organizer, None once it has been dropped
+    pub fn upgrade(&self) -> Option<ElementOrganizer> {
+        Some(ElementOrganizer {
+            els: self.els.upgrade()?,
+            last_draw_details: self.last_draw_details.upgrade()?,
+            removed_element_queue: self.removed_element_queue.upgrade()?,
+        })
+    }
+}
+
 /// element details
 #[derive(Clone)]
 pub struct ElDetails {
@@ -68,6 +92,14 @@ impl ElDetails {
 }
 
 impl ElementOrganizer {
+    pub fn downgrade(&self) -> WeakElementOrganizer {
+        WeakElementOrganizer {
+            els: Rc::downgrade(&self.els),
+            last_draw_details: Rc::downgrade(&self.last_draw_details),
+            removed_element_queue: Rc::downgrade(&self.removed_element_queue),
+        }
+    }
+
     pub fn add_element(&self, el: Box<dyn Element>, parent: Option<Box<dyn Parent>>) {
         // assign the new element id
         let el_id = el.id().clone();
@@ -273,7 +305,12 @@ impl ElementOrganizer {
             .removed_element_queue
             .borrow_mut()
             .drain(..)
-            .map(|el_id| DrawUpdate::clear_all_at_sub_id(vec![el_id]))
+            .map(|el_id| {
+                // cancel the tasks of the removed element
+                ctx.tasks.cancel_element(&el_id);
+                ctx.profiler.remove_element(&el_id);
+                DrawUpdate::clear_all_at_sub_id(vec![el_id])
+            })
             .collect::<Vec<DrawUpdate>>();
 
         // draw elements in order from highest z-index to lowest
@@ -327,7 +364,9 @@ impl ElementOrganizer {
             }
 
             let child_dr = dr.child_region(&el_id_z.1.loc.borrow().l);
+            let start = ctx.profiler.start();
             let mut el_upds = details.el.drawing(ctx, &child_dr, force_update);
+            ctx.profiler.record_draw(start, details.el.as_ref());
 
             for mut el_upd in el_upds.drain(..) {
                 // prepend the element_id to the DrawUpdate
@@ -406,9 +445,18 @@ impl ElementOrganizer {
                 EventResponse::Custom(_, _) => {}
                 EventResponse::Move(_) => {}
                 EventResponse::Resize(_) => {}
+                EventResponse::Title(_) => {}
+                EventResponse::ScheduleEvent(se) => {
+                    // record the path back down to the requesting element
+                    se.path.insert(0, el_id.clone());
+                }
+                EventResponse::TaskSpawned(st) => {
+                    // record the path back down to the spawning element
+                    st.path.insert(0, el_id.clone());
+                }
                 EventResponse::Destruct => {
                     // send down an exit event to the element about to be destroyed
-                    let _ = details.el.receive_event(ctx, Event::Exit);
+                    let _ = receive_event(ctx, details.el.as_ref(), Event::Exit);
                     self.remove_element(el_id);
                     *r = EventResponse::None;
                 }
@@ -475,7 +523,7 @@ impl ElementOrganizer {
         &self, ctx: &Context, ev: Event, parent: Box<dyn Parent>,
     ) -> (bool, EventResponses) {
         let (captured, resps) = match ev {
-            Event::KeyCombo(_) | Event::Custom(_, _) => {
+            Event::KeyCombo(_) | Event::Paste(_) | Event::Custom(_, _) => {
                 let (el_id, resps) = self.routed_event_process(ctx, ev, parent);
                 (el_id.is_some(), resps)
             }
@@ -492,7 +540,13 @@ impl ElementOrganizer {
                 let resps = self.initialize(ctx, parent);
                 (false, resps) // never capture
             }
-            Event::Exit | Event::Resize => self.propogate_event_to_all(ctx, ev, parent),
+            Event::Exit | Event::Resize | Event::FocusGained | Event::FocusLost => {
+                self.propogate_event_to_all(ctx, ev, parent)
+            }
+            Event::Routed(path, ev) => self.routed_path_event_process(ctx, path, *ev, parent),
+            // only delivered within Event::Routed
+            Event::TaskDone(_) => (false, EventResponses::default()),
+            Event::Redraw => (false, EventResponses::default()),
         };
 
         // TODO uncomment/fix
@@ -551,7 +605,7 @@ impl ElementOrganizer {
                 .get_element_details(&el_id)
                 .expect("no element for destination id in routed_event_process");
 
-            let (captured, mut resps_) = el_details.el.receive_event(ctx, ev.clone());
+            let (captured, mut resps_) = receive_event(ctx, el_details.el.as_ref(), ev.clone());
             self.partially_process_ev_resps(ctx, &el_id, &mut resps_, &parent);
             resps.0.extend(resps_.drain(..));
 
@@ -563,6 +617,29 @@ impl ElementOrganizer {
         (capturing_el_id, resps)
     }
 
+    /// sends the event to the child element at the front of the path, if this is the final
+    /// element of the path then the inner event is delivered directly otherwise the event
+    /// continues to be routed along the rest of the path.
+    ///
+    /// the event is considered captured if it has reached its final element
+    pub fn routed_path_event_process(
+        &self, ctx: &Context, mut path: Vec<ElementID>, ev: Event, parent: Box<dyn Parent>,
+    ) -> (bool, EventResponses) {
+        if path.is_empty() {
+            return (false, EventResponses::default());
+        }
+        let el_id = path.remove(0);
+        let Some(details) = self.get_element_details(&el_id) else {
+            // the element has since been removed
+            return (false, EventResponses::default());
+        };
+        let is_final = path.is_empty();
+        let ev = if is_final { ev } else { Event::Routed(path, Box::new(ev)) };
+        let (captured, mut resps) = receive_event(ctx, details.el.as_ref(), ev);
+        self.partially_process_ev_resps(ctx, &el_id, &mut resps, &parent);
+        (is_final || captured, resps)
+    }
+
     /// GetDestinationEl returns the id of the element that should
     /// receive the given event.
     pub fn get_destination_el(&self, input_ev: &Event) -> Vec<ElementID> {
@@ -577,9 +654,12 @@ impl ElementOrganizer {
         dests.drain(..).map(|a| a.0).collect::<Vec<ElementID>>()
     }
 
+    /// get the destination element for the most recent key events, should multiple combos
+    /// match the keyboard state the longest combo is chosen. The matched key events are consumed.
     pub fn get_destination_el_from_kb(
         &self, kb: &mut Keyboard,
     ) -> Option<(ElementID, Vec<crossterm::event::KeyEvent>)> {
+        let mut best: Option<(ElementID, Vec<KeyPossibility>)> = None;
         for (el_id, el_det) in self.els.borrow().iter() {
             let recs = el_det.el.receivable();
             for rec in recs {
@@ -587,13 +667,86 @@ impl ElementOrganizer {
                     let ReceivableEvent::KeyCombo(ref ekc) = rec else {
                         continue;
                     };
-                    if let Some(eks) = kb.matches(ekc, true) {
-                        return Some((el_id.clone(), eks));
+                    if best.as_ref().is_some_and(|(_, b)| b.len() >= ekc.len()) {
+                        continue;
+                    }
+                    if kb.matches(ekc, false).is_some() {
+                        best = Some((el_id.clone(), ekc.clone()));
                     }
                 }
             }
         }
-        None
+        let (el_id, ekc) = best?;
+        kb.matches(&ekc, true).map(|eks| (el_id, eks))
+    }
+
+    /// The lengths of the longest combo which fully matches the keyboard state and of the longest
+    /// partial match (the most recent key events being the start of a longer combo).
+    ///                                                        (full , partial)
+    pub fn kb_match_lens(&self, kb: &mut Keyboard) -> (usize, usize) {
+        let (mut full, mut partial) = (0, 0);
+        for el_det in self.els.borrow().values() {
+            for rec in el_det.el.receivable() {
+                for rec in rec.borrow().0.iter() {
+                    let ReceivableEvent::KeyCombo(ref ekc) = rec else {
+                        continue;
+                    };
+                    if ekc.len() > full && kb.matches(ekc, false).is_some() {
+                        full = ekc.len();
+                    }
+                    partial = partial.max(kb.partial_match_len(ekc));
+                }
+            }
+        }
+        (full, partial)
+    }
+
+    /// the key bindings of all the elements, highest z first
+    pub fn key_bindings(&self) -> Vec<KeyBindings> {
+        let mut els = self
+            .els
+            .borrow()
+            .iter()
+            .map(|(el_id, det)| (el_id.clone(), det.get_z_index(), det.el.clone()))
+            .collect::<Vec<_>>();
+        els.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        els.iter()
+            .flat_map(|(_, _, el)| el.key_bindings())
+            .collect()
+    }
+
+    /// inspect all the elements (see Element::inspect) provided the draw region of this organizer
+    /// and its position on the screen, highest z first
+    pub fn inspect(&self, dr: &DrawRegion, x: i32, y: i32) -> Vec<InspectNode> {
+        let mut els = self
+            .els
+            .borrow()
+            .iter()
+            .map(|(el_id, det)| (el_id.clone(), det.get_z_index(), det.clone()))
+            .collect::<Vec<_>>();
+        els.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        els.iter()
+            .map(|(_, _, det)| {
+                let l = det.loc.borrow().l.clone();
+                let child_dr = dr.child_region(&l);
+                let (cx, cy) = (x + l.get_start_x(dr), y + l.get_start_y(dr));
+                det.el.inspect(&child_dr, cx, cy)
+            })
+            .collect()
+    }
+
+    /// save the state of all the elements (see SessionState)
+    pub fn save_state(&self, state: &mut SessionState) {
+        for details in self.els.borrow().values() {
+            details.el.save_state(state);
+        }
+    }
+
+    /// restore the state of all the elements (see SessionState)
+    pub fn restore_state(&self, state: &SessionState) {
+        for details in self.els.borrow().values() {
+            details.el.restore_state(state);
+        }
     }
 
     pub fn propogate_event_to_all(
@@ -601,7 +754,10 @@ impl ElementOrganizer {
     ) -> (bool, EventResponses) {
         let mut resps = EventResponses::default();
         for (el_id, details) in self.els.borrow().iter() {
-            let (_, mut resps_) = details.el.receive_event(ctx, ev.clone());
+            if ev == Event::Exit {
+                ctx.tasks.cancel_element(el_id);
+            }
+            let (_, mut resps_) = receive_event(ctx, details.el.as_ref(), ev.clone());
             self.partially_process_ev_resps(ctx, el_id, &mut resps_, &parent);
             resps.0.extend(resps_.drain(..));
         }
@@ -616,7 +772,7 @@ impl ElementOrganizer {
             .get_element_details(el_id)
             .expect("no element for destination id in send_event_to_el");
 
-        let (_, mut resps) = details.el.receive_event(ctx, ev);
+        let (_, mut resps) = receive_event(ctx, details.el.as_ref(), ev);
         self.partially_process_ev_resps(ctx, el_id, &mut resps, &parent);
         resps
     }
@@ -630,7 +786,7 @@ impl ElementOrganizer {
         // initialize all children
         let mut resps = EventResponses::default();
         for details in self.els.borrow().values() {
-            let (_, mut resp_) = details.el.receive_event(ctx, Event::Initialize);
+            let (_, mut resp_) = receive_event(ctx, details.el.as_ref(), Event::Initialize);
             self.partially_process_ev_resps(ctx, &details.el.id(), &mut resp_, &parent);
             resps.0.extend(resp_.drain(..));
         }
@@ -667,8 +823,17 @@ impl ElementOrganizer {
         ezo
     }
 
+    /// the tooltip of the highest-z element under the mouse (or of its descendant under the mouse)
+    pub fn tooltip_at(&self, ev: &MouseEvent) -> Option<String> {
+        let (el_id, _) = self.get_el_id_z_order_under_mouse(ev).into_iter().next()?;
+        let details = self.get_element_details(&el_id)?;
+        let ev_adj = details.loc.borrow().l.adjusted_mouse_event(ev);
+        details.el.tooltip_at(&ev_adj)
+    }
+
     /// mouse_event_process :
-    /// - determines the appropriate element to send mouse events to
+    /// - determines the appropriate element to send mouse events to, this is the highest-z element
+    ///   under the mouse which accepts the kind of mouse event and captures it
     /// - sends the event to the element
     /// - processes changes to the element's receivable events
     pub fn mouse_event_process(
@@ -686,10 +851,17 @@ impl ElementOrganizer {
                 .expect("no element for destination id");
 
             // adjust event to the relative position of the element
-            let ev_adj = details.loc.borrow().l.adjusted_mouse_event(ev);
+            let ev_adj = Event::Mouse(details.loc.borrow().l.adjusted_mouse_event(ev));
+
+            // pass over elements which have not declared this kind of mouse event, they will
+            // receive it as an external mouse event
+            if !details.el.accepts_mouse(&ev_adj) {
+                i += 1;
+                continue;
+            }
 
             // send mouse event to the element
-            let (captured, mut resps_) = details.el.receive_event(ctx, Event::Mouse(ev_adj));
+            let (captured, mut resps_) = receive_event(ctx, details.el.as_ref(), ev_adj);
             self.partially_process_ev_resps(ctx, el_id, &mut resps_, &parent);
             resps.0.extend(resps_.drain(..));
 
@@ -715,7 +887,8 @@ impl ElementOrganizer {
             }
             //let child_ctx = ctx.child_context(&details2.loc.borrow().l);
             let ev_adj = details2.loc.borrow().l.adjusted_mouse_event(ev);
-            let (_, mut resps_) = details2.el.receive_event(ctx, Event::ExternalMouse(ev_adj));
+            let (_, mut resps_) =
+                receive_event(ctx, details2.el.as_ref(), Event::ExternalMouse(ev_adj));
             //.receive_event(&child_ctx, Event::ExternalMouse(ev_adj));
             //debug!("about to process external mouse resp: id:{el_id2:?} resps: {resps_:?}");
             self.partially_process_ev_resps(ctx, el_id2, &mut resps_, &parent);
@@ -796,3 +969,11 @@ impl ElementOrganizer {
             .and_modify(|ed| *ed.vis.borrow_mut() = visible);
     }
 }
+
+/// deliver the event to the element, timing it when profiling
+fn receive_event(ctx: &Context, el: &dyn Element, ev: Event) -> (bool, EventResponses) {
+    let start = ctx.profiler.start();
+    let out = el.receive_event(ctx, ev);
+    ctx.profiler.record_event(start, el);
+    out
+}
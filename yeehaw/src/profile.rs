@@ -0,0 +1,237 @@
+use {
+    crate::{Element, ElementID},
+    std::{
+        cell::{Cell, RefCell},
+        collections::HashMap,
+        rc::Rc,
+        time::{Duration, Instant},
+    },
+};
+
+/// Profiler collects the render times of the tui along with the time spent within drawing and
+/// receive_event of each element. Nothing is collected until it is enabled (see
+/// Tui::with_profiling or the ProfileOverlay), the collected profile may then be displayed with
+/// the ProfileOverlay or written out with Profiler::report (logged on exit when enabled).
+///
+/// The times of an element exclude the times of its children (self time), the inclusive times are
+/// also kept.
+#[derive(Clone, Debug, Default)]
+pub struct Profiler {
+    enabled: Rc<Cell<bool>>,
+    frames: Rc<RefCell<FrameStats>>,
+    elements: Rc<RefCell<HashMap<ElementID, ElementProfile>>>,
+    /// the time spent within the children of each measurement in progress, the innermost last
+    child_times: Rc<RefCell<Vec<Duration>>>,
+}
+
+/// the profile of a single render
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FrameProfile {
+    pub time: Duration,
+    /// the number of draw updates returned by the element tree
+    pub draw_updates: usize,
+    /// the number of screen cells changed by the render
+    pub cells_changed: usize,
+    /// whether the element tree was drawn (see Tui::render_if_needed)
+    pub tree_drawn: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    pub count: u64,
+    pub total_time: Duration,
+    pub max_time: Duration,
+    pub last: FrameProfile,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ElementProfile {
+    pub kind: String,
+    pub draws: u64,
+    /// excluding the time spent drawing the children
+    pub draw_time: Duration,
+    pub draw_time_inclusive: Duration,
+    pub events: u64,
+    /// excluding the time spent within the receive_event of the children
+    pub event_time: Duration,
+    pub event_time_inclusive: Duration,
+}
+
+impl Profiler {
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+
+    /// clear everything collected so far
+    pub fn reset(&self) {
+        *self.frames.borrow_mut() = FrameStats::default();
+        self.elements.borrow_mut().clear();
+    }
+
+    /// the start of a measurement, None when disabled. Each started measurement must be ended
+    /// with record_draw or record_event.
+    pub fn start(&self) -> Option<Instant> {
+        if !self.is_enabled() {
+            return None;
+        }
+        self.child_times.borrow_mut().push(Duration::ZERO);
+        Some(Instant::now())
+    }
+
+    /// end the measurement which began at start returning its (self, inclusive) times, the
+    /// inclusive time is added to the child time of the enclosing measurement
+    fn end(&self, start: Instant) -> (Duration, Duration) {
+        let inclusive = start.elapsed();
+        let mut child_times = self.child_times.borrow_mut();
+        let children = child_times.pop().unwrap_or_default();
+        if let Some(parent) = child_times.last_mut() {
+            *parent += inclusive;
+        }
+        (inclusive.saturating_sub(children), inclusive)
+    }
+
+    pub fn record_frame(&self, frame: FrameProfile) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut frames = self.frames.borrow_mut();
+        frames.count += 1;
+        frames.total_time += frame.time;
+        frames.max_time = frames.max_time.max(frame.time);
+        frames.last = frame;
+    }
+
+    /// record a call to drawing of the element which began at start
+    pub fn record_draw(&self, start: Option<Instant>, el: &dyn Element) {
+        if let Some(start) = start {
+            let (self_time, inclusive) = self.end(start);
+            let mut elements = self.elements.borrow_mut();
+            let p = Self::entry(&mut elements, el);
+            p.draws += 1;
+            p.draw_time += self_time;
+            p.draw_time_inclusive += inclusive;
+        }
+    }
+
+    /// record a call to receive_event of the element which began at start
+    pub fn record_event(&self, start: Option<Instant>, el: &dyn Element) {
+        if let Some(start) = start {
+            let (self_time, inclusive) = self.end(start);
+            let mut elements = self.elements.borrow_mut();
+            let p = Self::entry(&mut elements, el);
+            p.events += 1;
+            p.event_time += self_time;
+            p.event_time_inclusive += inclusive;
+        }
+    }
+
+    /// drop the profile of an element removed from the tree
+    pub fn remove_element(&self, el_id: &ElementID) {
+        self.elements.borrow_mut().remove(el_id);
+    }
+
+    fn entry<'a>(
+        elements: &'a mut HashMap<ElementID, ElementProfile>, el: &dyn Element,
+    ) -> &'a mut ElementProfile {
+        elements.entry(el.id()).or_insert_with(|| ElementProfile {
+            kind: el.kind().to_string(),
+            ..Default::default()
+        })
+    }
+
+    pub fn frames(&self) -> FrameStats {
+        *self.frames.borrow()
+    }
+
+    /// the profiles of the elements, the most time consuming (self time) first
+    pub fn elements(&self) -> Vec<(ElementID, ElementProfile)> {
+        let mut out: Vec<_> = self
+            .elements
+            .borrow()
+            .iter()
+            .map(|(id, p)| (id.clone(), p.clone()))
+            .collect();
+        out.sort_by(|a, b| b.1.total_time().cmp(&a.1.total_time()).then(a.0.cmp(&b.0)));
+        out
+    }
+
+    /// the summary of the frames followed by a header and a line per element (at most
+    /// max_elements), the most time consuming first
+    pub fn report_lines(&self, max_elements: Option<usize>) -> Vec<String> {
+        let frames = self.frames();
+        let avg = frames
+            .total_time
+            .checked_div(frames.count as u32)
+            .unwrap_or_default();
+        let last = frames.last;
+        let mut lines = vec![
+            format!(
+                "frames: {}  last: {}  avg: {}  max: {}",
+                frames.count,
+                fmt_dur(last.time),
+                fmt_dur(avg),
+                fmt_dur(frames.max_time)
+            ),
+            format!(
+                "last frame: {} draw updates, {} cells changed{}",
+                last.draw_updates,
+                last.cells_changed,
+                if last.tree_drawn { "" } else { " (tree not drawn)" }
+            ),
+            format!(
+                "{:<24} {:<16} {:>6} {:>9} {:>6} {:>9} {:>9}",
+                "element", "kind", "draws", "draw", "events", "event", "incl"
+            ),
+        ];
+        let elements = self.elements();
+        let n = max_elements.unwrap_or(elements.len());
+        for (id, p) in elements.into_iter().take(n) {
+            lines.push(format!(
+                "{:<24} {:<16} {:>6} {:>9} {:>6} {:>9} {:>9}",
+                truncate(&id, 24),
+                truncate(&p.kind, 16),
+                p.draws,
+                fmt_dur(p.draw_time),
+                p.events,
+                fmt_dur(p.event_time),
+                fmt_dur(p.inclusive_time())
+            ));
+        }
+        lines
+    }
+
+    /// the full report of everything collected so far
+    pub fn report(&self) -> String {
+        self.report_lines(None).join("\n")
+    }
+}
+
+impl ElementProfile {
+    /// the self time spent drawing and receiving events
+    pub fn total_time(&self) -> Duration {
+        self.draw_time + self.event_time
+    }
+
+    /// the time spent drawing and receiving events including the children
+    pub fn inclusive_time(&self) -> Duration {
+        self.draw_time_inclusive + self.event_time_inclusive
+    }
+}
+
+/// durations in milliseconds
+fn fmt_dur(d: Duration) -> String {
+    format!("{:.2}ms", d.as_secs_f64() * 1000.)
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+    let mut out: String = s.chars().take(max - 1).collect();
+    out.push('…');
+    out
+}
@@ -0,0 +1,252 @@
+use {
+    crate::{ElementID, Event, EventResponse, EventResponses},
+    std::time::Duration,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+/// when a scheduled event should first be delivered
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScheduleTime {
+    /// after the provided duration from the moment the request reaches the tui
+    After(Duration),
+    /// at the provided duration since the launch of the tui (see Context::dur_since_launch)
+    At(Duration),
+}
+
+/// A ScheduledEvent is a request from an element to have an event delivered back to itself at
+/// some point in the future (optionally repeating). It is sent upward as an
+/// EventResponse::ScheduleEvent, as the response travels up the element tree the path of element
+/// ids to the requesting element is recorded so that the event can be routed back down to it.
+///
+/// A handle is kept with the scheduled event which can be used to cancel it (for instance when
+/// the requesting element receives Event::Exit).
+#[derive(Clone, Debug)]
+pub struct ScheduledEvent {
+    pub time: ScheduleTime,
+
+    /// if Some, the event is redelivered at this interval after its first delivery
+    pub repeat: Option<Duration>,
+
+    pub event: Event,
+
+    pub handle: ScheduleHandle,
+
+    /// the path of element ids from the top of the element tree down to the requesting element,
+    /// this is filled in by the element organizers as the response propagates upward
+    pub path: Vec<ElementID>,
+}
+
+impl ScheduledEvent {
+    /// deliver the event once after the provided duration
+    pub fn after(dur: Duration, event: Event) -> Self {
+        ScheduledEvent {
+            time: ScheduleTime::After(dur),
+            repeat: None,
+            event,
+            handle: ScheduleHandle::default(),
+            path: Vec::new(),
+        }
+    }
+
+    /// deliver the event once at the provided duration since the launch of the tui
+    pub fn at(dur_since_launch: Duration, event: Event) -> Self {
+        ScheduledEvent {
+            time: ScheduleTime::At(dur_since_launch),
+            ..Self::after(Duration::ZERO, event)
+        }
+    }
+
+    /// deliver the event repeatedly every interval (a heartbeat) until cancelled
+    pub fn heartbeat(interval: Duration, event: Event) -> Self {
+        Self::after(interval, event).with_repeat(interval)
+    }
+
+    pub fn with_repeat(mut self, interval: Duration) -> Self {
+        self.repeat = Some(interval);
+        self
+    }
+
+    /// a handle which can be used to cancel this scheduled event
+    pub fn handle(&self) -> ScheduleHandle {
+        self.handle.clone()
+    }
+}
+
+impl From<ScheduledEvent> for EventResponse {
+    fn from(se: ScheduledEvent) -> Self {
+        EventResponse::ScheduleEvent(se)
+    }
+}
+
+impl From<ScheduledEvent> for EventResponses {
+    fn from(se: ScheduledEvent) -> Self {
+        EventResponse::ScheduleEvent(se).into()
+    }
+}
+
+/// handle to a scheduled event, once cancelled the event will no longer be delivered
+#[derive(Clone, Debug, Default)]
+pub struct ScheduleHandle {
+    cancelled: Rc<Cell<bool>>,
+}
+
+impl ScheduleHandle {
+    pub fn cancel(&self) {
+        self.cancelled.set(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.get()
+    }
+}
+
+/// a scheduled event which is waiting to be delivered
+#[derive(Clone, Debug)]
+pub struct PendingEvent {
+    /// the time since the launch of the tui at which this event is next due
+    pub due: Duration,
+    pub se: ScheduledEvent,
+}
+
+/// The Scheduler holds all the pending scheduled events of the tui.
+#[derive(Clone, Default)]
+pub struct Scheduler {
+    pending: Rc<RefCell<Vec<PendingEvent>>>,
+}
+
+impl Scheduler {
+    /// add a scheduled event, now is the current time since the launch of the tui
+    pub fn schedule(&self, now: Duration, se: ScheduledEvent) {
+        if se.handle.is_cancelled() {
+            return;
+        }
+        let due = match se.time {
+            ScheduleTime::After(dur) => now + dur,
+            ScheduleTime::At(dur) => dur,
+        };
+        self.pending.borrow_mut().push(PendingEvent { due, se });
+    }
+
+    /// remove all the ScheduleEvent responses from the responses and schedule them
+    pub fn schedule_from_resps(&self, now: Duration, resps: &mut EventResponses) {
+        for r in resps.0.iter_mut() {
+            if matches!(r, EventResponse::ScheduleEvent(_)) {
+                let EventResponse::ScheduleEvent(se) = std::mem::take(r) else {
+                    continue;
+                };
+                self.schedule(now, se);
+            }
+        }
+    }
+
+    /// the time (since launch) of the next pending event
+    pub fn next_due(&self) -> Option<Duration> {
+        self.pending
+            .borrow()
+            .iter()
+            .filter(|p| !p.se.handle.is_cancelled())
+            .map(|p| p.due)
+            .min()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.borrow().is_empty()
+    }
+
+    /// the number of events waiting to be delivered
+    pub fn len(&self) -> usize {
+        self.pending.borrow().len()
+    }
+
+    /// Take all the scheduled events which are due at the provided time, in order of their due
+    /// time. Cancelled events are dropped and repeating events are rescheduled for their next
+    /// interval (missed intervals are skipped rather than delivered in a burst).
+    pub fn take_due(&self, now: Duration) -> Vec<ScheduledEvent> {
+        let mut pending = self.pending.borrow_mut();
+        pending.retain(|p| !p.se.handle.is_cancelled());
+        pending.sort_by_key(|p| p.due);
+
+        let mut out = Vec::new();
+        let mut i = 0;
+        while i < pending.len() {
+            if pending[i].due > now {
+                i += 1;
+                continue;
+            }
+            match pending[i].se.repeat {
+                Some(interval) if !interval.is_zero() => {
+                    out.push(pending[i].se.clone());
+                    let mut due = pending[i].due + interval;
+                    if due <= now {
+                        due = now + interval;
+                    }
+                    pending[i].due = due;
+                    i += 1;
+                }
+                _ => out.push(pending.remove(i).se),
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    fn tick() -> Event {
+        Event::Custom("tick".to_string(), vec![])
+    }
+
+    #[test]
+    fn test_one_shot() {
+        let s = Scheduler::default();
+        s.schedule(ms(100), ScheduledEvent::after(ms(50), tick()));
+        s.schedule(ms(100), ScheduledEvent::at(ms(120), tick()));
+        assert_eq!(s.next_due(), Some(ms(120)));
+        assert_eq!(s.take_due(ms(130)).len(), 1);
+        assert_eq!(s.take_due(ms(149)).len(), 0);
+        assert_eq!(s.take_due(ms(150)).len(), 1);
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn test_heartbeat_and_cancel() {
+        let s = Scheduler::default();
+        let se = ScheduledEvent::heartbeat(ms(10), tick());
+        let handle = se.handle();
+        s.schedule(ms(0), se);
+        assert_eq!(s.take_due(ms(10)).len(), 1);
+        assert_eq!(s.take_due(ms(15)).len(), 0);
+        assert_eq!(s.take_due(ms(20)).len(), 1);
+        // missed intervals are skipped
+        assert_eq!(s.take_due(ms(100)).len(), 1);
+        assert_eq!(s.next_due(), Some(ms(110)));
+
+        handle.cancel();
+        assert_eq!(s.next_due(), None);
+        assert_eq!(s.take_due(ms(200)).len(), 0);
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn test_schedule_from_resps() {
+        let s = Scheduler::default();
+        let mut resps: EventResponses = vec![
+            EventResponse::Quit,
+            ScheduledEvent::after(ms(5), tick()).into(),
+        ]
+        .into();
+        s.schedule_from_resps(ms(0), &mut resps);
+        assert_eq!(s.len(), 1);
+        assert!(matches!(resps[0], EventResponse::Quit));
+        assert!(matches!(resps[1], EventResponse::None));
+    }
+}
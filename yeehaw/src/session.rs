@@ -0,0 +1,74 @@
+use {
+    crate::Error,
+    serde::{de::DeserializeOwned, Serialize},
+    std::collections::BTreeMap,
+    std::path::Path,
+};
+
+/// The serializable layout/session state of an application. Elements opt in to being saved by
+/// being given a stable state name (see Element::with_state_name), each named element exports its
+/// state as a set of json values keyed by field (location, selected tab, stack ratios, etc.).
+///
+/// Typically the state is saved to a file on exit (`tui.save_session().save(path)`) and re-applied
+/// once the elements have been constructed (`tui.restore_session(&SessionState::load(path)?)`).
+/// Elements which are missing from either side are ignored.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SessionState {
+    #[serde(default)]
+    pub elements: BTreeMap<String, BTreeMap<String, serde_json::Value>>,
+}
+
+impl SessionState {
+    /// the location of the element within its parent (DynLocation)
+    pub const KEY_LOCATION: &'static str = "location";
+    /// the fraction of the stack which each of its elements takes up (Vec<f64>)
+    pub const KEY_RATIOS: &'static str = "ratios";
+    /// the index of the selected tab (usize)
+    pub const KEY_SELECTED_TAB: &'static str = "selected_tab";
+    /// the content offset of a scrollable element ((usize, usize))
+    pub const KEY_OFFSET: &'static str = "offset";
+    /// the text of a textbox (String)
+    pub const KEY_TEXT: &'static str = "text";
+
+    /// set a field of the state of the named element
+    pub fn set<T: Serialize>(&mut self, name: &str, key: &str, value: &T) {
+        match serde_json::to_value(value) {
+            Ok(v) => {
+                self.elements
+                    .entry(name.to_string())
+                    .or_default()
+                    .insert(key.to_string(), v);
+            }
+            Err(e) => log_err!("failed to serialize session state {name}.{key}: {e}"),
+        }
+    }
+
+    /// get a field of the state of the named element, None if the field is missing or is not
+    /// of the expected type
+    pub fn get<T: DeserializeOwned>(&self, name: &str, key: &str) -> Option<T> {
+        let v = self.elements.get(name)?.get(key)?;
+        match serde_json::from_value(v.clone()) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                debug!("ignoring session state {name}.{key}: {e}");
+                None
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        Ok(std::fs::write(path, self.to_json()?)?)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+}
@@ -0,0 +1,210 @@
+use {
+    crate::{ElementID, Error, Event, EventResponse, EventResponses},
+    std::{
+        any::Any,
+        cell::{Cell, RefCell},
+        collections::HashMap,
+        future::Future,
+        rc::Rc,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+    },
+    tokio::{sync::mpsc::Sender, task::AbortHandle},
+};
+
+/// the identifier of a task spawned with Context::spawn, unique within the tui
+pub type TaskID = u64;
+
+/// Tasks keeps track of the async tasks spawned by elements (see Context::spawn) so that they may
+/// be cancelled once the spawning element receives Event::Exit or is removed, along with the path
+/// to the spawning element which their outputs are routed along.
+#[derive(Clone, Debug, Default)]
+pub struct Tasks {
+    next_id: Rc<Cell<TaskID>>,
+    //                           spawning element
+    running: Rc<RefCell<HashMap<ElementID, Vec<TaskHandle>>>>,
+    //                                    path to the spawning element
+    paths: Rc<RefCell<HashMap<TaskID, Vec<ElementID>>>>,
+}
+
+/// Sent upward as an EventResponse::TaskSpawned when an element spawns a task, as the response
+/// travels up the element tree the path of element ids to the spawning element is recorded so
+/// that the output of the task can be routed back down to it (like a ScheduledEvent).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpawnedTask {
+    pub task: TaskID,
+    /// the path of element ids from the top of the element tree down to the spawning element,
+    /// this is filled in by the element organizers as the response propagates upward
+    pub path: Vec<ElementID>,
+}
+
+/// handle to a spawned task, once cancelled the task is aborted and its output is never delivered
+#[derive(Clone, Debug)]
+pub struct TaskHandle {
+    id: TaskID,
+    abort: AbortHandle,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// The output of a task spawned with Context::spawn, delivered to the spawning element as
+/// Event::TaskDone. The output is taken out by its type: `out.take::<Vec<String>>()`.
+#[derive(Clone)]
+pub struct TaskOutput {
+    pub task: TaskID,
+    output: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Tasks {
+    /// spawn the future on the current tokio runtime, once the future completes its output is
+    /// sent through ev_tx as an Event::TaskDone to be routed to the element with the provided id
+    pub fn spawn<F>(
+        &self, ev_tx: Sender<Event>, el_id: &ElementID, fut: F,
+    ) -> Result<TaskHandle, Error>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let rt = tokio::runtime::Handle::try_current()
+            .map_err(|_| Error::new("tasks may only be spawned within a tokio runtime"))?;
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let cancelled_ = cancelled.clone();
+        let jh = rt.spawn(async move {
+            let output = fut.await;
+            let out = TaskOutput {
+                task: id,
+                output: Arc::new(Mutex::new(Some(Box::new(output)))),
+                cancelled: cancelled_,
+            };
+            // the tui may have closed by now which is not a problem
+            let _ = ev_tx.send(Event::TaskDone(out)).await;
+        });
+        let handle = TaskHandle {
+            id,
+            abort: jh.abort_handle(),
+            cancelled,
+        };
+
+        let mut running = self.running.borrow_mut();
+        let handles = running.entry(el_id.clone()).or_default();
+        // NOTE the paths of completed tasks are kept until their outputs are delivered
+        let mut paths = self.paths.borrow_mut();
+        handles.retain(|h| {
+            if h.is_cancelled() {
+                paths.remove(&h.id);
+            }
+            !h.is_finished()
+        });
+        handles.push(handle.clone());
+        Ok(handle)
+    }
+
+    /// cancel all the tasks spawned by the element
+    pub fn cancel_element(&self, el_id: &ElementID) {
+        if let Some(handles) = self.running.borrow_mut().remove(el_id) {
+            let mut paths = self.paths.borrow_mut();
+            for h in handles {
+                h.cancel();
+                paths.remove(&h.id);
+            }
+        }
+    }
+
+    /// remove all the TaskSpawned responses from the responses and record the paths of their
+    /// tasks
+    pub fn record_paths_from_resps(&self, resps: &mut EventResponses) {
+        for r in resps.0.iter_mut() {
+            if matches!(r, EventResponse::TaskSpawned(_)) {
+                let EventResponse::TaskSpawned(st) = std::mem::take(r) else {
+                    continue;
+                };
+                self.paths.borrow_mut().insert(st.task, st.path);
+            }
+        }
+    }
+
+    /// stop tracking the task once its output has arrived, returning the path to the element
+    /// which spawned it. None if the task was cancelled or its path was never recorded.
+    pub fn complete(&self, task: TaskID) -> Option<Vec<ElementID>> {
+        let mut running = self.running.borrow_mut();
+        running.retain(|_, handles| {
+            handles.retain(|h| h.id != task);
+            !handles.is_empty()
+        });
+        self.paths.borrow_mut().remove(&task)
+    }
+
+    /// the number of tasks being tracked across all elements, tasks are tracked until their
+    /// outputs are delivered or they are cancelled along with their element
+    pub fn len(&self) -> usize {
+        self.running.borrow().values().map(|hs| hs.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// the number of tasks spawned by the element which have yet to complete
+    pub fn running(&self, el_id: &ElementID) -> usize {
+        self.running
+            .borrow()
+            .get(el_id)
+            .map(|hs| hs.iter().filter(|h| !h.is_finished()).count())
+            .unwrap_or(0)
+    }
+}
+
+impl TaskHandle {
+    pub fn id(&self) -> TaskID {
+        self.id
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.abort.abort();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// whether the task has completed or been cancelled
+    pub fn is_finished(&self) -> bool {
+        self.is_cancelled() || self.abort.is_finished()
+    }
+}
+
+impl TaskOutput {
+    /// take the output of the task, None if the output has already been taken or is not of the
+    /// provided type
+    pub fn take<T: 'static>(&self) -> Option<T> {
+        let mut output = self.output.lock().ok()?;
+        if !output.as_ref()?.is::<T>() {
+            return None;
+        }
+        output.take()?.downcast::<T>().ok().map(|o| *o)
+    }
+
+    /// whether the task was cancelled after it had completed but before its output was delivered
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl std::fmt::Debug for TaskOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TaskOutput({})", self.task)
+    }
+}
+
+impl PartialEq for TaskOutput {
+    fn eq(&self, other: &Self) -> bool {
+        self.task == other.task && Arc::ptr_eq(&self.output, &other.output)
+    }
+}
+
+impl Eq for TaskOutput {}
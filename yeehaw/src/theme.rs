@@ -0,0 +1,297 @@
+use {
+    crate::{Color, Error, SelStyles, Style},
+    std::collections::BTreeMap,
+    std::path::Path,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+/// The serializable contents of a theme. Keys are by convention of the form "kind.state" where
+/// kind is the element kind (for instance "button.selected", "menu.disabled", "scrollbar").
+///
+/// NOTE colors which refer to the ColorStore (gradients and patterns) are only meaningful within
+/// the tui they were created in, solid colors are recommended for saved themes.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ThemeEntries {
+    #[serde(default)]
+    pub styles: BTreeMap<String, Style>,
+    #[serde(default)]
+    pub colors: BTreeMap<String, Color>,
+}
+
+impl ThemeEntries {
+    pub fn with_style(mut self, key: &str, sty: Style) -> Self {
+        self.styles.insert(key.to_string(), sty);
+        self
+    }
+
+    pub fn with_color(mut self, key: &str, c: Color) -> Self {
+        self.colors.insert(key.to_string(), c);
+        self
+    }
+
+    /// add the selected, ready and unselectable styles for an element kind
+    pub fn with_sel_styles(self, kind: &str, styles: SelStyles) -> Self {
+        self.with_style(&format!("{kind}.selected"), styles.selected_style)
+            .with_style(&format!("{kind}.ready"), styles.ready_style)
+            .with_style(&format!("{kind}.unselectable"), styles.unselectable_style)
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        Ok(std::fs::write(path, self.to_json()?)?)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+}
+
+/// The Theme is a string-keyed store of styles and colors which default element constructors
+/// consult before falling back to their own hard-coded styles. It is carried in the Context (like
+/// the ColorStore) and is shared between all clones, so replacing its entries (see
+/// Context::set_theme) restyles the whole tui.
+///
+/// A subtree may override entries by constructing its elements with a context holding a child
+/// theme (`ctx.clone().with_theme(ctx.theme.child())`), any keys not found in the child are looked
+/// up in the parent.
+#[derive(Clone, Default, Debug)]
+pub struct Theme {
+    entries: Rc<RefCell<ThemeEntries>>,
+    parent: Option<Box<Theme>>,
+    /// incremented each time the entries of this theme change
+    version: Rc<Cell<u64>>,
+}
+
+impl Theme {
+    pub fn new(entries: ThemeEntries) -> Self {
+        Theme {
+            entries: Rc::new(RefCell::new(entries)),
+            ..Default::default()
+        }
+    }
+
+    /// a new theme which falls back to this theme for any missing keys
+    pub fn child(&self) -> Self {
+        Theme {
+            parent: Some(Box::new(self.clone())),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_style(self, key: &str, sty: Style) -> Self {
+        self.set_style(key, sty);
+        self
+    }
+
+    pub fn with_color(self, key: &str, c: Color) -> Self {
+        self.set_color(key, c);
+        self
+    }
+
+    pub fn with_sel_styles(self, kind: &str, styles: SelStyles) -> Self {
+        self.modify(|e| *e = std::mem::take(e).with_sel_styles(kind, styles));
+        self
+    }
+
+    pub fn set_style(&self, key: &str, sty: Style) {
+        self.modify(|e| {
+            e.styles.insert(key.to_string(), sty);
+        });
+    }
+
+    pub fn set_color(&self, key: &str, c: Color) {
+        self.modify(|e| {
+            e.colors.insert(key.to_string(), c);
+        });
+    }
+
+    /// replace all the entries of this theme (not including the parent theme)
+    pub fn set_entries(&self, entries: ThemeEntries) {
+        self.modify(|e| *e = entries);
+    }
+
+    /// the entries of this theme (not including the parent theme)
+    pub fn entries(&self) -> ThemeEntries {
+        self.entries.borrow().clone()
+    }
+
+    fn modify<F: FnOnce(&mut ThemeEntries)>(&self, f: F) {
+        f(&mut self.entries.borrow_mut());
+        self.version.set(self.version.get() + 1);
+    }
+
+    /// the combined version of this theme and its parents, this changes whenever any of the
+    /// entries change
+    pub fn version(&self) -> u64 {
+        self.version.get() + self.parent.as_ref().map_or(0, |p| p.version())
+    }
+
+    pub fn get_style(&self, key: &str) -> Option<Style> {
+        if let Some(sty) = self.entries.borrow().styles.get(key) {
+            return Some(sty.clone());
+        }
+        self.parent.as_ref().and_then(|p| p.get_style(key))
+    }
+
+    pub fn get_color(&self, key: &str) -> Option<Color> {
+        if let Some(c) = self.entries.borrow().colors.get(key) {
+            return Some(c.clone());
+        }
+        self.parent.as_ref().and_then(|p| p.get_color(key))
+    }
+
+    /// the style for the key or the provided default
+    pub fn style(&self, key: &str, default: Style) -> Style {
+        self.get_style(key).unwrap_or(default)
+    }
+
+    /// the color for the key or the provided default
+    pub fn color(&self, key: &str, default: Color) -> Color {
+        self.get_color(key).unwrap_or(default)
+    }
+
+    /// the selectable styles for an element kind, each state falls back to the provided default
+    pub fn sel_styles(&self, kind: &str, default: SelStyles) -> SelStyles {
+        SelStyles {
+            selected_style: self.style(&format!("{kind}.selected"), default.selected_style),
+            ready_style: self.style(&format!("{kind}.ready"), default.ready_style),
+            unselectable_style: self
+                .style(&format!("{kind}.unselectable"), default.unselectable_style),
+        }
+    }
+}
+
+/// ThemedStyles binds the style fields of an element to the theme it was created with. Once the
+/// theme has changed (see Theme::version) the bound fields are re-resolved on the next refresh,
+/// a field which has been set explicitly since it was last resolved is left as is.
+#[derive(Clone, Default)]
+pub struct ThemedStyles {
+    theme: Theme,
+    /// the version of the theme when the fields were last resolved
+    version: Rc<Cell<u64>>,
+    #[allow(clippy::type_complexity)]
+    bindings: Rc<RefCell<Vec<Box<dyn FnMut(&Theme) -> bool>>>>,
+}
+
+impl ThemedStyles {
+    pub fn new(theme: &Theme) -> Self {
+        ThemedStyles {
+            theme: theme.clone(),
+            version: Rc::new(Cell::new(theme.version())),
+            bindings: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    /// set the field from the theme now and again whenever the theme changes
+    pub fn bind<T, F>(&self, field: &Rc<RefCell<T>>, resolve: F)
+    where
+        T: Clone + PartialEq + 'static,
+        F: Fn(&Theme) -> T + 'static,
+    {
+        let mut last = resolve(&self.theme);
+        *field.borrow_mut() = last.clone();
+        let field = field.clone();
+        self.bindings.borrow_mut().push(Box::new(move |theme| {
+            if *field.borrow() != last {
+                return false; // set explicitly
+            }
+            let v = resolve(theme);
+            if v == last {
+                return false;
+            }
+            *field.borrow_mut() = v.clone();
+            last = v;
+            true
+        }));
+    }
+
+    /// a new field holding the style for the key (or the default), see bind
+    pub fn style(&self, key: &str, default: Style) -> Rc<RefCell<Style>> {
+        let field = Rc::new(RefCell::new(default.clone()));
+        let key = key.to_string();
+        self.bind(&field, move |t| t.style(&key, default.clone()));
+        field
+    }
+
+    /// re-resolve the bound fields if the theme has changed since they were last resolved,
+    /// returns whether any of the fields changed
+    pub fn refresh(&self) -> bool {
+        let version = self.theme.version();
+        if version == self.version.get() {
+            return false;
+        }
+        self.version.set(version);
+        let mut changed = false;
+        for binding in self.bindings.borrow_mut().iter_mut() {
+            changed |= binding(&self.theme);
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_child_fallback() {
+        let theme =
+            Theme::default().with_style("button.ready", Style::new_const(Color::RED, Color::BLUE));
+        let child = theme.child().with_style(
+            "button.selected",
+            Style::new_const(Color::GREEN, Color::BLUE),
+        );
+        let sty = child.sel_styles("button", SelStyles::transparent());
+        assert_eq!(sty.ready_style, Style::new_const(Color::RED, Color::BLUE));
+        assert_eq!(
+            sty.selected_style,
+            Style::new_const(Color::GREEN, Color::BLUE)
+        );
+        assert_eq!(sty.unselectable_style, Style::transparent());
+        assert!(theme.get_style("button.selected").is_none());
+
+        // changes to the parent are visible through the child
+        let v = child.version();
+        theme.set_color("accent", Color::YELLOW);
+        assert_eq!(child.color("accent", Color::BLACK), Color::YELLOW);
+        assert_ne!(child.version(), v);
+    }
+
+    #[test]
+    fn test_themed_styles_refresh() {
+        let theme = Theme::default();
+        let themed = ThemedStyles::new(&theme);
+        let red = Style::new_const(Color::RED, Color::BLUE);
+        let sty = themed.style("button.ready", Style::transparent());
+        let explicit = themed.style("button.selected", Style::transparent());
+        assert!(!themed.refresh());
+
+        *explicit.borrow_mut() = red.clone();
+        theme.set_style("button.ready", red.clone());
+        theme.set_style("button.selected", Style::default());
+        assert!(themed.refresh());
+        assert_eq!(*sty.borrow(), red);
+        // explicitly set fields are no longer taken from the theme
+        assert_eq!(*explicit.borrow(), red);
+        assert!(!themed.refresh());
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let entries = ThemeEntries::default()
+            .with_sel_styles("button", SelStyles::transparent())
+            .with_color("accent", Color::new(10, 20, 30));
+        let json = entries.to_json().unwrap();
+        assert_eq!(ThemeEntries::from_json(&json).unwrap(), entries);
+    }
+}
@@ -0,0 +1,117 @@
+use {
+    crate::{Backend, Error, EventResponse, EventResponses},
+    std::{cell::RefCell, rc::Rc},
+};
+
+/// a change to the title of the host terminal (see EventResponse::Title)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TitleChange {
+    /// replace the current title
+    Set(String),
+    /// set a new title which is reverted to the current title once popped
+    Push(String),
+    /// revert to the title before the last push
+    Pop,
+}
+
+/// TerminalTitle is the title of the host terminal as requested by the application. It is carried
+/// in the Context and shared between all clones, the tui writes the changes to the backend.
+///
+/// Titles are kept as a stack, once the stack is emptied the title the terminal had before the
+/// application first changed it is restored (this also happens on closedown).
+#[derive(Clone, Default, Debug)]
+pub struct TerminalTitle {
+    inner: Rc<RefCell<TitleState>>,
+}
+
+#[derive(Default, Debug)]
+struct TitleState {
+    stack: Vec<String>,
+    /// the stack has changed since the backend was last updated
+    dirty: bool,
+    /// the original title of the terminal has been saved (pushed) on the terminal
+    saved: bool,
+}
+
+impl TerminalTitle {
+    /// the title set by the application, None if the original title of the terminal is in use
+    pub fn current(&self) -> Option<String> {
+        self.inner.borrow().stack.last().cloned()
+    }
+
+    pub fn set(&self, title: &str) {
+        let mut st = self.inner.borrow_mut();
+        st.stack.pop();
+        st.stack.push(title.to_string());
+        st.dirty = true;
+    }
+
+    pub fn push(&self, title: &str) {
+        let mut st = self.inner.borrow_mut();
+        st.stack.push(title.to_string());
+        st.dirty = true;
+    }
+
+    pub fn pop(&self) {
+        let mut st = self.inner.borrow_mut();
+        st.stack.pop();
+        st.dirty = true;
+    }
+
+    pub fn apply_change(&self, change: TitleChange) {
+        match change {
+            TitleChange::Set(t) => self.set(&t),
+            TitleChange::Push(t) => self.push(&t),
+            TitleChange::Pop => self.pop(),
+        }
+    }
+
+    /// remove all the Title responses from the responses and apply them
+    pub fn apply_from_resps(&self, resps: &mut EventResponses) {
+        for r in resps.0.iter_mut() {
+            if matches!(r, EventResponse::Title(_)) {
+                let EventResponse::Title(change) = std::mem::take(r) else {
+                    continue;
+                };
+                self.apply_change(change);
+            }
+        }
+    }
+
+    /// write the title to the backend should it have changed, returns whether anything was
+    /// written (the backend is not flushed)
+    pub fn update_backend(&self, backend: &mut dyn Backend) -> Result<bool, Error> {
+        let mut st = self.inner.borrow_mut();
+        if !st.dirty {
+            return Ok(false);
+        }
+        st.dirty = false;
+        match st.stack.last().cloned() {
+            Some(title) => {
+                if !st.saved {
+                    backend.push_title()?;
+                    st.saved = true;
+                }
+                backend.set_title(&title)?;
+            }
+            None if st.saved => {
+                st.saved = false;
+                backend.pop_title()?;
+            }
+            None => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// restore the original title of the terminal, the requested titles are kept so they are
+    /// reapplied should the tui be run again
+    pub fn restore_backend(&self, backend: &mut dyn Backend) -> Result<(), Error> {
+        let mut st = self.inner.borrow_mut();
+        if st.saved {
+            st.saved = false;
+            st.dirty = !st.stack.is_empty();
+            backend.pop_title()?;
+        }
+        Ok(())
+    }
+}
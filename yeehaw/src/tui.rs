@@ -1,32 +1,55 @@
 use {
     crate::{
-        keyboard::Keyboard, ColorStore, Context, DrawRegion, DrawingCache, DynLocation,
-        DynLocationSet, Element, ElementID, ElementOrganizer, Error, Event, EventResponse,
-        EventResponses, MouseEvent, Parent, SortingHat,
+        keyboard::Keyboard, Backend, ChPlus, Clipboard, ColorStore, Context, CrosstermBackend,
+        DrawChPos, DrawRegion, DrawingCache, DynLocation, DynLocationSet, Element, ElementID,
+        ElementOrganizer, Error, Event, EventResponse, EventResponses, FrameProfile,
+        HeadlessScreen, InProcessClipboard, InlineControl, Inspector, KeyMap, KeyPossibility,
+        MouseEvent, Parent, Profiler, Scheduler, SessionState, Size, SortingHat, Tasks,
+        TerminalTitle, Theme, Tooltip,
     },
     crossterm::{
-        cursor::{self, MoveTo},
         event::{
-            DisableMouseCapture, EnableMouseCapture, Event as CTEvent, EventStream,
-            KeyEvent as CTKeyEvent, MouseEvent as CTMouseEvent,
+            Event as CTEvent, EventStream, KeyCode, KeyEvent as CTKeyEvent, KeyEventKind,
+            KeyModifiers, KeyboardEnhancementFlags, MouseEvent as CTMouseEvent, MouseEventKind,
         },
-        execute, queue, style, terminal,
+        style::{ContentStyle, StyledContent},
     },
     futures::{future::FutureExt, StreamExt},
     std::collections::HashMap,
-    std::io::{stdout, Write},
-    std::{cell::RefCell, rc::Rc},
+    std::path::PathBuf,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
     tokio::sync::mpsc::{Receiver as MpscReceiver, Sender as MpscSender},
     tokio::sync::watch::{Receiver as WatchReceiver, Sender as WatchSender},
     tokio::time::{self, Duration},
 };
 
-/// the amount of time the tui will wait in between calls to re-render.
+/// the minimum amount of time in between renders, renders only occur once something has changed
+/// (or continuously while time gradients are displayed).
 /// If this value is set too large it will give the tui a laggy feel, but if it's set too low then
 /// drawing may consume excessive CPU time and starve events from being processed.
 /// recommended setting: 35ms (28.6 frames per second, like a vhs)
 pub const DEFAULT_ANIMATION_SPEED: Duration = Duration::from_millis(35);
 
+/// How long to wait for the next key when the keys typed so far are the start of a longer
+/// combo (for instance "g" when both "g" and "g g" are receivable). Once elapsed the longest combo
+/// matched by the keys typed so far is used.
+pub const DEFAULT_CHORD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// How long the mouse must rest over an element with a tooltip before the tooltip is displayed.
+pub const DEFAULT_TOOLTIP_DELAY: Duration = Duration::from_millis(700);
+
+/// The key which toggles the element tree inspector once enabled with Tui::with_inspector.
+pub const DEFAULT_INSPECTOR_KEY: CTKeyEvent = Keyboard::KEY_F9;
+
+/// The keyboard enhancement flags used by Tui::with_keyboard_enhancement: disambiguated keys (ctrl+i
+/// and tab, ctrl+h and backspace, etc.) as well as key repeats and releases.
+pub const DEFAULT_KEYBOARD_ENHANCEMENT: KeyboardEnhancementFlags =
+    KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+        .union(KeyboardEnhancementFlags::REPORT_EVENT_TYPES);
+
 /// configuration of a tui instance
 pub struct Tui {
     cup: TuiParent,
@@ -55,13 +78,66 @@ pub struct Tui {
 
     pub kill_keystrokes: Vec<CTKeyEvent>,
 
+    /// see DEFAULT_CHORD_TIMEOUT, a zero timeout disables waiting for longer combos
+    pub chord_timeout: Duration,
+    /// the start of a longer combo which has been typed so far
+    pending_keys: Vec<CTKeyEvent>,
+    /// the time since launch when the pending keys were last updated
+    pending_keys_since: Duration,
+
+    /// whether tooltips are displayed (see Element::set_tooltip)
+    pub tooltips: bool,
+    /// see DEFAULT_TOOLTIP_DELAY
+    pub tooltip_delay: Duration,
+    /// the position the mouse came to rest at and the time since launch when it did
+    hover: Option<(i32, i32, Duration)>,
+    /// the element id of the currently displayed tooltip
+    tooltip_el_id: Option<ElementID>,
+
+    /// the key which toggles the element tree inspector, None to disable the inspector (the
+    /// default), see with_inspector
+    pub inspector_key: Option<CTKeyEvent>,
+    /// the currently open inspector
+    inspector: Option<Inspector>,
+
+    /// the version of the theme when the element tree was last drawn (see Context::set_theme)
+    theme_version: u64,
+
+    /// whether each frame is wrapped in a synchronized update (DEC mode 2026) such that the
+    /// terminal displays it all at once without tearing (default false). Terminals without
+    /// support for the mode are meant to ignore it, however some older terminals print the
+    /// sequence or misbehave, hence it is only enabled on request (see with_synchronized_output).
+    pub synchronized_output: bool,
+    /// whether horizontally adjacent changed cells of the same style are printed together and
+    /// redundant cursor moves skipped (default true)
+    pub batch_output: bool,
+
+    /// the keyboard enhancement (kitty keyboard protocol) flags requested at startup, empty to
+    /// disable keyboard enhancement (the default)
+    pub keyboard_enhancement: KeyboardEnhancementFlags,
+    /// whether the keyboard enhancement flags have been pushed to the terminal
+    keyboard_enhanced: bool,
+
+    /// if Some then the session state is restored from this file once the main element is added
+    /// and saved to it when the tui exits (see SessionState)
+    pub session_file: Option<PathBuf>,
+
     /// if Some then the TUI is rendered inline in the terminal
     pub inline: Option<Rc<RefCell<InlineTui>>>,
+    /// the state of the TUI when rendered inline, shared with `inline` once started inline
+    inline_tui: Rc<RefCell<InlineTui>>,
 
     /// true if exit
     pub exit_recv: WatchReceiver<bool>,
     /// event receiver for internally generated events
     pub ev_recv: MpscReceiver<Event>,
+
+    /// the output target of the TUI, defaults to the stdout terminal
+    pub backend: Box<dyn Backend>,
+
+    /// if Some then the TUI is headless and renders to this in-memory screen instead of the
+    /// backend, time within the tui is also driven by the virtual clock of this screen.
+    pub headless: Option<HeadlessScreen>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -73,6 +149,21 @@ pub struct InlineTui {
 
     /// last height of the screen
     pub scr_height: u16,
+
+    /// leave the final frame visible on closedown, otherwise the rows of the tui are cleared
+    /// (default true)
+    pub keep_final_frame: bool,
+}
+
+impl Default for InlineTui {
+    fn default() -> Self {
+        InlineTui {
+            cursor_start_row: 0,
+            tui_height: 0,
+            scr_height: 0,
+            keep_final_frame: true,
+        }
+    }
 }
 
 impl Tui {
@@ -95,23 +186,315 @@ impl Tui {
             mouse_backlog: Option::None,
             animation_speed: DEFAULT_ANIMATION_SPEED,
             kill_keystrokes: vec![Keyboard::KEY_CTRL_C],
+            chord_timeout: DEFAULT_CHORD_TIMEOUT,
+            pending_keys: Vec::new(),
+            pending_keys_since: Duration::ZERO,
+            tooltips: true,
+            tooltip_delay: DEFAULT_TOOLTIP_DELAY,
+            hover: None,
+            tooltip_el_id: None,
+            inspector_key: None,
+            inspector: None,
+            theme_version: 0,
+            synchronized_output: false,
+            batch_output: true,
+            keyboard_enhancement: KeyboardEnhancementFlags::empty(),
+            keyboard_enhanced: false,
+            session_file: None,
             inline: None,
+            inline_tui: Rc::new(RefCell::new(InlineTui::default())),
             exit_recv,
             ev_recv,
+            backend: Box::new(CrosstermBackend::stdout()),
+            headless: None,
         };
 
-        let ctx =
-            Context::new_context_no_dur(&tui.cup.hat, tui.cup.ev_tx.clone(), &tui.cup.color_store);
+        let mut ctx = Context::new_context_no_dur(
+            &tui.cup.hat,
+            tui.cup.ev_tx.clone(),
+            &tui.cup.color_store,
+            &tui.cup.theme,
+            &tui.cup.keymap,
+            &tui.cup.clipboard,
+        );
+        tui.wire_context(&mut ctx);
+        Ok((tui, ctx))
+    }
+
+    /// creates a new headless TUI of the provided size. Nothing is written to the terminal, instead
+    /// the TUI renders into an in-memory HeadlessScreen. The main element is added with
+    /// `start_headless` after which events may be fed in directly (`process_event_key`,
+    /// `process_event_mouse`, `resize_headless`) and the virtual clock advanced with
+    /// `advance_time`.
+    ///
+    /// NOTE the clipboard of a headless TUI is in-process only
+    pub fn new_headless(width: u16, height: u16) -> Result<(Tui, Context), Error> {
+        let (mut tui, ctx) = Tui::new()?;
+        tui.headless = Some(HeadlessScreen::new(Size::new(width, height)));
+        tui.cup
+            .clipboard
+            .set_provider(Box::new(InProcessClipboard::default()));
         Ok((tui, ctx))
     }
 
     pub fn context(&self) -> Context {
-        Context::new_context(
+        if let Some(headless) = &self.headless {
+            let mut ctx = Context::new_context_no_dur(
+                &self.cup.hat,
+                self.cup.ev_tx.clone(),
+                &self.cup.color_store,
+                &self.cup.theme,
+                &self.cup.keymap,
+                &self.cup.clipboard,
+            );
+            ctx.dur_since_launch = headless.elapsed;
+            self.wire_context(&mut ctx);
+            return ctx;
+        }
+        let mut ctx = Context::new_context(
             self.launch_instant,
             &self.cup.hat,
             self.cup.ev_tx.clone(),
             &self.cup.color_store,
-        )
+            &self.cup.theme,
+            &self.cup.keymap,
+            &self.cup.clipboard,
+        );
+        self.wire_context(&mut ctx);
+        ctx
+    }
+
+    /// connect the context to the shared state of the tui
+    fn wire_context(&self, ctx: &mut Context) {
+        ctx.pending_keys = self.pending_keys.clone();
+        ctx.terminal_title = self.cup.terminal_title.clone();
+        ctx.inline = self.cup.inline.clone();
+        ctx.tasks = self.cup.tasks.clone();
+        ctx.profiler = self.cup.profiler.clone();
+    }
+
+    /// the keys typed so far of a multi-key combo which is waiting to be completed
+    pub fn pending_keys(&self) -> &[CTKeyEvent] {
+        &self.pending_keys
+    }
+
+    /// Builder-style setter for the chord timeout (see DEFAULT_CHORD_TIMEOUT).
+    pub fn with_chord_timeout(mut self, timeout: Duration) -> Self {
+        self.chord_timeout = timeout;
+        self
+    }
+
+    /// Builder-style setter to enable or disable tooltips globally.
+    pub fn with_tooltips(mut self, enabled: bool) -> Self {
+        self.set_tooltips(enabled);
+        self
+    }
+
+    /// Enable or disable tooltips globally, disabling removes any displayed tooltip.
+    pub fn set_tooltips(&mut self, enabled: bool) {
+        self.tooltips = enabled;
+        if !enabled {
+            self.remove_tooltip();
+        }
+    }
+
+    /// Builder-style setter for the tooltip delay (see DEFAULT_TOOLTIP_DELAY).
+    pub fn with_tooltip_delay(mut self, delay: Duration) -> Self {
+        self.tooltip_delay = delay;
+        self
+    }
+
+    /// Builder-style opt-in to keyboard enhancement with the DEFAULT_KEYBOARD_ENHANCEMENT flags.
+    /// The flags are only pushed at startup if the terminal supports them.
+    pub fn with_keyboard_enhancement(self) -> Self {
+        self.with_keyboard_enhancement_flags(DEFAULT_KEYBOARD_ENHANCEMENT)
+    }
+
+    /// Builder-style setter for the keyboard enhancement flags requested at startup.
+    pub fn with_keyboard_enhancement_flags(mut self, flags: KeyboardEnhancementFlags) -> Self {
+        self.keyboard_enhancement = flags;
+        self
+    }
+
+    /// Builder-style setter for the session file (see SessionState). The state of the named
+    /// elements is restored from the file (should it exist) once the main element is added and is
+    /// written to the file when the tui exits.
+    pub fn with_session_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.session_file = Some(path.into());
+        self
+    }
+
+    /// the current state of the named elements of the main element
+    pub fn save_session(&self) -> SessionState {
+        let mut state = SessionState::default();
+        if let Some(main_el) = self.cup.eo.get_element(&self.main_el_id) {
+            main_el.save_state(&mut state);
+        }
+        state
+    }
+
+    /// restore the state of the named elements of the main element
+    pub fn restore_session(&self, state: &SessionState) {
+        if let Some(main_el) = self.cup.eo.get_element(&self.main_el_id) {
+            main_el.restore_state(state);
+        }
+    }
+
+    /// restore the session from the session file, a missing or invalid file is ignored
+    fn load_session_file(&self) {
+        let Some(path) = &self.session_file else {
+            return;
+        };
+        if !path.exists() {
+            return;
+        }
+        match SessionState::load(path) {
+            Ok(state) => self.restore_session(&state),
+            Err(e) => debug!("ignoring session file {}: {e}", path.display()),
+        }
+    }
+
+    fn save_session_file(&self) -> Result<(), Error> {
+        match &self.session_file {
+            Some(path) => self.save_session().save(path),
+            None => Ok(()),
+        }
+    }
+
+    /// whether keyboard enhancement is in use, that is it was requested and the terminal
+    /// supported it at startup
+    pub fn keyboard_enhanced(&self) -> bool {
+        self.keyboard_enhanced
+    }
+
+    /// push the requested keyboard enhancement flags should the terminal support them
+    fn push_keyboard_enhancement(&mut self) -> Result<(), Error> {
+        if self.keyboard_enhancement.is_empty() || !self.backend.supports_keyboard_enhancement() {
+            return Ok(());
+        }
+        if self.backend.is_terminal() {
+            set_panic_hook_with_keyboard_enhancement_pop();
+        }
+        self.backend
+            .push_keyboard_enhancement(self.keyboard_enhancement)?;
+        self.backend.flush()?;
+        self.keyboard_enhanced = true;
+        Ok(())
+    }
+
+    fn pop_keyboard_enhancement(&mut self) -> Result<(), Error> {
+        if self.keyboard_enhanced {
+            self.keyboard_enhanced = false;
+            self.backend.pop_keyboard_enhancement()?;
+        }
+        Ok(())
+    }
+
+    /// Builder-style setter for the title of the host terminal.
+    pub fn with_title(self, title: &str) -> Self {
+        self.set_title(title);
+        self
+    }
+
+    /// Set the title of the host terminal, the original title is restored on closedown. Elements
+    /// may also change the title through the context (`ctx.terminal_title`) or with an
+    /// EventResponse::Title.
+    pub fn set_title(&self, title: &str) {
+        self.cup.terminal_title.set(title);
+    }
+
+    /// Set a new title of the host terminal which is reverted once popped.
+    pub fn push_title(&self, title: &str) {
+        self.cup.terminal_title.push(title);
+    }
+
+    /// Revert to the title before the last push.
+    pub fn pop_title(&self) {
+        self.cup.terminal_title.pop();
+    }
+
+    /// the title set for the host terminal, None if the original title is in use
+    pub fn title(&self) -> Option<String> {
+        self.cup.terminal_title.current()
+    }
+
+    /// Set the output backend of the TUI.
+    pub fn set_backend(&mut self, backend: Box<dyn Backend>) {
+        self.backend = backend;
+    }
+
+    /// Builder-style setter for the output backend.
+    pub fn with_backend(mut self, backend: Box<dyn Backend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// the current output target, the headless screen if headless otherwise the backend
+    fn out(&mut self) -> &mut dyn Backend {
+        match &mut self.headless {
+            Some(headless) => headless,
+            None => self.backend.as_mut(),
+        }
+    }
+
+    /// collect the draw and event times of the elements (see Profiler), the profile is written to
+    /// the log when the tui exits
+    pub fn set_profiling(&self, enabled: bool) {
+        self.cup.profiler.set_enabled(enabled);
+    }
+
+    pub fn with_profiling(self) -> Self {
+        self.set_profiling(true);
+        self
+    }
+
+    /// enable the element tree inspector (see Inspector) toggled with the provided key, None
+    /// disables it
+    pub fn set_inspector_key(&mut self, key: Option<CTKeyEvent>) {
+        self.inspector_key = key;
+    }
+
+    /// enable the element tree inspector toggled with DEFAULT_INSPECTOR_KEY
+    pub fn with_inspector(self) -> Self {
+        self.with_inspector_key(DEFAULT_INSPECTOR_KEY)
+    }
+
+    pub fn with_inspector_key(mut self, key: CTKeyEvent) -> Self {
+        self.set_inspector_key(Some(key));
+        self
+    }
+
+    pub fn inspector(&self) -> Option<&Inspector> {
+        self.inspector.as_ref()
+    }
+
+    /// open or close the inspector
+    pub fn toggle_inspector(&mut self) {
+        if let Some(insp) = self.inspector.take() {
+            self.cup.eo.remove_element(&insp.id());
+        } else {
+            self.hover = None;
+            self.remove_tooltip();
+            let insp = Inspector::new(&self.context(), self.cup.eo.clone());
+            self.cup
+                .eo
+                .add_element(Box::new(insp.clone()), Some(Box::new(self.cup.clone())));
+            self.inspector = Some(insp);
+        }
+        self.cup.needs_render.set(true);
+    }
+
+    /// Enable synchronized output for terminals known to support it (kitty, wezterm, alacritty,
+    /// foot, iTerm2, windows terminal, tmux, etc.), large redraws are then displayed at once
+    /// rather than tearing.
+    pub fn with_synchronized_output(mut self, enabled: bool) -> Self {
+        self.synchronized_output = enabled;
+        self
+    }
+
+    pub fn with_batch_output(mut self, enabled: bool) -> Self {
+        self.batch_output = enabled;
+        self
     }
 
     /// Set the list of keystrokes that will cause the TUI to exit.
@@ -126,22 +509,43 @@ impl Tui {
     }
 
     pub fn draw_region(&self) -> DrawRegion {
-        let mut dr = DrawRegion::new_for_screen();
+        let size = match &self.headless {
+            Some(headless) => headless.size(),
+            None => match self.backend.size() {
+                Ok(size) => size,
+                Err(e) => {
+                    log_err!("failed to get backend size: {}", e);
+                    Size::default()
+                }
+            },
+        };
+        let mut dr = DrawRegion::default().with_size(size);
         if let Some(inline) = &self.inline {
             dr.size.height = inline.borrow().tui_height;
         }
         dr
     }
 
-    pub async fn run(&mut self, main_el: Box<dyn Element>) -> Result<(), Error> {
+    fn log_profile(&self) {
+        if self.cup.profiler.is_enabled() {
+            debug!("render profile:\n{}", self.cup.profiler.report());
+        }
+    }
+
+    /// add the main element to the top of the element tree and initialize it to fill the provided
+    /// draw region
+    fn set_main_el(&mut self, main_el: Box<dyn Element>, dr: &DrawRegion) {
         self.main_el_id = main_el.id();
         // add the element here after the location has been created
-        let ctx = Context::new_context_no_dur(
+        let mut ctx = Context::new_context_no_dur(
             &self.cup.hat,
             self.cup.ev_tx.clone(),
             &self.cup.color_store,
+            &self.cup.theme,
+            &self.cup.keymap,
+            &self.cup.clipboard,
         );
-        let dr = DrawRegion::new_for_screen();
+        self.wire_context(&mut ctx);
         let loc = DynLocation::new_fixed(0, dr.size.width as i32, 0, dr.size.height as i32);
         let loc = DynLocationSet::new(loc, vec![], 0);
         main_el.set_dyn_location_set(loc);
@@ -154,65 +558,169 @@ impl Tui {
         self.cup
             .eo
             .add_element(main_el.clone(), Some(Box::new(self.cup.clone())));
-        self.cup.eo.initialize(&ctx, Box::new(self.cup.clone()));
+        let mut resps = self.cup.eo.initialize(&ctx, Box::new(self.cup.clone()));
         self.cup.main_el_id = main_el.id();
+        self.cup
+            .scheduler
+            .schedule_from_resps(ctx.dur_since_launch, &mut resps);
+        self.theme_version = self.cup.theme.version();
+        self.cup.needs_render.set(true);
+        self.load_session_file();
+    }
 
-        sc_startup()?;
-        self.launch().await?;
-        sc_closedown()?;
-        Ok(())
+    /// Add the main element to the tui without running the event loop. This is only necessary when
+    /// driving the tui manually (calling `render` and feeding in events directly), `run` and
+    /// `run_in_line` add the main element themselves.
+    pub fn set_main_element(&mut self, main_el: Box<dyn Element>) {
+        let dr = self.draw_region();
+        self.set_main_el(main_el, &dr);
     }
 
-    /// run the TUI in terminal line
-    pub async fn run_in_line(
+    pub async fn run(&mut self, main_el: Box<dyn Element>) -> Result<(), Error> {
+        let dr = self.draw_region();
+        self.set_main_el(main_el, &dr);
+
+        if self.backend.is_terminal() {
+            set_panic_hook_with_closedown();
+        }
+        self.backend.startup()?;
+        self.push_keyboard_enhancement()?;
+        let res = self.launch().await;
+        self.pop_keyboard_enhancement()?;
+        self.cup.clipboard.update_backend(self.backend.as_mut())?;
+        self.cup
+            .terminal_title
+            .restore_backend(self.backend.as_mut())?;
+        self.backend.closedown()?;
+        self.save_session_file()?;
+        self.log_profile();
+        res
+    }
+
+    /// Start a headless TUI (see `new_headless`) with the provided main element. The first frame is
+    /// rendered to the headless screen before returning.
+    pub fn start_headless(&mut self, main_el: Box<dyn Element>) -> Result<(), Error> {
+        if self.headless.is_none() {
+            return Err(Error::new("start_headless called on a non-headless tui"));
+        }
+        self.set_main_element(main_el);
+        self.render()
+    }
+
+    /// the in-memory screen of a headless TUI
+    pub fn headless_screen(&self) -> Option<&HeadlessScreen> {
+        self.headless.as_ref()
+    }
+
+    /// Advance the virtual clock of a headless TUI, any internally generated events and due
+    /// scheduled events are processed and then the screen is re-rendered.
+    ///
+    /// NOTE a repeating scheduled event is delivered at most once per call
+    ///                                                              exit-tui
+    pub fn advance_time(&mut self, dur: Duration) -> Result<bool, Error> {
+        if let Some(headless) = &mut self.headless {
+            headless.advance(dur);
+        }
+        while let Ok(ev) = self.ev_recv.try_recv() {
+            if self.process_channel_event(ev)? {
+                return Ok(true);
+            }
+        }
+        if self.process_pending_keys_timeout()? {
+            return Ok(true);
+        }
+        if self.process_scheduled_events()? {
+            return Ok(true);
+        }
+        if self.process_mouse_backlog()? {
+            return Ok(true);
+        }
+        self.process_tooltip_timeout();
+        self.render()?;
+        Ok(false)
+    }
+
+    /// resize the screen of a headless TUI
+    pub fn resize_headless(&mut self, width: u16, height: u16) -> Result<(), Error> {
+        if let Some(headless) = &mut self.headless {
+            headless.resize(Size::new(width, height));
+        }
+        self.process_event_resize()?;
+        self.render()
+    }
+
+    /// Start a headless TUI rendered inline at the cursor of the headless screen taking up the
+    /// provided height (see run_in_line).
+    pub fn start_headless_inline(
         &mut self, main_el: Box<dyn Element>, height: u16,
     ) -> Result<(), Error> {
-        self.main_el_id = main_el.id();
-        // add the element here after the location has been created
-        let ctx = Context::new_context_no_dur(
-            &self.cup.hat,
-            self.cup.ev_tx.clone(),
-            &self.cup.color_store,
-        );
-        let mut dr = DrawRegion::new_for_screen();
-        dr.size.height = height;
-        let loc = DynLocation::new_fixed(0, dr.size.width as i32, 0, dr.size.height as i32);
-        let loc = DynLocationSet::new(loc, vec![], 0);
-        main_el.set_dyn_location_set(loc);
-        main_el.set_visible(true);
-        main_el.set_focused(true);
+        if self.headless.is_none() {
+            return Err(Error::new(
+                "start_headless_inline called on a non-headless tui",
+            ));
+        }
+        self.setup_inline(main_el, height)?;
+        self.render()
+    }
 
-        // when adding the main element, nil is passed in as the parent
-        // this is because the top of the tree is the TUI's main EO and so no parent
-        // is necessary
-        self.cup
-            .eo
-            .add_element(main_el.clone(), Some(Box::new(self.cup.clone())));
-        self.cup.eo.initialize(&ctx, Box::new(self.cup.clone()));
-        self.cup.main_el_id = main_el.id();
+    /// Set whether the final frame of an inline TUI is left visible once it exits.
+    pub fn with_keep_inline_frame(self, keep: bool) -> Self {
+        self.inline_tui.borrow_mut().keep_final_frame = keep;
+        self
+    }
+
+    /// reserve the rows below the cursor for an inline tui and set the main element
+    fn setup_inline(
+        &mut self, main_el: Box<dyn Element>, height: u16,
+    ) -> Result<Rc<RefCell<InlineTui>>, Error> {
+        let mut dr = self.draw_region();
+        dr.size.height = height;
+        self.set_main_el(main_el, &dr);
 
         // get the cursor position
-        let (_, mut cur_row) = cursor::position()?;
-        let scr_height = terminal::size()?.1;
+        let inline = self.inline_tui.clone();
+        let out = self.out();
+        let (_, mut cur_row) = out.cursor_position()?;
+        let scr_height = out.size()?.height;
         //let offset = if cur_row + height > scr_height { cur_row + height - scr_height } else { 0 };
         let offset = (cur_row + height).saturating_sub(scr_height);
 
         cur_row -= offset;
-        let inline = Rc::new(RefCell::new(InlineTui {
-            cursor_start_row: cur_row,
-            tui_height: height,
-            scr_height,
-        }));
-        self.inline = Some(inline.clone());
+        {
+            let mut inline = inline.borrow_mut();
+            inline.cursor_start_row = cur_row;
+            inline.tui_height = height;
+            inline.scr_height = scr_height;
+        }
 
         // scroll if there isn't enough room in the terminal
-        execute!(stdout(), terminal::ScrollUp(offset))?;
-
-        sc_line_startup(inline.clone())?;
-        self.launch().await?;
-        sc_line_closedown(*inline.borrow())?;
+        out.scroll_up(offset)?;
+        out.flush()?;
+        self.inline = Some(inline.clone());
+        Ok(inline)
+    }
 
-        Ok(())
+    /// Run the TUI in terminal line, taking up the provided height below the cursor. The height
+    /// may be changed and lines committed above the TUI while it runs (see InlineControl).
+    pub async fn run_in_line(
+        &mut self, main_el: Box<dyn Element>, height: u16,
+    ) -> Result<(), Error> {
+        let inline = self.setup_inline(main_el, height)?;
+        if self.backend.is_terminal() {
+            set_line_panic_hook_with_closedown(inline.clone());
+        }
+        self.backend.line_startup()?;
+        self.push_keyboard_enhancement()?;
+        let res = self.launch().await;
+        self.pop_keyboard_enhancement()?;
+        self.cup.clipboard.update_backend(self.backend.as_mut())?;
+        self.cup
+            .terminal_title
+            .restore_backend(self.backend.as_mut())?;
+        line_closedown(self.backend.as_mut(), *inline.borrow())?;
+        self.save_session_file()?;
+        self.log_profile();
+        res
     }
 
     async fn launch(&mut self) -> Result<(), Error> {
@@ -220,71 +728,39 @@ impl Tui {
         self.launch_instant = std::time::Instant::now();
 
         loop {
-            let delay = time::sleep(self.animation_speed).fuse();
+            // sleep until an event is received unless something is due before then
+            let wake = self.next_wake();
+            let delay = async move {
+                match wake {
+                    Some(dur) => time::sleep(dur).await,
+                    None => std::future::pending().await,
+                }
+            }
+            .fuse();
             let event = reader.next().fuse();
 
             tokio::select! {
                 ev_res = event => {
                     match ev_res {
                         Some(Ok(ev)) => {
-                            match ev {
-                                CTEvent::Key(key_ev) => {
-                                    //debug!("tui Key event: {:?}", key_ev);
-                                    if self.process_event_key(key_ev)? {
-                                        break Ok(());
-                                    }
-                                }
-                                CTEvent::Mouse(mouse_ev) => {
-                                    if self.process_event_mouse(mouse_ev)? {
-                                        break Ok(());
-                                    }
-                                }
-
+                            let exit = match ev {
+                                CTEvent::Key(key_ev) => self.process_event_key(key_ev)?,
+                                CTEvent::Mouse(mouse_ev) => self.process_event_mouse(mouse_ev)?,
+                                CTEvent::Paste(text) => self.process_event_paste(text)?,
+                                CTEvent::FocusGained => self.process_event_focus(true)?,
+                                CTEvent::FocusLost => self.process_event_focus(false)?,
                                 CTEvent::Resize(_, _) => {
-                                    if let Some(inline) = &mut self.inline {
-                                        let scr_height = terminal::size()?.1;
-                                        let mut inline = inline.borrow_mut();
-                                        let last_scr_height = inline.scr_height;
-
-                                        match scr_height.cmp(&last_scr_height) {
-                                            std::cmp::Ordering::Equal => {}
-                                            std::cmp::Ordering::Less => {
-                                                let diff = last_scr_height - scr_height;
-                                                execute!(stdout(), terminal::ScrollDown(diff))?;
-                                                inline.scr_height = scr_height;
-                                                inline.cursor_start_row = inline.cursor_start_row.saturating_sub(diff);
-                                            }
-                                            std::cmp::Ordering::Greater => {
-                                                let diff = scr_height - last_scr_height;
-                                                execute!(stdout(), terminal::ScrollUp(diff))?;
-
-                                                inline.scr_height = scr_height;
-                                                inline.cursor_start_row += diff;
-                                                let cur_row = inline.cursor_start_row;
-                                                let tui_height = inline.tui_height;
-
-                                                // if increasing the screen height, and the tui is not
-                                                // fully visible, then move the cursor start row up
-                                                let offset = (cur_row + tui_height).saturating_sub(scr_height);
-                                                inline.cursor_start_row = cur_row.saturating_sub(offset);
-                                            }
-                                        }
-                                    }
-
-                                    let ctx = self.context();
-                                    let dr = self.draw_region();
-                                    let loc = DynLocation::new_fixed(0, dr.size.width as i32, 0, dr.size.height as i32);
-                                    // There should only be one element at index 0 in the upper level EO
-                                    self.cup.eo.update_el_primary_location(self.main_el_id.clone(), loc);
-                                    let _ = self.cup.eo.get_element(&self.main_el_id).expect("main element missing").receive_event(&ctx, Event::Resize{});
-                                    self.clear_screen()?;
+                                    self.process_event_resize()?;
+                                    false
                                 }
-                                _ => {}
+                            };
+                            if exit {
+                                break Ok(());
                             }
                             // important to render here to not starve rendering when there
                             // are ample events coming in. Within render it will skip renders if
                             // the animation speed is not met
-                            self.render()?;
+                            self.render_if_needed()?;
                         }
                         Some(Err(e)) => println!("Error: {e:?}\r"),
                         None => break Ok(()),
@@ -292,8 +768,7 @@ impl Tui {
                 }
 
                 Some(ev_res) = self.ev_recv.recv() => {
-                    let (_, resps) = self.cup.eo.event_process(&self.context(), ev_res, Box::new(self.cup.clone()));
-                    if process_event_resps(resps, None, &self.cup.eo, self.main_el_id.clone())? {
+                    if self.process_channel_event(ev_res)? {
                         break Ok(());
                     }
                 }
@@ -306,10 +781,17 @@ impl Tui {
                 }
 
                 _ = delay => {
+                    if self.process_pending_keys_timeout()? {
+                        break Ok(());
+                    }
+                    if self.process_scheduled_events()? {
+                        break Ok(());
+                    }
                     if self.process_mouse_backlog()? {
                         break Ok(());
                     }
-                    self.render()?;
+                    self.process_tooltip_timeout();
+                    self.render_if_needed()?;
                 },
             };
         }
@@ -319,28 +801,178 @@ impl Tui {
         Ok(())
     }
 
+    /// schedule any scheduled event responses then process the remaining responses
+    ///                                                                         exit-tui
+    fn process_resps(&self, ctx: &Context, mut resps: EventResponses) -> Result<bool, Error> {
+        self.cup
+            .scheduler
+            .schedule_from_resps(ctx.dur_since_launch, &mut resps);
+        self.cup.tasks.record_paths_from_resps(&mut resps);
+        self.cup.terminal_title.apply_from_resps(&mut resps);
+        self.cup.needs_render.set(true);
+        process_event_resps(resps, None, &self.cup.eo, self.main_el_id.clone())
+    }
+
+    /// process an event sent through the event channel (Context::ev_tx), the outputs of tasks
+    /// are routed back down to the elements which spawned them
+    ///                                                                exit-tui
+    fn process_channel_event(&mut self, ev: Event) -> Result<bool, Error> {
+        let ev = match ev {
+            Event::TaskDone(out) => {
+                let Some(path) = self.cup.tasks.complete(out.task) else {
+                    // cancelled, or spawned by an element outside of the element tree
+                    return Ok(false);
+                };
+                if out.is_cancelled() {
+                    return Ok(false);
+                }
+                Event::Routed(path, Box::new(Event::TaskDone(out)))
+            }
+            ev => ev,
+        };
+        let ctx = self.context();
+        let (_, resps) = self
+            .cup
+            .eo
+            .event_process(&ctx, ev, Box::new(self.cup.clone()));
+        self.process_resps(&ctx, resps)
+    }
+
+    /// deliver all the scheduled events which are due to the elements which scheduled them.
+    /// Should the requesting element no longer exist the scheduled event is cancelled.
+    ///                                                                exit-tui
+    pub fn process_scheduled_events(&mut self) -> Result<bool, Error> {
+        let ctx = self.context();
+        for se in self.cup.scheduler.take_due(ctx.dur_since_launch) {
+            let ev = Event::Routed(se.path.clone(), Box::new(se.event.clone()));
+            let (delivered, resps) =
+                self.cup
+                    .eo
+                    .event_process(&ctx, ev, Box::new(self.cup.clone()));
+            if !delivered {
+                se.handle.cancel();
+            }
+            if self.process_resps(&ctx, resps)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
     /// process_event_key handles key events
+    ///
+    /// When the keys typed so far are the start of a longer receivable combo the keys are held as
+    /// pending until either the combo is completed, a key which does not continue it is typed, or
+    /// the chord timeout elapses, after which the longest combo matched is used.
     ///                                                                 exit-tui
     pub fn process_event_key(&mut self, key_ev: CTKeyEvent) -> Result<bool, Error> {
-        self.kb.add_ev(key_ev);
-
-        if self.kill_keystrokes.contains(&key_ev) {
+        if key_ev.kind == KeyEventKind::Release {
+            return self.process_key_release(key_ev);
+        }
+        self.hover = None;
+        self.remove_tooltip();
+        let is_kill = |k: &CTKeyEvent| KeyPossibility::Key(*k).matches_key(&key_ev);
+        if self.kill_keystrokes.iter().any(is_kill) {
+            self.kb.add_ev(key_ev);
             self.cup
                 .eo
                 .event_process(&self.context(), Event::Exit, Box::new(self.cup.clone()));
             return Ok(true);
         }
 
+        // the open inspector captures all keys
+        let is_insp = |k: &CTKeyEvent| KeyPossibility::Key(*k).matches_key(&key_ev);
+        if self.inspector_key.as_ref().is_some_and(is_insp) {
+            self.toggle_inspector();
+            return Ok(false);
+        }
+        if let Some(insp) = &self.inspector {
+            if key_ev.code == KeyCode::Esc {
+                self.toggle_inspector();
+            } else {
+                insp.key(key_ev);
+                self.cup.needs_render.set(true);
+            }
+            return Ok(false);
+        }
+
         //debug!("tui Key event: {:?}", key_ev);
 
-        // we only care about the event response as all keys are sent to the main
-        // element no matter what
+        // pending keys which are not continued by this key are resolved first
+        if !self.pending_keys.is_empty() {
+            let mut kb = self.kb.clone();
+            kb.add_ev(key_ev);
+            let (full, partial) = self.cup.eo.kb_match_lens(&mut kb);
+            if full.max(partial) <= self.pending_keys.len() && self.flush_pending_keys()? {
+                return Ok(true);
+            }
+        }
+
         self.kb.add_ev(key_ev);
 
+        // wait for more keys if a longer combo may still be typed
+        let (full, partial) = self.cup.eo.kb_match_lens(&mut self.kb);
+        if partial > 0 && partial >= full && !self.chord_timeout.is_zero() {
+            self.pending_keys = self.kb.recent_keys(partial);
+            self.pending_keys_since = self.context().dur_since_launch;
+            return Ok(false);
+        }
+        self.pending_keys.clear();
+        self.dispatch_kb()
+    }
+
+    /// Key releases (only reported with keyboard enhancement) are sent on their own to the
+    /// focused element which receives the release, they are never part of a longer combo.
+    ///                                                                  exit-tui
+    fn process_key_release(&mut self, key_ev: CTKeyEvent) -> Result<bool, Error> {
+        let ctx = self.context();
+        let (_, resps) = self.cup.eo.event_process(
+            &ctx,
+            Event::KeyCombo(vec![key_ev]),
+            Box::new(self.cup.clone()),
+        );
+        self.process_resps(&ctx, resps)
+    }
+
+    /// process_event_paste sends pasted text to the focused element which receives pastes. Should
+    /// no element receive it, the paste is dropped.
+    ///                                                                  exit-tui
+    pub fn process_event_paste(&mut self, text: String) -> Result<bool, Error> {
+        self.hover = None;
+        self.remove_tooltip();
+        if !self.pending_keys.is_empty() && self.flush_pending_keys()? {
+            return Ok(true);
+        }
+
+        let ctx = self.context();
+        let (captured, resps) =
+            self.cup
+                .eo
+                .event_process(&ctx, Event::Paste(text), Box::new(self.cup.clone()));
+        if !captured {
+            debug!("paste not received by any element, dropped");
+        }
+        self.process_resps(&ctx, resps)
+    }
+
+    /// process_event_focus sends Event::FocusGained or Event::FocusLost to all the elements
+    ///                                                            exit-tui
+    pub fn process_event_focus(&mut self, focused: bool) -> Result<bool, Error> {
+        let ctx = self.context();
+        let ev = if focused { Event::FocusGained } else { Event::FocusLost };
+        let (_, resps) = self
+            .cup
+            .eo
+            .event_process(&ctx, ev, Box::new(self.cup.clone()));
+        self.process_resps(&ctx, resps)
+    }
+
+    /// send the longest combo matched by the keyboard state to its destination element
+    ///                                         exit-tui
+    fn dispatch_kb(&mut self) -> Result<bool, Error> {
         // get the event combos and consume the keyboard events
         // NOTE the element ID is not used from GetDestinationElFromKB as it is
         // re-determined within the KeyEventsProcess (inefficient but convenient)
-
         let Some((_, evs)) = self.cup.eo.get_destination_el_from_kb(&mut self.kb) else {
             return Ok(false);
         };
@@ -352,19 +984,267 @@ impl Tui {
                 .eo
                 .routed_event_process(&ctx, evs.into(), Box::new(self.cup.clone()));
 
-        process_event_resps(resps, None, &self.cup.eo, self.main_el_id.clone())
+        self.process_resps(&ctx, resps)
+    }
+
+    /// stop waiting for a longer combo and dispatch whatever the pending keys match
+    ///                                                 exit-tui
+    fn flush_pending_keys(&mut self) -> Result<bool, Error> {
+        self.pending_keys.clear();
+        self.dispatch_kb()
+    }
+
+    fn dur_since_launch(&self) -> Duration {
+        match &self.headless {
+            Some(headless) => headless.elapsed,
+            None => self.launch_instant.elapsed(),
+        }
+    }
+
+    /// The duration until the event loop must next wake, either to render a change or time
+    /// gradient, or to process a scheduled event, chord timeout, tooltip or mouse backlog. None if
+    /// nothing is pending, in which case the loop sleeps until an event is received.
+    pub fn next_wake(&self) -> Option<Duration> {
+        let now = self.dur_since_launch();
+        let mut wake: Option<Duration> = None;
+        let mut wake_in = |dur: Duration| wake = Some(wake.map_or(dur, |w| w.min(dur)));
+
+        // renders and mouse events are throttled to the animation speed
+        let next_frame =
+            |last: std::time::Instant| self.animation_speed.saturating_sub(last.elapsed());
+        if self.needs_render() || self.drawing_cache.time_grad_count > 0 {
+            wake_in(next_frame(self.last_render));
+        }
+        if self.mouse_backlog.is_some() {
+            wake_in(next_frame(self.last_mouse));
+        }
+        let dues = [
+            self.cup.scheduler.next_due(),
+            self.pending_keys_due(),
+            self.tooltip_due(),
+        ];
+        for due in dues.into_iter().flatten() {
+            wake_in(due.saturating_sub(now));
+        }
+        wake
+    }
+
+    /// whether something has changed since the last render
+    pub fn needs_render(&self) -> bool {
+        self.cup.needs_render.get()
+            || self.cup.inline.is_pending()
+            || self.cup.theme.version() != self.theme_version
+            || self.cup.clipboard.is_dirty()
+    }
+
+    /// the time since launch at which the pending keys time out
+    fn pending_keys_due(&self) -> Option<Duration> {
+        if self.pending_keys.is_empty() {
+            return None;
+        }
+        Some(self.pending_keys_since + self.chord_timeout)
+    }
+
+    /// dispatch the pending keys should the chord timeout have elapsed
+    ///                                                              exit-tui
+    pub fn process_pending_keys_timeout(&mut self) -> Result<bool, Error> {
+        match self.pending_keys_due() {
+            Some(due) if self.context().dur_since_launch >= due => self.flush_pending_keys(),
+            _ => Ok(false),
+        }
+    }
+
+    /// the time since launch at which the tooltip under the resting mouse is due
+    fn tooltip_due(&self) -> Option<Duration> {
+        if !self.tooltips || self.tooltip_el_id.is_some() || self.inspector.is_some() {
+            return None;
+        }
+        self.hover.map(|(_, _, since)| since + self.tooltip_delay)
+    }
+
+    /// display the tooltip of the element under the mouse should it have rested there for the
+    /// tooltip delay
+    pub fn process_tooltip_timeout(&mut self) {
+        let ctx = self.context();
+        match self.tooltip_due() {
+            Some(due) if ctx.dur_since_launch >= due => {}
+            _ => return,
+        }
+        let Some((x, y, _)) = self.hover.take() else {
+            return;
+        };
+        let dr = self.draw_region();
+        let ev = MouseEvent {
+            dr: dr.clone(),
+            kind: MouseEventKind::Moved,
+            column: x,
+            row: y,
+            modifiers: KeyModifiers::NONE,
+        };
+        let Some(text) = self.cup.eo.tooltip_at(&ev) else {
+            return;
+        };
+        let tooltip = Tooltip::new(&ctx, &text, x, y, dr.size);
+        self.tooltip_el_id = Some(tooltip.id());
+        self.cup
+            .eo
+            .add_element(Box::new(tooltip), Some(Box::new(self.cup.clone())));
+        self.cup.needs_render.set(true);
+    }
+
+    /// remove the displayed tooltip if there is one
+    fn remove_tooltip(&mut self) {
+        if let Some(el_id) = self.tooltip_el_id.take() {
+            self.cup.eo.remove_element(&el_id);
+            self.cup.needs_render.set(true);
+        }
+    }
+
+    /// process_event_resize handles a change in the screen size
+    pub fn process_event_resize(&mut self) -> Result<(), Error> {
+        self.hover = None;
+        self.remove_tooltip();
+        if let Some(inline) = &mut self.inline {
+            let scr_height = self.backend.size()?.height;
+            let mut inline = inline.borrow_mut();
+            let last_scr_height = inline.scr_height;
+
+            match scr_height.cmp(&last_scr_height) {
+                std::cmp::Ordering::Equal => {}
+                std::cmp::Ordering::Less => {
+                    let diff = last_scr_height - scr_height;
+                    self.backend.scroll_down(diff)?;
+                    self.backend.flush()?;
+                    inline.scr_height = scr_height;
+                    inline.cursor_start_row = inline.cursor_start_row.saturating_sub(diff);
+                }
+                std::cmp::Ordering::Greater => {
+                    let diff = scr_height - last_scr_height;
+                    self.backend.scroll_up(diff)?;
+                    self.backend.flush()?;
+
+                    inline.scr_height = scr_height;
+                    inline.cursor_start_row += diff;
+                    let cur_row = inline.cursor_start_row;
+                    let tui_height = inline.tui_height;
+
+                    // if increasing the screen height, and the tui is not
+                    // fully visible, then move the cursor start row up
+                    let offset = (cur_row + tui_height).saturating_sub(scr_height);
+                    inline.cursor_start_row = cur_row.saturating_sub(offset);
+                }
+            }
+        }
+
+        self.resize_main_el();
+        self.clear_screen()
+    }
+
+    /// fit the main element to the draw region
+    fn resize_main_el(&mut self) {
+        let ctx = self.context();
+        let dr = self.draw_region();
+        let loc = DynLocation::new_fixed(0, dr.size.width as i32, 0, dr.size.height as i32);
+        // There should only be one element at index 0 in the upper level EO
+        self.cup
+            .eo
+            .update_el_primary_location(self.main_el_id.clone(), loc);
+        let _ = self
+            .cup
+            .eo
+            .get_element(&self.main_el_id)
+            .expect("main element missing")
+            .receive_event(&ctx, Event::Resize {});
+    }
+
+    /// apply the height changes and commit the lines requested for an inline tui (see
+    /// InlineControl), returns whether anything changed in which case the tui must be redrawn.
+    /// The backend is not flushed.
+    fn update_inline(&mut self) -> Result<bool, Error> {
+        let Some(inline) = self.inline.clone() else {
+            return Ok(false);
+        };
+        let (height, lines) = self.cup.inline.take();
+        if height.is_none() && lines.is_empty() {
+            return Ok(false);
+        }
+        let ctx = self.context();
+        let out = self.out();
+        let width = out.size()?.width;
+        let mut il = inline.borrow_mut();
+
+        if let Some(height) = height.map(|h| h.min(il.scr_height)) {
+            if height > il.tui_height {
+                // scroll if there isn't enough room below the tui
+                let offset = (il.cursor_start_row + height).saturating_sub(il.scr_height);
+                out.scroll_up(offset)?;
+                il.cursor_start_row -= offset;
+            } else {
+                for y in il.cursor_start_row + height..il.cursor_start_row + il.tui_height {
+                    out.clear_row(y)?;
+                }
+            }
+            il.tui_height = height;
+        }
+
+        // each line is written over the top row of the tui which is then moved down a row,
+        // scrolling the screen up once the tui reaches the bottom. Lines wider than the screen
+        // are wrapped onto further rows.
+        let line_size = Size::new(width, 1);
+        let rows = lines.iter().flat_map(|line| {
+            if line.is_empty() {
+                vec![&line[..]]
+            } else {
+                line.chunks(width.max(1) as usize).collect()
+            }
+        });
+        for row in rows {
+            if il.cursor_start_row + il.tui_height >= il.scr_height && il.cursor_start_row > 0 {
+                out.scroll_up(1)?;
+                il.cursor_start_row -= 1;
+            }
+            let y = il.cursor_start_row;
+            out.clear_row(y)?;
+            let mut prev = StyledContent::new(ContentStyle::default(), ChPlus::Char(' '));
+            for (x, ch) in row.iter().enumerate() {
+                let dcp = DrawChPos::new(ch.clone(), x as u16, 0);
+                prev = dcp.get_content_style(
+                    &ctx.color_store,
+                    &ctx.dur_since_launch,
+                    &line_size,
+                    &prev,
+                );
+                out.print_styled(prev.clone())?;
+            }
+            if il.cursor_start_row + il.tui_height < il.scr_height {
+                il.cursor_start_row += 1;
+            }
+        }
+        drop(il);
+
+        // the whole tui is redrawn at its new position
+        self.resize_main_el();
+        self.drawing_cache.clear_screen();
+        Ok(true)
     }
 
     /// process_event_mouse handles mouse events
+    ///
+    /// NOTE when headless, mouse events are never throttled
     //                                                                        exit-tui
     pub fn process_event_mouse(&mut self, mut mouse_ev: CTMouseEvent) -> Result<bool, Error> {
-        if self.last_mouse.elapsed() < self.animation_speed || self.mouse_processing {
+        let throttle = self.headless.is_none() && self.last_mouse.elapsed() < self.animation_speed;
+        if throttle || self.mouse_processing {
             // add to the mouse backlock removing from the front if it gets too long
             self.mouse_backlog.replace(mouse_ev);
             return Ok(false);
         }
         self.mouse_processing = true;
 
+        // any mouse event removes the tooltip, only a resting mouse displays one
+        self.hover = None;
+        self.remove_tooltip();
+
         let ctx = self.context();
         let dr = self.draw_region();
         let mut in_bounds = true;
@@ -379,13 +1259,22 @@ impl Tui {
             }
         }
 
-        let out = if in_bounds {
+        let out = if let Some(insp) = self.inspector.as_ref().filter(|_| in_bounds) {
+            // the open inspector captures all mouse events
+            insp.mouse(mouse_ev.kind, mouse_ev.column as i32, mouse_ev.row as i32);
+            self.cup.needs_render.set(true);
+            Ok(false)
+        } else if in_bounds {
+            if self.tooltips && mouse_ev.kind == MouseEventKind::Moved {
+                let since = ctx.dur_since_launch;
+                self.hover = Some((mouse_ev.column as i32, mouse_ev.row as i32, since));
+            }
             let mouse_ev = MouseEvent::new(dr, mouse_ev);
             let (_, resps) =
                 self.cup
                     .eo
                     .mouse_event_process(&ctx, &mouse_ev, Box::new(self.cup.clone()));
-            process_event_resps(resps, None, &self.cup.eo, self.main_el_id.clone())
+            self.process_resps(&ctx, resps)
         } else {
             Ok(false)
         };
@@ -405,12 +1294,10 @@ impl Tui {
 
     pub fn clear_screen(&mut self) -> Result<(), Error> {
         self.drawing_cache.clear_screen();
-        let mut sc = stdout();
-        execute!(
-            sc,
-            crossterm::terminal::Clear(crossterm::terminal::ClearType::All)
-        )?;
-        Ok(())
+        self.cup.needs_render.set(true);
+        let out = self.out();
+        out.clear_all()?;
+        out.flush()
     }
 
     /// Render all elements, draws the screen using the DrawChPos array passed to it
@@ -424,42 +1311,165 @@ impl Tui {
     /// provided by each element in order from the bottom of the tree to the top.
     /// This results in elements higher up the tree being able to overwrite elements
     /// lower down the tree.
+    ///
+    /// NOTE when headless, renders are never throttled and are written to the headless screen
     pub fn render(&mut self) -> Result<(), Error> {
-        let delay = self.animation_speed;
-        if self.last_render.elapsed() < delay || self.rendering {
-            return Ok(());
+        self.render_frame(true).map(|_| ())
+    }
+
+    /// Render only should something have changed since the last render (see
+    /// Parent::propagate_dirty_upward), otherwise the element tree is not traversed and only the
+    /// time gradients on the screen (if any) are redrawn. Returns whether the element tree was
+    /// drawn.
+    pub fn render_if_needed(&mut self) -> Result<bool, Error> {
+        let needs_render = self.needs_render();
+        if !needs_render && self.drawing_cache.time_grad_count == 0 {
+            return Ok(false);
+        }
+        self.render_frame(needs_render)
+    }
+
+    /// render to the screen, the element tree is only drawn if draw_tree is set. Returns whether
+    /// the element tree was drawn.
+    fn render_frame(&mut self, draw_tree: bool) -> Result<bool, Error> {
+        let throttle = self.headless.is_none() && self.last_render.elapsed() < self.animation_speed;
+        if throttle || self.rendering {
+            return Ok(false);
         }
         self.rendering = true;
+        let start = std::time::Instant::now();
+
+        // the lines committed above an inline tui are written within the same synchronized update
+        // as the redrawn tui
+        let sync = self.synchronized_output;
+        let mut in_sync = false;
+        if sync && self.inline.is_some() && self.cup.inline.is_pending() {
+            self.out().begin_synchronized_update()?;
+            in_sync = true;
+        }
+        let inline_changed = self.update_inline()?;
+        let draw_tree = draw_tree || inline_changed;
 
-        let mut sc = stdout();
         let ctx = self.context();
         let dr = self.draw_region();
-        let updates = self.cup.eo.all_drawing_updates(&ctx, &dr, false);
+        let updates = if draw_tree {
+            let updates = self.cup.eo.all_drawing_updates(&ctx, &dr, inline_changed);
+            // cleared after drawing as elements regenerating their content while drawing is not
+            // a change which requires another render
+            self.cup.needs_render.set(false);
+            self.theme_version = self.cup.theme.version();
+            updates
+        } else {
+            Vec::new()
+        };
 
         //self.drawing_cache.clear_screen(); // helpful for debugging, force constant redraw
+        let draw_updates = updates.len();
         let mut upd = self.drawing_cache.update_and_get(&ctx, &dr.size, updates);
+        if self.inline.is_some() {
+            // the cache may still hold rows from before the inline tui shrunk
+            upd.retain(|(_, y, _)| *y < dr.size.height as usize);
+        }
+        let cells_changed = upd.len();
 
-        if !upd.is_empty() {
+        let title = self.cup.terminal_title.clone();
+        let title_changed = title.update_backend(self.out())?;
+        let clipboard = self.cup.clipboard.clone();
+        let clipboard_changed = clipboard.update_backend(self.out())?;
+        if !upd.is_empty() || title_changed || clipboard_changed || inline_changed {
             let y_offset = if let Some(inline) = &self.inline {
                 inline.borrow().cursor_start_row as usize
             } else {
                 0
             };
-            for (x, y, upd) in upd.drain(..) {
-                let y = y + y_offset;
-                queue!(
-                    &mut sc,
-                    MoveTo(x as u16, y as u16),
-                    style::PrintStyledContent(upd)
-                )?;
+            let batch = self.batch_output;
+            let out = self.out();
+            if sync && !in_sync {
+                out.begin_synchronized_update()?;
+                in_sync = true;
+            }
+            if batch {
+                print_batched(out, upd, y_offset)?;
+            } else {
+                for (x, y, upd) in upd.drain(..) {
+                    let y = y + y_offset;
+                    out.move_to(x as u16, y as u16)?;
+                    out.print_styled(upd)?;
+                }
+            }
+            if in_sync {
+                out.end_synchronized_update()?;
             }
-            sc.flush()?;
+            out.flush()?;
         }
 
+        self.cup.profiler.record_frame(FrameProfile {
+            time: start.elapsed(),
+            draw_updates,
+            cells_changed,
+            tree_drawn: draw_tree,
+        });
         self.last_render = std::time::Instant::now(); // important only set this at the end
         self.rendering = false;
-        Ok(())
+        Ok(draw_tree)
+    }
+}
+
+/// print the changed cells (in row-major order) coalescing the horizontally adjacent single
+/// column chars of the same style into a single print, the cursor is only moved when it is not
+/// already in place
+fn print_batched(
+    out: &mut dyn Backend, cells: Vec<(usize, usize, StyledContent<ChPlus>)>, y_offset: usize,
+) -> Result<(), Error> {
+    // the position of the cursor if known
+    let mut cursor: Option<(usize, usize)> = None;
+    let mut run = String::new();
+
+    let mut cells = cells.into_iter().peekable();
+    while let Some((x, y, cell)) = cells.next() {
+        let y = y + y_offset;
+        if cursor != Some((x, y)) {
+            out.move_to(x as u16, y as u16)?;
+        }
+        let ch = match cell.content() {
+            ChPlus::Char(ch) if is_single_column(*ch) => *ch,
+            _ => {
+                // the width of anything else is left to the terminal
+                out.print_styled(cell)?;
+                cursor = None;
+                continue;
+            }
+        };
+
+        run.clear();
+        run.push(ch);
+        let run_style = *cell.style();
+        let mut end = x + 1;
+        while let Some((nx, ny, next)) = cells.peek() {
+            let joins = *nx == end && *ny + y_offset == y && *next.style() == run_style;
+            match next.content() {
+                ChPlus::Char(nch) if joins && is_single_column(*nch) => run.push(*nch),
+                _ => break,
+            }
+            end += 1;
+            cells.next();
+        }
+        out.print_styled_str(StyledContent::new(run_style, run.as_str()))?;
+        cursor = Some((end, y));
     }
+    Ok(())
+}
+
+/// whether the char is known to take up exactly one column in the terminal, only these chars
+/// are coalesced such that the position of the cursor after a run is certain
+fn is_single_column(ch: char) -> bool {
+    matches!(ch,
+        ' '..='~'                     // ascii
+        | '\u{a1}'..='\u{24f}'        // latin-1 supplement and latin extended
+        | '\u{391}'..='\u{3c9}'       // greek
+        | '\u{2500}'..='\u{259f}'     // box drawing and block elements
+        | '\u{2800}'..='\u{28ff}'     // braille
+    )
 }
 
 pub fn process_event_resps(
@@ -487,12 +1497,25 @@ pub fn process_event_resps(
 pub struct TuiParent {
     pub hat: SortingHat,
     pub color_store: ColorStore,
+    pub theme: Theme,
+    pub keymap: KeyMap,
+    pub clipboard: Clipboard,
     pub eo: ElementOrganizer,
     pub el_store: Rc<RefCell<HashMap<String, Vec<u8>>>>,
     pub exit_tx: WatchSender<bool>,
     /// event senter for internally generated events
     pub ev_tx: MpscSender<Event>,
     pub main_el_id: ElementID,
+    /// the pending scheduled events of the tui
+    pub scheduler: Scheduler,
+    pub terminal_title: TerminalTitle,
+    /// the changes requested to an inline tui
+    pub inline: InlineControl,
+    /// the async tasks spawned by the elements
+    pub tasks: Tasks,
+    /// whether something has changed since the last render, see Parent::propagate_dirty_upward
+    pub needs_render: Rc<Cell<bool>>,
+    pub profiler: Profiler,
 }
 
 impl TuiParent {
@@ -500,11 +1523,20 @@ impl TuiParent {
         TuiParent {
             hat: SortingHat::default(),
             color_store: ColorStore::default(),
+            theme: Theme::default(),
+            keymap: KeyMap::default(),
+            clipboard: Clipboard::default(),
             eo: ElementOrganizer::default(),
             el_store: Rc::new(RefCell::new(HashMap::new())),
             exit_tx,
             ev_tx,
             main_el_id: "".to_string(),
+            scheduler: Scheduler::default(),
+            terminal_title: TerminalTitle::default(),
+            inline: InlineControl::default(),
+            tasks: Tasks::default(),
+            needs_render: Rc::new(Cell::new(true)),
+            profiler: Profiler::default(),
         }
     }
 }
@@ -524,6 +1556,11 @@ impl Parent for TuiParent {
         let b: Box<dyn Parent> = Box::new(self.clone());
         self.eo
             .partially_process_ev_resps(parent_ctx, child_el_id, &mut resps, &b);
+        self.scheduler
+            .schedule_from_resps(parent_ctx.dur_since_launch, &mut resps);
+        self.tasks.record_paths_from_resps(&mut resps);
+        self.terminal_title.apply_from_resps(&mut resps);
+        self.needs_render.set(true);
         if let Err(e) = process_event_resps(
             resps,
             Some(self.exit_tx.clone()),
@@ -552,33 +1589,23 @@ impl Parent for TuiParent {
     fn get_id(&self) -> ElementID {
         "TUI".to_string()
     }
+
+    fn propagate_dirty_upward(&self) {
+        self.needs_render.set(true);
+    }
 }
 
+/// prepare the stdout terminal for a full screen tui
 pub fn sc_startup() -> Result<(), Error> {
     set_panic_hook_with_closedown();
-    let mut sc = stdout();
-    execute!(
-        sc,
-        cursor::Hide,
-        terminal::EnterAlternateScreen,
-        EnableMouseCapture
-    )?;
-    terminal::enable_raw_mode()?;
-    Ok(())
+    CrosstermBackend::stdout().startup()
 }
 
+/// restore the stdout terminal after a full screen tui
 pub fn sc_closedown() -> Result<(), Error> {
-    let mut sc = stdout();
-    execute!(
-        sc,
-        style::ResetColor,
-        cursor::Show,
-        terminal::LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal::disable_raw_mode()?;
-    Ok(())
+    CrosstermBackend::stdout().closedown()
 }
+
 pub fn set_panic_hook_with_closedown() {
     use std::panic;
 
@@ -590,33 +1617,51 @@ pub fn set_panic_hook_with_closedown() {
     }));
 }
 
+/// pop the keyboard enhancement flags of the stdout terminal should the application panic
+pub fn set_panic_hook_with_keyboard_enhancement_pop() {
+    use std::panic;
+
+    let prev_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let mut backend = CrosstermBackend::stdout();
+        let _ = backend.pop_keyboard_enhancement();
+        let _ = backend.flush();
+        prev_hook(info);
+    }));
+}
+
 // -------------
 
+/// prepare the stdout terminal for an inline tui
 pub fn sc_line_startup(inline: Rc<RefCell<InlineTui>>) -> Result<(), Error> {
     set_line_panic_hook_with_closedown(inline);
-    let mut sc = stdout();
-    execute!(sc, cursor::Hide, EnableMouseCapture)?;
-    terminal::enable_raw_mode()?;
-    Ok(())
+    CrosstermBackend::stdout().line_startup()
 }
 
+/// restore the stdout terminal after an inline tui
 pub fn sc_line_closedown(inline: InlineTui) -> Result<(), Error> {
-    let mut sc = stdout();
+    line_closedown(&mut CrosstermBackend::stdout(), inline)
+}
+
+/// restore the backend after an inline tui, leaving the cursor below the tui
+pub fn line_closedown(backend: &mut dyn Backend, inline: InlineTui) -> Result<(), Error> {
     let cur_row = inline.cursor_start_row;
     let tui_height = inline.tui_height;
     let scr_height = inline.scr_height;
 
+    // leave the cursor where the tui started, just below any committed lines
+    if !inline.keep_final_frame {
+        for y in cur_row..cur_row + tui_height {
+            backend.clear_row(y)?;
+        }
+        return backend.line_closedown(cur_row);
+    }
+
     // set the cursor back to the bottom of the screen
     if cur_row + tui_height == scr_height {
-        execute!(sc, terminal::ScrollUp(1))?;
-        execute!(sc, cursor::MoveTo(0, cur_row + tui_height),)?;
-    } else {
-        execute!(sc, cursor::MoveTo(0, cur_row + tui_height),)?;
+        backend.scroll_up(1)?;
     }
-
-    execute!(sc, style::ResetColor, cursor::Show, DisableMouseCapture)?;
-    terminal::disable_raw_mode()?;
-    Ok(())
+    backend.line_closedown(cur_row + tui_height)
 }
 
 pub fn set_line_panic_hook_with_closedown(inline: Rc<RefCell<InlineTui>>) {
@@ -0,0 +1,111 @@
+use {
+    std::{
+        cell::RefCell,
+        io::Write,
+        rc::Rc,
+        time::{Duration, Instant},
+    },
+    yeehaw::*,
+};
+
+/// a Write target which can be inspected after being handed to the tui
+#[derive(Clone, Default)]
+struct SharedBuf(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn render_to_write_target() {
+    let buf = SharedBuf::default();
+    let backend = CrosstermBackend::new(buf.clone(), Size::new(20, 3));
+    let (tui, ctx) = Tui::new().unwrap();
+    let mut tui = tui.with_backend(Box::new(backend));
+
+    let main_el = ParentPane::new(&ctx, "main_element");
+    main_el.add_element(Box::new(Label::new(&ctx, "hi").at(3, 1)));
+    tui.set_main_element(Box::new(main_el));
+    tui.last_render = Instant::now() - Duration::from_secs(1);
+    tui.render().unwrap();
+
+    let out = String::from_utf8(buf.0.borrow().clone()).unwrap();
+    // cursor moves are 1-indexed within the escape sequence, the changed row is printed in
+    // runs following a single move to its start
+    assert!(out.contains("\x1b[2;1H"));
+    assert!(!out.contains("\x1b[2;4H"));
+    assert!(out.contains("hi"));
+    assert_eq!(tui.draw_region().size, Size::new(20, 3));
+}
+
+#[test]
+fn backend_startup_closedown() {
+    let buf = SharedBuf::default();
+    let mut backend = CrosstermBackend::new(buf.clone(), Size::new(20, 3));
+    assert!(!backend.is_terminal());
+    backend.startup().unwrap();
+    backend.closedown().unwrap();
+
+    let out = String::from_utf8(buf.0.borrow().clone()).unwrap();
+    assert!(out.contains("\x1b[?1049h")); // enter alternate screen
+    assert!(out.contains("\x1b[?1049l")); // leave alternate screen
+    assert!(out.contains("\x1b[?1000h")); // mouse capture
+}
+
+#[test]
+fn synchronized_frames() {
+    let render = |sync: bool| {
+        let buf = SharedBuf::default();
+        let backend = CrosstermBackend::new(buf.clone(), Size::new(20, 3));
+        let (tui, ctx) = Tui::new().unwrap();
+        let mut tui = tui
+            .with_backend(Box::new(backend))
+            .with_synchronized_output(sync);
+        let main_el = ParentPane::new(&ctx, "main_element");
+        main_el.add_element(Box::new(Label::new(&ctx, "hello").at(3, 1)));
+        tui.set_main_element(Box::new(main_el));
+        tui.last_render = Instant::now() - Duration::from_secs(1);
+        tui.render().unwrap();
+        let out = String::from_utf8(buf.0.borrow().clone()).unwrap();
+        out
+    };
+
+    let out = render(true);
+    let begin = out.find("\x1b[?2026h").unwrap();
+    let end = out.find("\x1b[?2026l").unwrap();
+    // the label is printed as a single run within the frame
+    let hello = out.find("hello").unwrap();
+    assert!(begin < hello && hello < end);
+    assert!(!render(false).contains("\x1b[?2026"));
+}
+
+#[test]
+fn osc52_written_through_backend() {
+    let buf = SharedBuf::default();
+    let backend = CrosstermBackend::new(buf.clone(), Size::new(20, 3));
+    let (tui, ctx) = Tui::new().unwrap();
+    let mut tui = tui.with_backend(Box::new(backend));
+    let mut osc52 = Osc52Clipboard::new();
+    osc52.tmux = false;
+    ctx.clipboard.set_provider(Box::new(osc52));
+    tui.set_main_element(Box::new(ParentPane::new(&ctx, "main_element")));
+    tui.last_render = Instant::now() - Duration::from_secs(1);
+    tui.render().unwrap();
+
+    // nothing is written to the terminal until the tui renders
+    ctx.clipboard.set_text("hi").unwrap();
+    assert!(!String::from_utf8(buf.0.borrow().clone())
+        .unwrap()
+        .contains("\x1b]52;"));
+    assert!(tui.needs_render());
+    tui.last_render = Instant::now() - Duration::from_secs(1);
+    tui.render_if_needed().unwrap();
+    let out = String::from_utf8(buf.0.borrow().clone()).unwrap();
+    assert_eq!(out.matches("\x1b]52;c;aGk=\x07").count(), 1);
+}
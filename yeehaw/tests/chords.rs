@@ -0,0 +1,60 @@
+mod common;
+
+use {
+    common::{headless_with, Recorder},
+    std::time::Duration,
+    yeehaw::{tui::DEFAULT_CHORD_TIMEOUT, *},
+};
+
+fn setup() -> (Tui, Recorder) {
+    let (tui, _, rec) = headless_with(10, 3, |ctx, main| {
+        let rec = Recorder::new_with_combos(
+            ctx,
+            vec![
+                vec![Keyboard::KEY_G.into()],
+                vec![Keyboard::KEY_G.into(), Keyboard::KEY_G.into()],
+                vec![Keyboard::KEY_X.into()],
+            ],
+        );
+        main.add_element(Box::new(rec.clone()));
+        rec
+    });
+    (tui, rec)
+}
+
+#[test]
+fn longer_combo_completes() {
+    let (mut tui, rec) = setup();
+    tui.process_event_key(Keyboard::KEY_G).unwrap();
+    assert!(rec.keys().is_empty());
+    assert_eq!(tui.pending_keys(), &[Keyboard::KEY_G]);
+
+    tui.process_event_key(Keyboard::KEY_G).unwrap();
+    assert_eq!(rec.keys(), vec!["g g"]);
+    assert!(tui.pending_keys().is_empty());
+}
+
+#[test]
+fn shorter_combo_after_timeout() {
+    let (mut tui, rec) = setup();
+    tui.process_event_key(Keyboard::KEY_G).unwrap();
+    tui.advance_time(DEFAULT_CHORD_TIMEOUT / 2).unwrap();
+    assert!(rec.keys().is_empty());
+    tui.advance_time(DEFAULT_CHORD_TIMEOUT).unwrap();
+    assert_eq!(rec.keys(), vec!["g"]);
+    assert!(tui.pending_keys().is_empty());
+}
+
+#[test]
+fn shorter_combo_when_not_continued() {
+    let (mut tui, rec) = setup();
+    tui.process_event_key(Keyboard::KEY_G).unwrap();
+    tui.process_event_key(Keyboard::KEY_X).unwrap();
+    assert_eq!(rec.keys(), vec!["g", "x"]);
+
+    // without a timeout the shorter combo is used immediately
+    let (tui, rec) = setup();
+    let mut tui = tui.with_chord_timeout(Duration::ZERO);
+    tui.process_event_key(Keyboard::KEY_G).unwrap();
+    assert_eq!(rec.keys(), vec!["g"]);
+}
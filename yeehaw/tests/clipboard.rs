@@ -0,0 +1,35 @@
+mod common;
+
+use yeehaw::*;
+
+#[test]
+fn headless_clipboard_is_in_process() {
+    let (_tui, ctx) = Tui::new_headless(20, 5).unwrap();
+    assert_eq!(ctx.clipboard.provider_name(), "in-process");
+    ctx.clipboard.set_text("hello").unwrap();
+    // the clipboard is shared between clones of the context
+    assert_eq!(ctx.clone().clipboard.get_text().unwrap(), "hello");
+}
+
+#[cfg(feature = "textbox")]
+#[test]
+fn textbox_and_list_control_use_context_clipboard() {
+    let (_tui, ctx, tb) = common::headless_with(20, 5, |ctx, main| {
+        let tb = TextBox::new(ctx, "ab");
+        main.add_element(Box::new(tb.clone()));
+        tb
+    });
+    let lc = ListControl::new(&ctx, vec!["one".into(), "two".into(), "three".into()]);
+
+    ctx.clipboard.set_text("cd").unwrap();
+    tb.set_cursor_pos(2);
+    let _ = tb.inner.borrow().paste_from_clipboard(&ctx);
+    assert_eq!(tb.get_text(), "abcd");
+
+    // without a selection the provided entry is copied
+    lc.inner.borrow().copy_to_clipboard(&ctx, Some(1));
+    assert_eq!(ctx.clipboard.get_text().unwrap(), "two");
+    *lc.inner.borrow().selected.borrow_mut() = vec![2, 0];
+    lc.inner.borrow().copy_to_clipboard(&ctx, Some(1));
+    assert_eq!(ctx.clipboard.get_text().unwrap(), "one\nthree");
+}
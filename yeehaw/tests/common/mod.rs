@@ -0,0 +1,103 @@
+// shared by the integration tests, not every test uses all of it
+#![allow(dead_code)]
+
+use {
+    std::{cell::RefCell, rc::Rc},
+    yeehaw::*,
+};
+
+/// A started headless tui of the provided size whose main element is a parent pane, the elements
+/// of the test are added to it by the build fn whose output is returned.
+pub fn headless_with<R>(
+    width: u16, height: u16, build: impl FnOnce(&Context, &ParentPane) -> R,
+) -> (Tui, Context, R) {
+    let (mut tui, ctx) = Tui::new_headless(width, height).unwrap();
+    let main = ParentPane::new(&ctx, "main");
+    let out = build(&ctx, &main);
+    tui.start_headless(Box::new(main)).unwrap();
+    (tui, ctx, out)
+}
+
+pub type RespondFn = Box<dyn FnMut(&Event) -> (bool, EventResponses)>;
+
+/// A focused element which records the key combos, pastes, focus changes and task outputs it
+/// receives. By default the key combos, pastes and task outputs are captured without responses,
+/// this may be changed with a respond function.
+#[derive(Clone)]
+pub struct Recorder {
+    pub pane: Pane,
+    pub got: Rc<RefCell<Vec<Event>>>,
+    pub respond: Rc<RefCell<RespondFn>>,
+}
+
+impl Recorder {
+    pub fn new(ctx: &Context, rec_evs: ReceivableEvents) -> Self {
+        let pane = Pane::new(ctx, "recorder")
+            .with_focused(true)
+            .with_focused_receivable_events(rec_evs);
+        Recorder {
+            pane,
+            got: Rc::new(RefCell::new(Vec::new())),
+            respond: Rc::new(RefCell::new(Box::new(|ev| {
+                let captured = !matches!(ev, Event::FocusGained | Event::FocusLost);
+                (captured, EventResponses::default())
+            }))),
+        }
+    }
+
+    /// a recorder receiving the provided key combos
+    pub fn new_with_combos(ctx: &Context, combos: Vec<Vec<KeyPossibility>>) -> Self {
+        let evs = combos
+            .into_iter()
+            .map(ReceivableEvent::KeyCombo)
+            .collect::<Vec<_>>();
+        Self::new(ctx, evs.into())
+    }
+
+    pub fn with_respond(self, respond: RespondFn) -> Self {
+        *self.respond.borrow_mut() = respond;
+        self
+    }
+
+    /// the key combos received, as strings
+    pub fn keys(&self) -> Vec<String> {
+        self.got
+            .borrow()
+            .iter()
+            .filter_map(|ev| match ev {
+                Event::KeyCombo(keys) => Some(Keyboard::keys_to_string(keys)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// take the task outputs of the type received
+    pub fn task_outputs<T: 'static>(&self) -> Vec<T> {
+        self.got
+            .borrow()
+            .iter()
+            .filter_map(|ev| match ev {
+                Event::TaskDone(out) => out.take::<T>(),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[yeehaw_derive::impl_element_from(pane)]
+impl Element for Recorder {
+    fn receive_event(&self, _ctx: &Context, ev: Event) -> (bool, EventResponses) {
+        match ev {
+            Event::KeyCombo(_)
+            | Event::Paste(_)
+            | Event::FocusGained
+            | Event::FocusLost
+            | Event::TaskDone(_) => {
+                let resp = self.respond.borrow_mut()(&ev);
+                self.got.borrow_mut().push(ev);
+                resp
+            }
+            _ => (false, EventResponses::default()),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use yeehaw::*;
+
+/// a 2x2 grid of buttons plus one off to the bottom right
+///
+///  a   b
+///  c   d
+///         e
+fn grid(ctx: &Context, pane: &ParentPaneOfSelectable) -> Vec<Button> {
+    let btns = [
+        ("a", 0, 0),
+        ("b", 10, 0),
+        ("c", 0, 4),
+        ("d", 10, 4),
+        ("e", 20, 8),
+    ]
+    .iter()
+    .map(|(t, x, y)| Button::new(ctx, t).at(*x, *y))
+    .collect::<Vec<_>>();
+    for b in btns.iter() {
+        pane.add_element(Box::new(b.clone()));
+    }
+    btns
+}
+
+fn selected(btns: &[Button]) -> Vec<String> {
+    btns.iter()
+        .filter(|b| b.pane.get_selectability() == Selectability::Selected)
+        .map(|b| b.text.borrow().clone())
+        .collect()
+}
+
+#[test]
+fn directional_nav_moves_spatially() {
+    let (mut tui, ctx) = Tui::new_headless(40, 12).unwrap();
+    let pane =
+        ParentPaneOfSelectable::new(&ctx).with_directional_nav(&ctx, DirectionalNav::default());
+    let btns = grid(&ctx, &pane);
+    tui.start_headless(Box::new(pane.clone())).unwrap();
+
+    // nothing selected, the first selectable is chosen
+    tui.process_event_key(Keyboard::KEY_RIGHT).unwrap();
+    assert_eq!(selected(&btns), vec!["a"]);
+
+    tui.process_event_key(Keyboard::KEY_RIGHT).unwrap();
+    assert_eq!(selected(&btns), vec!["b"]);
+    tui.process_event_key(Keyboard::KEY_J).unwrap();
+    assert_eq!(selected(&btns), vec!["d"]);
+    tui.process_event_key(Keyboard::KEY_H).unwrap();
+    assert_eq!(selected(&btns), vec!["c"]);
+    tui.process_event_key(Keyboard::KEY_UP).unwrap();
+    assert_eq!(selected(&btns), vec!["a"]);
+
+    // no element further up, the selection is unchanged
+    tui.process_event_key(Keyboard::KEY_UP).unwrap();
+    assert_eq!(selected(&btns), vec!["a"]);
+
+    // unselectable elements are skipped
+    let _ = btns[1].pane.disable();
+    tui.process_event_key(Keyboard::KEY_RIGHT).unwrap();
+    assert_eq!(selected(&btns), vec!["d"]);
+}
+
+#[test]
+fn directional_nav_is_opt_in() {
+    let (mut tui, ctx) = Tui::new_headless(40, 12).unwrap();
+    let pane = ParentPaneOfSelectable::new(&ctx);
+    let btns = grid(&ctx, &pane);
+    tui.start_headless(Box::new(pane.clone())).unwrap();
+
+    tui.process_event_key(Keyboard::KEY_RIGHT).unwrap();
+    assert!(selected(&btns).is_empty());
+    tui.process_event_key(Keyboard::KEY_TAB).unwrap();
+    assert_eq!(selected(&btns), vec!["a"]);
+}
+
+#[test]
+fn directional_nav_weighting() {
+    let nav = DirectionalNav::default();
+    // moving right: a nearer but misaligned element vs a further aligned element
+    let from = (0, 4, 0, 1);
+    let near_misaligned = (6, 10, 4, 5);
+    let far_aligned = (12, 16, 0, 1);
+    assert!(
+        nav.distance(NavDirection::Right, from, far_aligned)
+            .unwrap()
+            < nav
+                .distance(NavDirection::Right, from, near_misaligned)
+                .unwrap()
+    );
+    let nav = nav.with_perpendicular_weight(0.);
+    assert!(
+        nav.distance(NavDirection::Right, from, near_misaligned)
+            .unwrap()
+            < nav
+                .distance(NavDirection::Right, from, far_aligned)
+                .unwrap()
+    );
+    assert!(nav
+        .distance(NavDirection::Left, from, far_aligned)
+        .is_none());
+}
@@ -0,0 +1,148 @@
+mod common;
+
+use {
+    common::headless_with,
+    std::{cell::RefCell, rc::Rc, time::Duration},
+    yeehaw::*,
+};
+
+#[test]
+fn update_dirty_content_only_when_dirty() {
+    let (mut tui, ctx) = Tui::new_headless(10, 3).unwrap();
+    let calls = Rc::new(RefCell::new(0));
+    let calls_ = calls.clone();
+    let pane = Pane::new(&ctx, "counter");
+    let pane_ = pane.clone();
+    pane.set_update_dirty_content(Box::new(move |_, dr| {
+        *calls_.borrow_mut() += 1;
+        pane_.set_content_from_string(format!("{}x{}", dr.size.width, dr.size.height));
+    }));
+    tui.start_headless(Box::new(pane.clone())).unwrap();
+    assert_eq!(*calls.borrow(), 1);
+    assert!(tui.headless_screen().unwrap().find("10x3").is_some());
+
+    // idle frames reuse the content
+    for _ in 0..5 {
+        tui.advance_time(Duration::from_millis(50)).unwrap();
+    }
+    assert_eq!(*calls.borrow(), 1);
+
+    pane.set_dirty();
+    assert!(pane.is_dirty());
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+    assert_eq!(*calls.borrow(), 2);
+    assert!(!pane.is_dirty());
+
+    // as well as on a size change
+    tui.resize_headless(8, 2).unwrap();
+    assert_eq!(*calls.borrow(), 3);
+    assert!(tui.headless_screen().unwrap().find("8x2").is_some());
+}
+
+#[test]
+fn widgets_are_clean_when_idle() {
+    let (mut tui, _, (lb, dd, slider, fnp)) = headless_with(40, 12, |ctx, main| {
+        let lb = ListBox::new(ctx, vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .with_dyn_width(DynVal::new_fixed(5));
+        let dd = DropdownList::new(
+            ctx,
+            vec!["x", "y"],
+            Box::new(|_, _| EventResponses::default()),
+        )
+        .at(10, 0);
+        let slider = Slider::new_basic_block(ctx)
+            .at(0, 5)
+            .with_dyn_width(DynVal::new_fixed(10));
+        let fnp = FileNavPane::new(ctx, std::env::temp_dir());
+        fnp.pane.set_at(20.into(), 0.into());
+        main.add_element(Box::new(lb.clone()));
+        main.add_element(Box::new(dd.clone()));
+        main.add_element(Box::new(slider.clone()));
+        main.add_element(Box::new(fnp.clone()));
+        (lb, dd, slider, fnp)
+    });
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+
+    assert!(!lb.inner.borrow().pane.is_dirty());
+    assert!(!dd.is_dirty());
+    assert!(!slider.is_dirty());
+    assert!(!fnp.pane.is_dirty());
+
+    // changes are still drawn
+    let before = tui.headless_screen().unwrap().text();
+    slider.set_position(1.0);
+    assert!(slider.is_dirty());
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+    assert!(!slider.is_dirty());
+    assert_ne!(tui.headless_screen().unwrap().text(), before);
+}
+
+/// counts the number of times it is drawn
+#[derive(Clone)]
+struct DrawCounter {
+    pane: Pane,
+    draws: Rc<RefCell<usize>>,
+}
+
+#[yeehaw_derive::impl_element_from(pane)]
+impl Element for DrawCounter {
+    fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
+        *self.draws.borrow_mut() += 1;
+        self.pane.drawing(ctx, dr, force_update)
+    }
+}
+
+fn counter_tui(bg: impl Fn(&Context) -> Color) -> (Tui, DrawCounter) {
+    let (tui, _, counter) = headless_with(10, 3, |ctx, main| {
+        let counter = DrawCounter {
+            pane: Pane::new(ctx, "counter"),
+            draws: Rc::new(RefCell::new(0)),
+        };
+        counter.pane.set_bg(bg(ctx));
+        // nested to ensure dirtiness is signalled up through the parents
+        let inner = ParentPane::new(ctx, "inner");
+        inner.add_element(Box::new(counter.clone()));
+        main.add_element(Box::new(inner));
+        counter
+    });
+    (tui, counter)
+}
+
+#[test]
+fn idle_tui_skips_rendering() {
+    let (mut tui, counter) = counter_tui(|_| Color::BLUE);
+    assert_eq!(*counter.draws.borrow(), 1);
+    assert!(!tui.needs_render());
+    assert_eq!(tui.next_wake(), None);
+    assert!(!tui.render_if_needed().unwrap());
+    assert_eq!(*counter.draws.borrow(), 1);
+
+    counter.pane.set_dirty();
+    assert!(tui.needs_render());
+    assert!(tui.next_wake().is_some());
+    assert!(tui.render_if_needed().unwrap());
+    assert_eq!(*counter.draws.borrow(), 2);
+
+    // processed events always render
+    tui.process_event_focus(false).unwrap();
+    assert!(tui.render_if_needed().unwrap());
+    assert_eq!(*counter.draws.borrow(), 3);
+    assert_eq!(tui.next_wake(), None);
+}
+
+#[test]
+fn time_gradients_animate_without_rendering() {
+    let (mut tui, counter) = counter_tui(|ctx| {
+        TimeGradient::new_loop(ctx, Duration::from_secs(1), vec![Color::RED, Color::BLUE]).into()
+    });
+    assert!(tui.next_wake().is_some());
+    let before = tui.headless_screen().unwrap().style_at(0, 0);
+
+    tui.headless
+        .as_mut()
+        .unwrap()
+        .advance(Duration::from_millis(500));
+    assert!(!tui.render_if_needed().unwrap());
+    assert_eq!(*counter.draws.borrow(), 1);
+    assert_ne!(tui.headless_screen().unwrap().style_at(0, 0), before);
+}
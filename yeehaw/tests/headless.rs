@@ -0,0 +1,89 @@
+use {
+    crossterm::event::{KeyModifiers, MouseButton, MouseEvent as CTMouseEvent, MouseEventKind},
+    std::time::Duration,
+    yeehaw::*,
+};
+
+fn hello_world(ctx: &Context) -> (ParentPane, Label) {
+    let main_el = ParentPane::new(ctx, "main_element");
+    let label = Label::new(ctx, "Hello, World!").at(2, 1);
+    let label_ = label.clone();
+    let button = Button::new(ctx, "Click Here!")
+        .with_fn(Box::new(move |_, _| {
+            label_.set_text("Button clicked!".to_string());
+            EventResponses::default()
+        }))
+        .at(2, 3);
+    main_el.add_element(Box::new(label.clone()));
+    main_el.add_element(Box::new(button));
+    (main_el, label)
+}
+
+fn click(tui: &mut Tui, column: u16, row: u16) {
+    for kind in [
+        MouseEventKind::Down(MouseButton::Left),
+        MouseEventKind::Up(MouseButton::Left),
+    ] {
+        let ev = CTMouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        tui.process_event_mouse(ev).unwrap();
+    }
+}
+
+#[test]
+fn headless_renders_to_screen() {
+    let (mut tui, ctx) = Tui::new_headless(30, 6).unwrap();
+    let (main_el, _) = hello_world(&ctx);
+    tui.start_headless(Box::new(main_el)).unwrap();
+
+    let screen = tui.headless_screen().unwrap();
+    assert_eq!(screen.find("Hello, World!"), Some((2, 1)));
+    assert_eq!(screen.row_text(1).chars().count(), 30);
+    assert!(screen.contains("Click Here!"));
+}
+
+#[test]
+fn headless_mouse_click() {
+    let (mut tui, ctx) = Tui::new_headless(30, 6).unwrap();
+    let (main_el, label) = hello_world(&ctx);
+    tui.start_headless(Box::new(main_el)).unwrap();
+
+    let (x, y) = tui.headless_screen().unwrap().find("Click").unwrap();
+    click(&mut tui, x as u16, y as u16);
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+
+    assert_eq!(label.get_text(), "Button clicked!");
+    assert!(tui.headless_screen().unwrap().contains("Button clicked!"));
+}
+
+#[test]
+fn headless_resize_and_clock() {
+    let (mut tui, ctx) = Tui::new_headless(30, 6).unwrap();
+    let (main_el, _) = hello_world(&ctx);
+    tui.start_headless(Box::new(main_el)).unwrap();
+
+    tui.resize_headless(20, 4).unwrap();
+    let screen = tui.headless_screen().unwrap();
+    assert_eq!(screen.size(), Size::new(20, 4));
+    assert!(screen.contains("Hello, World!"));
+
+    tui.advance_time(Duration::from_secs(2)).unwrap();
+    assert_eq!(tui.context().dur_since_launch, Duration::from_secs(2));
+}
+
+#[test]
+fn headless_snapshot() {
+    let (mut tui, ctx) = Tui::new_headless(30, 6).unwrap();
+    let (main_el, _) = hello_world(&ctx);
+    tui.start_headless(Box::new(main_el)).unwrap();
+
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/snapshots/hello_world.snap"
+    );
+    tui.headless_screen().unwrap().assert_snapshot(path);
+}
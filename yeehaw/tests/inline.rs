@@ -0,0 +1,103 @@
+use yeehaw::{tui::line_closedown, *};
+
+/// an inline tui of the provided height filled with '#', the tui starts at the top of the screen
+fn setup(width: u16, scr_height: u16, height: u16, keep_frame: bool) -> (Tui, Context) {
+    let (tui, ctx) = Tui::new_headless(width, scr_height).unwrap();
+    let mut tui = tui.with_keep_inline_frame(keep_frame);
+    let pane = Pane::new(&ctx, "fill");
+    pane.set_content_from_string(vec!["#".repeat(width as usize); 20].join("\n"));
+    tui.start_headless_inline(Box::new(pane), height).unwrap();
+    (tui, ctx)
+}
+
+fn filled_rows(tui: &Tui) -> Vec<usize> {
+    let scr = tui.headless_screen().unwrap();
+    (0..scr.size().height as usize)
+        .filter(|y| scr.row_text(*y).starts_with('#'))
+        .collect()
+}
+
+#[test]
+fn grow_and_shrink() {
+    let (mut tui, ctx) = setup(20, 10, 3, true);
+    assert_eq!(filled_rows(&tui), vec![0, 1, 2]);
+
+    ctx.inline.set_height(5);
+    assert!(tui.needs_render());
+    tui.render().unwrap();
+    assert!(!tui.needs_render());
+    assert_eq!(filled_rows(&tui), vec![0, 1, 2, 3, 4]);
+
+    ctx.inline.set_height(2);
+    tui.render().unwrap();
+    assert_eq!(filled_rows(&tui), vec![0, 1]);
+
+    // growing beyond the bottom of the screen scrolls the screen
+    ctx.inline.set_height(25);
+    tui.render().unwrap();
+    assert_eq!(filled_rows(&tui), (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn commit_lines_into_scrollback() {
+    let (mut tui, ctx) = setup(20, 6, 3, true);
+    for i in 1..=5 {
+        ctx.inline.print_line(&format!("line {i}"));
+    }
+    tui.render().unwrap();
+
+    // the lines are written above the tui, the oldest scrolling off the screen
+    let scr = tui.headless_screen().unwrap();
+    assert_eq!(scr.scrollback(), ["line 1", "line 2"]);
+    assert_eq!(scr.row_text(0).trim_end(), "line 3");
+    assert_eq!(scr.row_text(2).trim_end(), "line 5");
+    assert_eq!(filled_rows(&tui), vec![3, 4, 5]);
+    assert_eq!(tui.inline.as_ref().unwrap().borrow().cursor_start_row, 3);
+
+    // styled multi-line content
+    let sty = Style::default().with_fg(Color::RED);
+    ctx.inline.print_styled_line("red\nlines", sty);
+    tui.render().unwrap();
+    let scr = tui.headless_screen().unwrap();
+    assert_eq!(scr.row_text(2).trim_end(), "lines");
+    assert_ne!(scr.style_at(0, 2), scr.style_at(0, 0));
+    assert_eq!(scr.scrollback().len(), 4);
+}
+
+#[test]
+fn long_lines_wrap() {
+    let (mut tui, ctx) = setup(10, 10, 3, true);
+    ctx.inline.print_line("0123456789abcdefghij!");
+    ctx.inline.print_line("");
+    tui.render().unwrap();
+    let scr = tui.headless_screen().unwrap();
+    assert_eq!(scr.row_text(0), "0123456789");
+    assert_eq!(scr.row_text(1), "abcdefghij");
+    assert_eq!(scr.row_text(2).trim_end(), "!");
+    assert_eq!(scr.row_text(3).trim_end(), "");
+    assert_eq!(filled_rows(&tui), vec![4, 5, 6]);
+}
+
+#[test]
+fn closedown_final_frame() {
+    for keep in [true, false] {
+        let (mut tui, ctx) = setup(20, 10, 3, keep);
+        ctx.inline.print_line("done");
+        tui.render().unwrap();
+        let inline = *tui.inline.as_ref().unwrap().borrow();
+        let scr = tui.headless.as_mut().unwrap();
+        line_closedown(scr, inline).unwrap();
+
+        assert_eq!(scr.row_text(0).trim_end(), "done");
+        if keep {
+            assert_eq!(filled_rows(&tui), vec![1, 2, 3]);
+        } else {
+            assert!(filled_rows(&tui).is_empty());
+            // the cursor is left just below the committed lines
+            assert_eq!(
+                tui.headless.as_mut().unwrap().cursor_position().unwrap(),
+                (0, 1)
+            );
+        }
+    }
+}
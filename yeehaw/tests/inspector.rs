@@ -0,0 +1,118 @@
+mod common;
+
+use {
+    common::headless_with,
+    crossterm::event::{KeyModifiers, MouseButton, MouseEvent as CTMouseEvent, MouseEventKind},
+    yeehaw::*,
+};
+
+fn mouse(tui: &mut Tui, kind: MouseEventKind, column: u16, row: u16) {
+    let ev = CTMouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    };
+    tui.process_event_mouse(ev).unwrap();
+}
+
+fn key(tui: &mut Tui, key: crossterm::event::KeyEvent) {
+    tui.process_event_key(key).unwrap();
+    tui.render().unwrap();
+}
+
+/// a leaf nested within an inner parent, the leaf is at (7, 5) on the screen and of size 4x3
+fn setup() -> (Tui, ParentPane, Pane) {
+    let (tui, _, (inner, leaf)) = headless_with(80, 20, |ctx, main| {
+        let inner =
+            ParentPane::new(ctx, "inner").with_dyn_location(DynLocation::new_fixed(5, 25, 3, 13));
+        let leaf = Pane::new(ctx, "leaf").with_dyn_location(DynLocation::new_fixed(2, 6, 2, 5));
+        inner.add_element(Box::new(leaf.clone()));
+        main.add_element(Box::new(inner.clone()));
+        (inner, leaf)
+    });
+    (tui, inner, leaf)
+}
+
+#[test]
+fn disabled_by_default() {
+    let (mut tui, _, _) = setup();
+    key(&mut tui, Keyboard::KEY_F9);
+    assert!(tui.inspector().is_none());
+    assert!(!tui.headless_screen().unwrap().contains("inspector"));
+}
+
+#[test]
+fn toggle_shows_tree() {
+    let (tui, inner, leaf) = setup();
+    let mut tui = tui.with_inspector();
+    key(&mut tui, Keyboard::KEY_F9);
+    assert!(tui.inspector().is_some());
+    let scr = tui.headless_screen().unwrap();
+    assert!(scr.contains("inspector (esc to close)"));
+    assert!(scr.contains(&inner.id()));
+    assert!(scr.contains(&leaf.id()));
+
+    let node = tui.inspector().unwrap().node(&leaf.id()).unwrap();
+    assert_eq!(node.kind, "leaf");
+    assert_eq!(node.at, Point::new(7, 5));
+    assert_eq!(node.size, Size::new(4, 3));
+
+    // esc closes the inspector as does the key
+    key(&mut tui, Keyboard::KEY_ESC);
+    assert!(tui.inspector().is_none());
+    assert!(!tui.headless_screen().unwrap().contains("inspector"));
+    key(&mut tui, Keyboard::KEY_F9);
+    key(&mut tui, Keyboard::KEY_F9);
+    assert!(tui.inspector().is_none());
+}
+
+#[test]
+fn hover_and_select() {
+    let (tui, inner, leaf) = setup();
+    let mut tui = tui.with_inspector();
+    key(&mut tui, Keyboard::KEY_F9);
+
+    // the deepest element under the mouse is hovered
+    mouse(&mut tui, MouseEventKind::Moved, 8, 6);
+    let insp = tui.inspector().unwrap().clone();
+    assert_eq!(*insp.hovered.borrow(), Some(leaf.id()));
+    mouse(&mut tui, MouseEventKind::Moved, 6, 4);
+    assert_eq!(*insp.hovered.borrow(), Some(inner.id()));
+
+    // clicking selects and outlines the element
+    mouse(&mut tui, MouseEventKind::Down(MouseButton::Left), 8, 6);
+    tui.render().unwrap();
+    assert_eq!(*insp.selected.borrow(), Some(leaf.id()));
+    let scr = tui.headless_screen().unwrap();
+    assert_eq!(scr.row_text(5).chars().nth(7), Some('┌'));
+    assert_eq!(scr.row_text(7).chars().nth(10), Some('┘'));
+    assert!(scr.contains(&format!("{} (leaf)", leaf.id())));
+    assert!(scr.contains("size: 4x3"));
+
+    // up and down move through the tree
+    key(&mut tui, Keyboard::KEY_UP);
+    assert_eq!(*insp.selected.borrow(), Some(inner.id()));
+    key(&mut tui, Keyboard::KEY_DOWN);
+    assert_eq!(*insp.selected.borrow(), Some(leaf.id()));
+}
+
+#[test]
+fn scrolled_pane_children() {
+    let (tui, ctx) = Tui::new_headless(80, 20).unwrap();
+    let mut tui = tui.with_inspector();
+    let sc = PaneScrollable::new(&ctx, 80, 40);
+    let leaf = Pane::new(&ctx, "leaf").with_dyn_location(DynLocation::new_fixed(2, 6, 12, 15));
+    sc.add_element(Box::new(leaf.clone()));
+    tui.start_headless(Box::new(sc.clone())).unwrap();
+    sc.set_content_y_offset(Some(&tui.draw_region()), 10);
+    key(&mut tui, Keyboard::KEY_F9);
+
+    // positioned where the leaf is drawn
+    let node = tui.inspector().unwrap().node(&leaf.id()).unwrap();
+    assert_eq!(node.at, Point::new(2, 2));
+    assert_eq!(node.size, Size::new(4, 3));
+    mouse(&mut tui, MouseEventKind::Moved, 3, 3);
+    let insp = tui.inspector().unwrap().clone();
+    assert_eq!(*insp.hovered.borrow(), Some(leaf.id()));
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use {
+    common::{headless_with, Recorder},
+    crossterm::event::{KeyEvent, KeyEventKind},
+    yeehaw::*,
+};
+
+fn setup(combos: Vec<Vec<KeyPossibility>>) -> (Tui, Recorder) {
+    let (tui, _, rec) = headless_with(10, 3, |ctx, main| {
+        let rec = Recorder::new_with_combos(ctx, combos);
+        main.add_element(Box::new(rec.clone()));
+        rec
+    });
+    (tui, rec)
+}
+
+fn with_kind(k: KeyEvent, kind: KeyEventKind) -> KeyEvent {
+    KeyEvent { kind, ..k }
+}
+
+#[test]
+fn releases_only_reach_release_combos() {
+    let (mut tui, rec) = setup(vec![
+        vec![KeyPossibility::Chars],
+        vec![Keyboard::KEY_G.into(), Keyboard::KEY_G.into()],
+        vec![KeyPossibility::released(Keyboard::KEY_X)],
+    ]);
+    let release = |k| with_kind(k, KeyEventKind::Release);
+
+    // a release between the keys of a combo does not break it
+    tui.process_event_key(Keyboard::KEY_G).unwrap();
+    tui.process_event_key(release(Keyboard::KEY_G)).unwrap();
+    tui.process_event_key(Keyboard::KEY_G).unwrap();
+    tui.process_event_key(release(Keyboard::KEY_G)).unwrap();
+
+    tui.process_event_key(Keyboard::KEY_X).unwrap();
+    tui.process_event_key(release(Keyboard::KEY_X)).unwrap();
+    assert_eq!(rec.keys(), vec!["g g", "x", "release+x"]);
+}
+
+#[test]
+fn repeats_are_delivered_as_presses() {
+    let (mut tui, rec) = setup(vec![
+        vec![Keyboard::KEY_J.into()],
+        vec![KeyPossibility::repeated(Keyboard::KEY_K)],
+    ]);
+    let repeat = |k| with_kind(k, KeyEventKind::Repeat);
+
+    tui.process_event_key(Keyboard::KEY_J).unwrap();
+    tui.process_event_key(repeat(Keyboard::KEY_J)).unwrap();
+    // only repeats are matched when explicitly requested
+    tui.process_event_key(Keyboard::KEY_K).unwrap();
+    tui.process_event_key(repeat(Keyboard::KEY_K)).unwrap();
+    assert_eq!(rec.keys(), vec!["j", "j", "repeat+k"]);
+}
+
+#[test]
+fn key_kind_strings() {
+    let k: KeyPossibility = "release+ctrl+a".parse().unwrap();
+    assert_eq!(k, KeyPossibility::released(Keyboard::KEY_CTRL_A));
+    assert_eq!(k.to_string(), "release+ctrl+a");
+    let any: KeyPossibility = "<repeat>".parse().unwrap();
+    assert_eq!(any, KeyPossibility::AnyOfKind(KeyEventKind::Repeat));
+    assert_eq!(any.to_string(), "<repeat>");
+    assert!(any.matches_key(&with_kind(Keyboard::KEY_A, KeyEventKind::Repeat)));
+    assert!(!any.matches_key(&Keyboard::KEY_A));
+}
+
+#[cfg(feature = "terminal")]
+#[test]
+fn terminal_key_encoding() {
+    use {
+        crossterm::event::{KeyCode, KeyModifiers},
+        yeehaw::elements::panes::terminal::encode_key,
+    };
+    let enc = |code, modifiers| encode_key(&KeyEvent::new(code, modifiers), false);
+
+    // keys only distinguishable with keyboard enhancement
+    assert_eq!(enc(KeyCode::Tab, KeyModifiers::NONE), Some(vec![9]));
+    assert_eq!(
+        enc(KeyCode::Char('i'), KeyModifiers::CONTROL),
+        Some(vec![9])
+    );
+    assert_eq!(
+        enc(KeyCode::Backspace, KeyModifiers::NONE),
+        Some(vec![0x7F])
+    );
+    assert_eq!(
+        enc(KeyCode::Backspace, KeyModifiers::CONTROL),
+        Some(vec![8])
+    );
+
+    assert_eq!(
+        enc(KeyCode::Char('é'), KeyModifiers::NONE),
+        Some("é".into())
+    );
+    assert_eq!(
+        enc(KeyCode::Char('x'), KeyModifiers::ALT),
+        Some(b"\x1bx".to_vec())
+    );
+    assert_eq!(
+        enc(KeyCode::Left, KeyModifiers::NONE),
+        Some(b"\x1b[D".to_vec())
+    );
+    assert_eq!(
+        enc(KeyCode::Left, KeyModifiers::CONTROL),
+        Some(b"\x1b[1;5D".to_vec())
+    );
+    assert_eq!(
+        enc(KeyCode::Delete, KeyModifiers::SHIFT),
+        Some(b"\x1b[3;2~".to_vec())
+    );
+    assert_eq!(
+        enc(KeyCode::F(1), KeyModifiers::NONE),
+        Some(b"\x1bOP".to_vec())
+    );
+    assert_eq!(
+        enc(KeyCode::F(5), KeyModifiers::NONE),
+        Some(b"\x1b[15~".to_vec())
+    );
+
+    // application cursor keys
+    let up = KeyEvent::new(KeyCode::Up, KeyModifiers::NONE);
+    assert_eq!(encode_key(&up, true), Some(b"\x1bOA".to_vec()));
+
+    // releases are not sent, repeats are sent as presses
+    assert_eq!(
+        encode_key(&with_kind(up, KeyEventKind::Release), false),
+        None
+    );
+    assert_eq!(
+        encode_key(&with_kind(up, KeyEventKind::Repeat), false),
+        Some(b"\x1b[A".to_vec())
+    );
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use {common::headless_with, yeehaw::*};
+
+fn cursor(lb: &ListBox) -> Option<usize> {
+    *lb.inner.borrow().cursor.borrow()
+}
+
+#[test]
+fn listbox_actions_are_remappable() {
+    let entries = KeyMapEntries::from_json(
+        r#"{"actions": {"listbox.cursor_down": ["ctrl+n"], "listbox.cursor_up": ["g g"]}}"#,
+    )
+    .unwrap();
+    let (mut tui, _, lb) = headless_with(20, 5, |ctx, main| {
+        ctx.keymap.set_entries(entries);
+        let lb = ListBox::new(ctx, vec!["a".into(), "b".into(), "c".into()]);
+        main.add_element(Box::new(lb.clone()));
+        lb
+    });
+    lb.pane.set_selectability(Selectability::Selected, false);
+
+    // the default keys are no longer bound
+    tui.process_event_key(Keyboard::KEY_J).unwrap();
+    tui.process_event_key(Keyboard::KEY_DOWN).unwrap();
+    assert_eq!(cursor(&lb), None);
+
+    tui.process_event_key(Keyboard::KEY_CTRL_N).unwrap();
+    tui.process_event_key(Keyboard::KEY_CTRL_N).unwrap();
+    assert_eq!(cursor(&lb), Some(1));
+
+    tui.process_event_key(Keyboard::KEY_G).unwrap();
+    tui.process_event_key(Keyboard::KEY_G).unwrap();
+    assert_eq!(cursor(&lb), Some(0));
+
+    // actions without an override keep their defaults
+    tui.process_event_key(Keyboard::KEY_ENTER).unwrap();
+    assert!(lb.inner.borrow().selected.borrow().contains(&0));
+}
+
+#[cfg(feature = "textbox")]
+#[test]
+fn textbox_delete_word() {
+    let (mut tui, _, tb) = headless_with(20, 5, |ctx, main| {
+        ctx.keymap.set_action(
+            "textbox.delete_word",
+            vec![vec![Keyboard::KEY_ALT_W.into()]],
+        );
+        let tb = TextBox::new(ctx, "");
+        main.add_element(Box::new(tb.clone()));
+        tb
+    });
+    tb.pane.set_selectability(Selectability::Selected, false);
+
+    for k in [
+        Keyboard::KEY_A,
+        Keyboard::KEY_SPACE,
+        Keyboard::KEY_B,
+        Keyboard::KEY_C,
+    ] {
+        tui.process_event_key(k).unwrap();
+    }
+    assert_eq!(tb.get_text(), "a bc");
+    tui.process_event_key(Keyboard::KEY_ALT_W).unwrap();
+    assert_eq!(tb.get_text(), "a ");
+    tui.process_event_key(Keyboard::KEY_ALT_W).unwrap();
+    assert_eq!(tb.get_text(), "");
+}
+
+#[test]
+fn checkbox_click_is_remappable() {
+    let (mut tui, _, cb) = headless_with(20, 5, |ctx, main| {
+        ctx.keymap
+            .set_action("checkbox.click", vec![vec![Keyboard::KEY_SPACE.into()]]);
+        let cb = Checkbox::new(ctx);
+        main.add_element(Box::new(cb.clone()));
+        cb
+    });
+    cb.pane.set_selectability(Selectability::Selected, false);
+
+    tui.process_event_key(Keyboard::KEY_ENTER).unwrap();
+    assert!(!*cb.checked.borrow());
+    tui.process_event_key(Keyboard::KEY_SPACE).unwrap();
+    assert!(*cb.checked.borrow());
+}
+
+#[test]
+fn focus_keys_are_remappable() {
+    let (mut tui, ctx) = Tui::new_headless(20, 5).unwrap();
+    ctx.keymap.set_action(
+        "parent_pane_of_selectable.next",
+        vec![vec![Keyboard::KEY_CTRL_N.into()]],
+    );
+    let pane = ParentPaneOfSelectable::new(&ctx);
+    let (a, b) = (Button::new(&ctx, "a"), Button::new(&ctx, "b").at(5, 0));
+    pane.add_element(Box::new(a.clone()));
+    pane.add_element(Box::new(b.clone()));
+    tui.start_headless(Box::new(pane)).unwrap();
+
+    tui.process_event_key(Keyboard::KEY_TAB).unwrap();
+    assert_ne!(a.pane.get_selectability(), Selectability::Selected);
+    tui.process_event_key(Keyboard::KEY_CTRL_N).unwrap();
+    assert_eq!(a.pane.get_selectability(), Selectability::Selected);
+    tui.process_event_key(Keyboard::KEY_CTRL_N).unwrap();
+    assert_eq!(b.pane.get_selectability(), Selectability::Selected);
+
+    // actions without an override keep their defaults
+    tui.process_event_key(Keyboard::KEY_BACKTAB).unwrap();
+    assert_eq!(a.pane.get_selectability(), Selectability::Selected);
+}
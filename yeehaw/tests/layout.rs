@@ -0,0 +1,144 @@
+use {
+    std::time::{Duration, SystemTime},
+    yeehaw::*,
+};
+
+const LAYOUT: &str = r#"{
+    "kind": "vertical_stack",
+    "children": [
+        {
+            "kind": "pane",
+            "height": 1,
+            "children": [{ "kind": "label", "name": "title", "text": "TITLE", "x": "50% - 2" }]
+        },
+        {
+            "kind": "tabs",
+            "name": "tabs",
+            "tabs": [
+                { "title": "one", "content": { "kind": "button", "name": "go", "text": "GO" } },
+                { "title": "two", "content": { "kind": "pane", "bg": "navy" } }
+            ]
+        }
+    ]
+}"#;
+
+#[test]
+fn dyn_val_from_str() {
+    assert_eq!("3".parse::<DynVal>().unwrap(), DynVal::new_fixed(3));
+    assert_eq!("50%".parse::<DynVal>().unwrap(), DynVal::new_flex(0.5));
+    let v: DynVal = "100% - 2".parse().unwrap();
+    assert_eq!((v.flex, v.fixed), (1.0, -2));
+    let v: DynVal = "-1+25%".parse().unwrap();
+    assert_eq!((v.flex, v.fixed), (0.25, -1));
+    assert!("".parse::<DynVal>().is_err());
+    assert!("abc".parse::<DynVal>().is_err());
+}
+
+#[test]
+fn build_layout() {
+    let (mut tui, ctx) = Tui::new_headless(20, 6).unwrap();
+    let layout = LayoutNode::from_json(LAYOUT).unwrap().build(&ctx).unwrap();
+    let mut names = layout.named.keys().cloned().collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, vec!["go", "tabs", "title"]);
+    let Some(LayoutElement::Button(go)) = layout.get("go") else {
+        panic!("go is not a button");
+    };
+    assert_eq!(go.get_state_name(), Some("go".to_string()));
+
+    let main = ParentPane::new(&ctx, "main");
+    main.add_element(layout.root.element());
+    tui.start_headless(Box::new(main)).unwrap();
+    let screen = tui.headless_screen().unwrap();
+    assert_eq!(screen.find("TITLE"), Some((8, 0)));
+    assert!(screen.find("GO").is_some());
+}
+
+#[test]
+fn invalid_layouts() {
+    let (_, ctx) = Tui::new_headless(20, 6).unwrap();
+    let dup = r#"{ "kind": "pane", "children": [
+        { "kind": "checkbox", "name": "a" }, { "kind": "checkbox", "name": "a" } ] }"#;
+    assert!(LayoutNode::from_json(dup).unwrap().build(&ctx).is_err());
+    let bad_val = r#"{ "kind": "checkbox", "x": "left" }"#;
+    assert!(LayoutNode::from_json(bad_val).unwrap().build(&ctx).is_err());
+    let bad_color = r#"{ "kind": "pane", "bg": "not a color" }"#;
+    assert!(LayoutNode::from_json(bad_color)
+        .unwrap()
+        .build(&ctx)
+        .is_err());
+    assert!(LayoutNode::from_json(r#"{ "kind": "unknown" }"#).is_err());
+}
+
+#[test]
+fn widget_colors() {
+    let (_, ctx) = Tui::new_headless(20, 6).unwrap();
+    let node = r#"{ "kind": "pane", "children": [
+        { "kind": "label", "name": "l", "text": "l", "fg": "red" },
+        { "kind": "checkbox", "name": "c", "fg": "red", "bg": "navy" } ] }"#;
+    let layout = LayoutNode::from_json(node).unwrap().build(&ctx).unwrap();
+    let Some(LayoutElement::Label(l)) = layout.get("l") else {
+        panic!("l is not a label");
+    };
+    assert_eq!(l.pane.get_style().fg.map(|fg| fg.0), Some(Color::RED));
+    let Some(LayoutElement::Checkbox(c)) = layout.get("c") else {
+        panic!("c is not a checkbox");
+    };
+    let styles = c.pane.styles.borrow().clone();
+    assert_eq!(styles.ready_style.bg.map(|bg| bg.0), Some(Color::NAVY));
+    assert_eq!(styles.selected_style.fg.map(|fg| fg.0), Some(Color::RED));
+    assert_ne!(styles.selected_style.bg.map(|bg| bg.0), Some(Color::NAVY));
+}
+
+#[test]
+fn hot_reload() {
+    let path = std::env::temp_dir().join(format!("yeehaw_layout_{}.json", std::process::id()));
+    std::fs::write(&path, LAYOUT).unwrap();
+
+    let (mut tui, ctx) = Tui::new_headless(20, 6).unwrap();
+    let attached = Rc::new(RefCell::new(Vec::new()));
+    let attached_ = attached.clone();
+    let lp = LayoutPane::new(&ctx, &path)
+        .unwrap()
+        .with_attach_fn(
+            &ctx,
+            Box::new(move |_, layout| {
+                let Some(LayoutElement::Label(l)) = layout.get("title") else {
+                    return;
+                };
+                attached_.borrow_mut().push(l.get_text());
+            }),
+        )
+        .with_hot_reload(Duration::from_millis(100));
+    tui.start_headless(Box::new(lp.clone())).unwrap();
+    let Some(LayoutElement::Tabs(tabs)) = lp.get_layout().get("tabs").cloned() else {
+        panic!("tabs are not tabs");
+    };
+    tabs.select(1);
+
+    // unchanged files are not reloaded
+    tui.advance_time(Duration::from_millis(150)).unwrap();
+    assert_eq!(*attached.borrow(), vec!["TITLE"]);
+
+    let file = std::fs::File::options().write(true).open(&path).unwrap();
+    std::fs::write(&path, LAYOUT.replace("TITLE", "NEW")).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(10))
+        .unwrap();
+    tui.advance_time(Duration::from_millis(100)).unwrap();
+    assert_eq!(*attached.borrow(), vec!["TITLE", "NEW"]);
+    assert!(tui.headless_screen().unwrap().find("NEW").is_some());
+
+    // the state of the named elements is carried over
+    let Some(LayoutElement::Tabs(tabs)) = lp.get_layout().get("tabs").cloned() else {
+        panic!("tabs are not tabs");
+    };
+    assert_eq!(*tabs.tabs_top.selected.borrow(), Some(1));
+
+    // an invalid file keeps the current layout
+    std::fs::write(&path, "{").unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(20))
+        .unwrap();
+    tui.advance_time(Duration::from_millis(100)).unwrap();
+    assert!(tui.headless_screen().unwrap().find("NEW").is_some());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,161 @@
+use {
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    std::time::Duration,
+    yeehaw::*,
+};
+
+// NOTE the logger is global and tests run concurrently, each test filters for its own records
+
+fn setup(filter: &str, height: u16) -> (Tui, LogPane) {
+    let (mut tui, ctx) = Tui::new_headless(50, height).unwrap();
+    let lp = LogPane::new(&ctx).with_filter(filter);
+    lp.set_focused(true);
+    tui.start_headless(Box::new(lp.clone())).unwrap();
+    (tui, lp)
+}
+
+fn key(tui: &mut Tui, key: KeyEvent) {
+    tui.process_event_key(key).unwrap();
+    tui.render().unwrap();
+}
+
+fn typed(tui: &mut Tui, s: &str) {
+    for c in s.chars() {
+        key(tui, KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+}
+
+#[test]
+fn records_displayed_live() {
+    let (mut tui, _) = setup("lp1-", 6);
+    log::log_record(LogLevel::Warn, "app", "lp1-warned".to_string());
+    debug!("lp1-debugged");
+
+    // the tui is woken to draw the records
+    assert_eq!(tui.ev_recv.try_recv().ok(), Some(Event::Redraw));
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+    let scr = tui.headless_screen().unwrap();
+    assert_eq!(scr.row_text(0).trim_end(), "WARN  app: lp1-warned");
+    assert_eq!(scr.row_text(1).trim_end(), "DEBUG lp1-debugged");
+    assert_ne!(scr.style_at(0, 0), scr.style_at(0, 1));
+    assert!(scr.row_text(5).contains("following"));
+}
+
+#[test]
+fn redraws_coalesced_until_exit() {
+    let (mut tui, lp) = setup("lp6-", 6);
+    for i in 0..3 {
+        debug!("lp6-{i}");
+    }
+    // a single redraw is requested until the pane is drawn
+    assert_eq!(tui.ev_recv.try_recv().ok(), Some(Event::Redraw));
+    assert!(tui.ev_recv.try_recv().is_err());
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+    debug!("lp6-3");
+    assert_eq!(tui.ev_recv.try_recv().ok(), Some(Event::Redraw));
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+
+    let ctx = tui.context();
+    let _ = lp.receive_event(&ctx, Event::Exit);
+    // drop any redraw requested by the records of the other tests
+    while tui.ev_recv.try_recv().is_ok() {}
+    debug!("lp6-4");
+    assert!(tui.ev_recv.try_recv().is_err());
+}
+
+#[test]
+fn filter_by_level_and_text() {
+    let (mut tui, lp) = setup("lp2-", 6);
+    log::log_record(LogLevel::Info, "", "lp2-a".to_string());
+    log::log_record(LogLevel::Debug, "", "lp2-b".to_string());
+    log::log_record(LogLevel::Trace, "", "lp2-c".to_string());
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+    assert!(tui.headless_screen().unwrap().contains("lp2-c"));
+
+    // cycles from trace around to error then warn, info and debug
+    for _ in 0..4 {
+        key(&mut tui, Keyboard::KEY_L);
+    }
+    assert_eq!(*lp.max_level.borrow(), LogLevel::Debug);
+    let scr = tui.headless_screen().unwrap();
+    assert!(scr.contains("lp2-b"));
+    assert!(!scr.contains("lp2-c"));
+
+    // the typed filter replaces the existing one
+    key(&mut tui, Keyboard::KEY_SLASH);
+    key(&mut tui, Keyboard::KEY_ESC);
+    assert_eq!(*lp.filter.borrow(), "");
+    key(&mut tui, Keyboard::KEY_SLASH);
+    typed(&mut tui, "LP2-a");
+    key(&mut tui, Keyboard::KEY_ENTER);
+    assert_eq!(*lp.filter.borrow(), "LP2-a");
+    assert!(!*lp.editing_filter.borrow());
+    let scr = tui.headless_screen().unwrap();
+    assert!(scr.contains("lp2-a"));
+    assert!(!scr.contains("lp2-b"));
+}
+
+#[test]
+fn follow_and_pause() {
+    // three lines of records and the status line
+    let (mut tui, lp) = setup("lp3-", 4);
+    for i in 0..10 {
+        log::log_record(LogLevel::Info, "", format!("lp3-{i}"));
+    }
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+    let scr = tui.headless_screen().unwrap();
+    assert!(scr.row_text(0).contains("lp3-7"));
+    assert!(scr.row_text(2).contains("lp3-9"));
+
+    key(&mut tui, Keyboard::KEY_UP);
+    assert!(!lp.is_following());
+    assert!(tui.headless_screen().unwrap().row_text(0).contains("lp3-6"));
+
+    // the view remains in place while paused
+    log::log_record(LogLevel::Info, "", "lp3-10".to_string());
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+    let scr = tui.headless_screen().unwrap();
+    assert!(scr.row_text(0).contains("lp3-6"));
+    assert!(scr.row_text(3).contains("paused"));
+
+    key(&mut tui, Keyboard::KEY_HOME);
+    assert!(tui.headless_screen().unwrap().row_text(0).contains("lp3-0"));
+    key(&mut tui, Keyboard::KEY_END);
+    assert!(lp.is_following());
+    assert!(tui
+        .headless_screen()
+        .unwrap()
+        .row_text(2)
+        .contains("lp3-10"));
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn log_facade() {
+    log::init_log_facade(LogLevel::Info).unwrap();
+    assert!(log::init_log_facade(LogLevel::Info).is_err());
+    ::log::info!(target: "lp4", "lp4-info");
+    ::log::debug!(target: "lp4", "lp4-debug");
+    let records: Vec<_> = log::records()
+        .into_iter()
+        .filter(|r| r.target == "lp4")
+        .collect();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].level, LogLevel::Info);
+    assert_eq!(records[0].message, "lp4-info");
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_subscriber() {
+    log::init_tracing(LogLevel::Debug).unwrap();
+    tracing::debug!(target: "lp5", answer = 42, "lp5-debug");
+    tracing::trace!(target: "lp5", "lp5-trace");
+    let records: Vec<_> = log::records()
+        .into_iter()
+        .filter(|r| r.target == "lp5")
+        .collect();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].level, LogLevel::Debug);
+    assert_eq!(records[0].message, "lp5-debug answer=42");
+}
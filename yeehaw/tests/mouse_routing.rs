@@ -0,0 +1,81 @@
+use {
+    crossterm::event::{KeyModifiers, MouseEvent as CTMouseEvent, MouseEventKind},
+    yeehaw::*,
+};
+
+fn mouse(tui: &mut Tui, kind: MouseEventKind, column: u16, row: u16) {
+    let ev = CTMouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    };
+    tui.process_event_mouse(ev).unwrap();
+}
+
+#[test]
+fn mouse_possibility_matches() {
+    use crossterm::event::MouseButton;
+    let left = MousePossibility::Click(Some(MouseButton::Left));
+    assert!(left.matches(&MouseEventKind::Down(MouseButton::Left)));
+    assert!(left.matches(&MouseEventKind::Up(MouseButton::Left)));
+    assert!(!left.matches(&MouseEventKind::Down(MouseButton::Right)));
+    assert!(!left.matches(&MouseEventKind::Drag(MouseButton::Left)));
+    assert!(MousePossibility::Click(None).matches(&MouseEventKind::Down(MouseButton::Right)));
+    assert!(MousePossibility::Scroll.matches(&MouseEventKind::ScrollLeft));
+    assert!(!MousePossibility::Scroll.matches(&MouseEventKind::Moved));
+}
+
+#[test]
+fn undeclared_scroll_passes_to_element_underneath() {
+    let (mut tui, ctx) = Tui::new_headless(10, 5).unwrap();
+    let outer = PaneScrollable::new(&ctx, 10, 20);
+    let inner = PaneScrollable::new(&ctx, 10, 20)
+        .with_dyn_height(DynVal::new_fixed(3))
+        .with_dyn_width(DynVal::FULL);
+    outer.add_element(Box::new(inner.clone()));
+    tui.start_headless(Box::new(outer.clone())).unwrap();
+
+    // by default the inner scrollable pane captures the scroll
+    mouse(&mut tui, MouseEventKind::ScrollDown, 1, 1);
+    assert_eq!(inner.get_content_y_offset(), 3);
+    assert_eq!(outer.get_content_y_offset(), 0);
+
+    // once the inner pane only declares clicks the scroll goes to the outer pane
+    *inner.pane.pane.rec_evs_always.borrow_mut() = MousePossibility::all_but_scroll().into();
+    mouse(&mut tui, MouseEventKind::ScrollDown, 1, 1);
+    assert_eq!(inner.get_content_y_offset(), 3);
+    assert_eq!(outer.get_content_y_offset(), 3);
+}
+
+#[cfg(feature = "textbox")]
+#[test]
+fn scroll_over_textbox_goes_to_scroll_pane() {
+    let (mut tui, ctx) = Tui::new_headless(10, 5).unwrap();
+    let sc = PaneScrollable::new(&ctx, 10, 20);
+    // the text fits within the textbox
+    let tb = TextBox::new(&ctx, "a\nb")
+        .with_dyn_height(DynVal::new_fixed(3))
+        .with_dyn_width(DynVal::FULL);
+    sc.add_element(Box::new(tb.clone()));
+    tui.start_headless(Box::new(sc.clone())).unwrap();
+
+    mouse(&mut tui, MouseEventKind::ScrollDown, 1, 1);
+    assert_eq!(sc.get_content_y_offset(), 3);
+}
+
+#[cfg(feature = "textbox")]
+#[test]
+fn overflowing_textbox_keeps_scroll() {
+    let (mut tui, ctx) = Tui::new_headless(10, 5).unwrap();
+    let sc = PaneScrollable::new(&ctx, 10, 20);
+    let tb = TextBox::new(&ctx, "a\nb\nc\nd\ne\nf")
+        .with_dyn_height(DynVal::new_fixed(3))
+        .with_dyn_width(DynVal::FULL);
+    sc.add_element(Box::new(tb.clone()));
+    tui.start_headless(Box::new(sc.clone())).unwrap();
+    assert!(tb.is_overflowing());
+
+    mouse(&mut tui, MouseEventKind::ScrollDown, 1, 1);
+    assert_eq!(sc.get_content_y_offset(), 0);
+}
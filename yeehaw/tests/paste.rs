@@ -0,0 +1,73 @@
+mod common;
+
+use {
+    common::{headless_with, Recorder},
+    yeehaw::*,
+};
+
+#[test]
+fn unreceived_paste_is_dropped() {
+    let (mut tui, _, rec) = headless_with(10, 3, |ctx, main| {
+        // the recorder receives all keys but not pastes
+        let rec = Recorder::new(ctx, vec![KeyPossibility::Anything.into()].into());
+        main.add_element(Box::new(rec.clone()));
+        rec
+    });
+
+    // the paste is never replayed as keys (a newline must not become an enter)
+    tui.process_event_paste("a\r\nb".to_string()).unwrap();
+    assert!(rec.got.borrow().is_empty());
+}
+
+#[cfg(feature = "textbox")]
+#[test]
+fn paste_into_textbox() {
+    let (mut tui, _, tb) = headless_with(20, 5, |ctx, main| {
+        let tb = TextBox::new(ctx, "xy");
+        main.add_element(Box::new(tb.clone()));
+        tb
+    });
+    tb.pane.set_selectability(Selectability::Selected, false);
+    tb.set_cursor_pos(1);
+
+    tui.process_event_paste("a\nb".to_string()).unwrap();
+    assert_eq!(tb.get_text(), "xa\nby");
+    assert_eq!(tb.inner.borrow().get_cursor_pos(), 4);
+
+    // the whole paste is undone at once
+    tui.process_event_key(Keyboard::KEY_CTRL_Z).unwrap();
+    assert_eq!(tb.get_text(), "xy");
+}
+
+#[cfg(feature = "textbox")]
+#[test]
+fn paste_into_single_line_textbox() {
+    let (mut tui, _, tb) = headless_with(20, 5, |ctx, main| {
+        let tb = SingleLineTextBox::new(ctx);
+        main.add_element(Box::new(tb.clone()));
+        tb
+    });
+    tb.tb.pane.set_selectability(Selectability::Selected, false);
+
+    tui.process_event_paste("one\r\ntwo".to_string()).unwrap();
+    assert_eq!(tb.tb.get_text(), "onetwo");
+}
+
+#[cfg(feature = "textbox")]
+#[test]
+fn paste_into_numbers_textbox() {
+    let (mut tui, _, ntb) = headless_with(20, 5, |ctx, main| {
+        let ntb = NumbersTextBox::new(ctx, 5i32);
+        main.add_element(Box::new(ntb.clone()));
+        ntb
+    });
+    ntb.tb
+        .pane
+        .set_selectability(Selectability::Selected, false);
+    ntb.tb.set_cursor_pos(1);
+
+    tui.process_event_paste("abc".to_string()).unwrap();
+    assert_eq!(ntb.tb.get_text(), "5");
+    tui.process_event_paste(" 12\n".to_string()).unwrap();
+    assert_eq!(ntb.tb.get_text(), "512");
+}
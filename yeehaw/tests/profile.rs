@@ -0,0 +1,146 @@
+mod common;
+
+use {common::headless_with, std::time::Duration, yeehaw::*};
+
+/// an element which is slow to draw
+#[derive(Clone)]
+struct Slow {
+    pane: Pane,
+}
+
+#[yeehaw_derive::impl_element_from(pane)]
+impl Element for Slow {
+    fn drawing(&self, ctx: &Context, dr: &DrawRegion, force_update: bool) -> Vec<DrawUpdate> {
+        std::thread::sleep(Duration::from_millis(5));
+        self.pane.drawing(ctx, dr, force_update)
+    }
+}
+
+fn key(tui: &mut Tui, key: crossterm::event::KeyEvent) {
+    tui.process_event_key(key).unwrap();
+    tui.advance_time(Duration::ZERO).unwrap();
+}
+
+fn setup() -> (Tui, Context, Pane, ProfileOverlay) {
+    let (tui, ctx, (editor, po)) = headless_with(80, 20, |ctx, main| {
+        let editor = Pane::new(ctx, "editor")
+            .with_focused(true)
+            .with_focused_receivable_events(vec![Keyboard::KEY_CTRL_S.into()].into());
+        main.add_element(Box::new(editor.clone()));
+        let po = ProfileOverlay::new(ctx);
+        main.add_element(Box::new(po.clone()));
+        (editor, po)
+    });
+    (tui, ctx, editor, po)
+}
+
+#[test]
+fn nothing_collected_unless_enabled() {
+    let (mut tui, ctx, _, _) = setup();
+    key(&mut tui, Keyboard::KEY_CTRL_S);
+    assert!(!ctx.profiler.is_enabled());
+    assert_eq!(ctx.profiler.frames().count, 0);
+    assert!(ctx.profiler.elements().is_empty());
+}
+
+#[test]
+fn profiling_collects_frames_and_elements() {
+    let (mut tui, ctx, editor, _) = setup();
+    tui.set_profiling(true);
+    key(&mut tui, Keyboard::KEY_CTRL_S);
+
+    let frames = ctx.profiler.frames();
+    assert_eq!(frames.count, 1);
+    assert!(frames.last.tree_drawn);
+    assert!(frames.last.draw_updates > 0);
+
+    let elements = ctx.profiler.elements();
+    let (_, p) = elements
+        .iter()
+        .find(|(id, _)| *id == editor.id())
+        .expect("editor profiled");
+    assert_eq!(p.kind, "editor");
+    assert_eq!((p.draws, p.events), (1, 1));
+    // only the inclusive times of the parent include those of the editor
+    let (_, main) = elements.iter().find(|(_, p)| p.kind == "main").unwrap();
+    assert!(main.draw_time_inclusive >= main.draw_time + p.draw_time_inclusive);
+
+    assert!(ctx.profiler.report().contains(&editor.id()));
+    ctx.profiler.reset();
+    assert_eq!(ctx.profiler.frames().count, 0);
+    assert!(ctx.profiler.elements().is_empty());
+}
+
+#[test]
+fn overlay_toggles_profiling_display() {
+    let (mut tui, ctx, _, po) = setup();
+    assert!(!tui.headless_screen().unwrap().contains("frames:"));
+
+    key(&mut tui, ProfileOverlay::DEFAULT_TRIGGER);
+    assert!(po.is_open());
+    assert!(ctx.profiler.is_enabled());
+    // the profile displayed is that of the previous renders
+    tui.render().unwrap();
+    let screen = tui.headless_screen().unwrap();
+    assert!(screen.contains("frames: 1"));
+    assert!(screen.contains("editor"));
+
+    key(&mut tui, ProfileOverlay::DEFAULT_TRIGGER);
+    assert!(!po.is_open());
+    assert!(!tui.headless_screen().unwrap().contains("frames:"));
+}
+
+#[test]
+fn ranked_by_self_time() {
+    let (mut tui, ctx, (inner, slow)) = headless_with(80, 20, |ctx, main| {
+        let inner = ParentPane::new(ctx, "inner");
+        let slow = Slow {
+            pane: Pane::new(ctx, "slow"),
+        };
+        inner.add_element(Box::new(slow.clone()));
+        main.add_element(Box::new(inner.clone()));
+        (inner, slow)
+    });
+    tui.set_profiling(true);
+    tui.render().unwrap();
+
+    // the parents spend little time drawing themselves
+    let elements = ctx.profiler.elements();
+    assert_eq!(elements[0].0, slow.id());
+    assert!(elements[0].1.draw_time >= Duration::from_millis(5));
+    let (_, main) = elements.iter().find(|(_, p)| p.kind == "main").unwrap();
+    assert!(main.draw_time < Duration::from_millis(5));
+    assert!(main.draw_time_inclusive >= Duration::from_millis(5));
+
+    // removed elements are no longer profiled
+    inner.remove_element(&slow.id());
+    tui.render().unwrap();
+    assert!(ctx
+        .profiler
+        .elements()
+        .iter()
+        .all(|(id, _)| *id != slow.id()));
+}
+
+#[test]
+fn overlay_refreshed_while_open() {
+    let (mut tui, ctx, _, po) = setup();
+    key(&mut tui, ProfileOverlay::DEFAULT_TRIGGER);
+    let handle = po
+        .refresh_handle
+        .borrow()
+        .clone()
+        .expect("refresh scheduled");
+    assert!(!handle.is_cancelled());
+
+    // the refresh has the tui render the latest profile without any input
+    assert!(!tui.needs_render());
+    let ev = Event::Custom(ProfileOverlay::EV_REFRESH.to_string(), vec![]);
+    let _ = po.receive_event(&ctx, ev);
+    assert!(tui.needs_render());
+
+    // the refresh stops once closed
+    key(&mut tui, ProfileOverlay::DEFAULT_TRIGGER);
+    assert!(handle.is_cancelled());
+    assert!(po.refresh_handle.borrow().is_none());
+}
@@ -0,0 +1,55 @@
+use yeehaw::*;
+
+const TEXT: &str = "The quick brown fox, wide 漢字 chars, ── box ── lines and ⣿ braille \
+    sitting side by side.";
+
+/// render a screen of text over a background and return the tui along with the bytes written
+fn render(batch: bool, sync: bool) -> (Tui, Pane, usize) {
+    let (tui, ctx) = Tui::new_headless(80, 24).unwrap();
+    let mut tui = tui.with_batch_output(batch).with_synchronized_output(sync);
+    let main = ParentPane::new(&ctx, "main");
+    let pane = Pane::new(&ctx, "text").with_bg(Color::BLUE);
+    pane.set_content_from_string(vec![TEXT; 24].join("\n"));
+    main.add_element(Box::new(pane.clone()));
+    main.add_element(Box::new(Label::new(&ctx, "status: ok").at(2, 22)));
+    tui.start_headless(Box::new(main)).unwrap();
+    let bytes = tui.headless_screen().unwrap().bytes_written();
+    (tui, pane, bytes)
+}
+
+#[test]
+fn batched_output_is_identical_and_smaller() {
+    let (plain_tui, plain_pane, plain) = render(false, false);
+    let (batch_tui, batch_pane, batched) = render(true, false);
+    assert_eq!(
+        plain_tui.headless_screen().unwrap().snapshot(),
+        batch_tui.headless_screen().unwrap().snapshot()
+    );
+    assert!(batch_tui.headless_screen().unwrap().contains("漢"));
+    // measured: 55320 bytes per cell versus 4765 bytes batched
+    assert!(batched * 10 < plain);
+
+    // a partial redraw of a single changed line
+    let mut results = Vec::new();
+    for (mut tui, pane) in [(plain_tui, plain_pane), (batch_tui, batch_pane)] {
+        tui.headless.as_mut().unwrap().reset_bytes_written();
+        let mut lines = vec![TEXT; 24];
+        lines[3] = "a single changed line";
+        pane.set_content_from_string(lines.join("\n"));
+        tui.render().unwrap();
+        let scr = tui.headless_screen().unwrap();
+        assert!(scr.row_text(3).starts_with("a single changed line"));
+        results.push((scr.snapshot(), scr.bytes_written()));
+    }
+    assert_eq!(results[0].0, results[1].0);
+    // measured: 1895 bytes per cell versus 418 bytes batched
+    assert!(results[1].1 * 4 < results[0].1);
+}
+
+#[test]
+fn synchronized_update_bytes() {
+    let (_, _, plain) = render(true, false);
+    let (_, _, synced) = render(true, true);
+    // "\x1b[?2026h" and "\x1b[?2026l"
+    assert_eq!(synced, plain + 16);
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use {
+    common::headless_with,
+    crossterm::event::{KeyModifiers, MouseButton, MouseEvent as CTMouseEvent, MouseEventKind},
+    std::time::Duration,
+    yeehaw::*,
+};
+
+fn click(tui: &mut Tui, column: u16, row: u16) {
+    for kind in [
+        MouseEventKind::Down(MouseButton::Left),
+        MouseEventKind::Up(MouseButton::Left),
+    ] {
+        let ev = CTMouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        tui.process_event_mouse(ev).unwrap();
+    }
+}
+
+#[test]
+fn button_enter_flash() {
+    let (mut tui, _, button) = headless_with(30, 6, |ctx, main| {
+        let button = Button::new(ctx, "Press").at(2, 1);
+        main.add_element(Box::new(button.clone()));
+        button
+    });
+
+    let (x, y) = tui.headless_screen().unwrap().find("Press").unwrap();
+    click(&mut tui, x as u16, y as u16); // select the button
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+    let start = tui.headless_screen().unwrap().text();
+
+    tui.process_event_key(Keyboard::KEY_ENTER).unwrap();
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+    assert!(*button.clicked_down.borrow());
+    assert_ne!(tui.headless_screen().unwrap().text(), start);
+
+    tui.advance_time(Duration::from_millis(150)).unwrap();
+    assert!(!*button.clicked_down.borrow());
+    assert_eq!(tui.headless_screen().unwrap().text(), start);
+}
+
+#[test]
+fn scheduled_event_cancelled_on_exit() {
+    let (mut tui, ctx, button) = headless_with(30, 6, |ctx, main| {
+        let button = Button::new(ctx, "Press").at(2, 1);
+        main.add_element(Box::new(button.clone()));
+        button
+    });
+
+    let (x, y) = tui.headless_screen().unwrap().find("Press").unwrap();
+    click(&mut tui, x as u16, y as u16);
+    tui.process_event_key(Keyboard::KEY_ENTER).unwrap();
+    let handle = button.flash_handle.borrow().clone().unwrap();
+    assert!(!handle.is_cancelled());
+
+    let _ = button.receive_event(&ctx, Event::Exit);
+    assert!(handle.is_cancelled());
+    assert!(button.flash_handle.borrow().is_none());
+
+    // the button is left in the tree so a delivered flash release would be observable
+    tui.advance_time(Duration::from_millis(200)).unwrap();
+    assert!(*button.clicked_down.borrow());
+    assert!(handle.is_cancelled());
+    assert_eq!(tui.next_wake(), None);
+}
@@ -0,0 +1,131 @@
+use yeehaw::*;
+
+/// the named elements of the session
+struct App {
+    main: ParentPane,
+    stack: VerticalStack,
+    tabs: Tabs,
+    scroll: PaneScrollable,
+    #[cfg(feature = "textbox")]
+    tb: TextBox,
+}
+
+impl App {
+    fn new(ctx: &Context) -> Self {
+        let main = ParentPane::new(ctx, "main");
+
+        let stack = VerticalStack::new(ctx).with_state_name("stack");
+        stack.push(Box::new(Pane::new(ctx, "top")));
+        stack.push(Box::new(Pane::new(ctx, "bottom")));
+
+        let tabs = Tabs::new(ctx).with_state_name("tabs");
+        tabs.push(Box::new(Pane::new(ctx, "one")), "one");
+        tabs.push(Box::new(Pane::new(ctx, "two")), "two");
+
+        let scroll = PaneScrollable::new(ctx, 100, 100).with_state_name("scroll");
+        scroll
+            .pane
+            .set_dyn_location(DynLocation::new_fixed(0, 10, 0, 10));
+
+        // nested within an unnamed element
+        let inner = ParentPane::new(ctx, "inner");
+        inner.add_element(Box::new(stack.clone()));
+        main.add_element(Box::new(inner));
+        main.add_element(Box::new(tabs.clone()));
+        main.add_element(Box::new(scroll.clone()));
+
+        #[cfg(feature = "textbox")]
+        let tb = TextBox::new(ctx, "").with_state_name("tb");
+        #[cfg(feature = "textbox")]
+        main.add_element(Box::new(tb.clone()));
+
+        App {
+            main,
+            stack,
+            tabs,
+            scroll,
+            #[cfg(feature = "textbox")]
+            tb,
+        }
+    }
+}
+
+fn start(session_file: Option<&std::path::Path>) -> (Tui, App) {
+    let (tui, ctx) = Tui::new_headless(20, 20).unwrap();
+    let mut tui = match session_file {
+        Some(path) => tui.with_session_file(path),
+        None => tui,
+    };
+    let app = App::new(&ctx);
+    tui.start_headless(Box::new(app.main.clone())).unwrap();
+    (tui, app)
+}
+
+fn modify(app: &App) {
+    app.stack.set_height_ratios(&[0.25, 0.75]);
+    app.tabs.select(1);
+    let dr = DrawRegion::default().with_size(Size::new(10, 10));
+    app.scroll.set_content_x_offset(Some(&dr), 3);
+    app.scroll.set_content_y_offset(Some(&dr), 7);
+    app.scroll
+        .set_dyn_location(DynLocation::new_fixed(2, 12, 1, 11));
+    #[cfg(feature = "textbox")]
+    app.tb.set_text("saved text".to_string());
+}
+
+fn assert_modified(app: &App) {
+    assert_eq!(app.stack.height_ratios(), vec![0.25, 0.75]);
+    assert_eq!(*app.tabs.tabs_top.selected.borrow(), Some(1));
+    assert_eq!(app.scroll.get_content_x_offset(), 3);
+    assert_eq!(app.scroll.get_content_y_offset(), 7);
+    assert_eq!(
+        app.scroll.get_dyn_location_set().l,
+        DynLocation::new_fixed(2, 12, 1, 11)
+    );
+    #[cfg(feature = "textbox")]
+    assert_eq!(app.tb.get_text(), "saved text");
+}
+
+#[test]
+fn save_and_restore_session() {
+    let (mut tui, app) = start(None);
+    modify(&app);
+    tui.render().unwrap();
+    let state = tui.save_session();
+    assert!(state.elements.contains_key("stack"));
+    assert!(!state.elements.contains_key("main"));
+
+    // through json as if written to a file
+    let state = SessionState::from_json(&state.to_json().unwrap()).unwrap();
+    let (mut tui, app) = start(None);
+    tui.restore_session(&state);
+    tui.render().unwrap();
+    assert_modified(&app);
+}
+
+#[test]
+fn mismatched_state_is_ignored() {
+    let (tui, app) = start(None);
+    let mut state = SessionState::default();
+    state.set("stack", SessionState::KEY_RATIOS, &vec![0.1, 0.2, 0.7]);
+    state.set("tabs", SessionState::KEY_SELECTED_TAB, &5);
+    state.set("scroll", SessionState::KEY_OFFSET, &"not an offset");
+    tui.restore_session(&state);
+    assert_eq!(app.stack.height_ratios(), vec![0.5, 0.5]);
+    assert_eq!(*app.tabs.tabs_top.selected.borrow(), Some(0));
+    assert_eq!(app.scroll.get_content_y_offset(), 0);
+}
+
+#[test]
+fn session_file() {
+    let path = std::env::temp_dir().join(format!("yeehaw_session_{}.json", std::process::id()));
+    let (mut tui, app) = start(Some(&path));
+    modify(&app);
+    tui.render().unwrap();
+    tui.save_session().save(&path).unwrap();
+
+    // restored as soon as the main element is added
+    let (_, app) = start(Some(&path));
+    std::fs::remove_file(&path).unwrap();
+    assert_modified(&app);
+}
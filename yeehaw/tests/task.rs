@@ -0,0 +1,94 @@
+mod common;
+
+use {
+    common::{headless_with, Recorder},
+    std::time::Duration,
+    yeehaw::*,
+};
+
+fn setup() -> (Tui, Context, ParentPane, Recorder) {
+    let (tui, ctx, (inner, rec)) = headless_with(10, 3, |ctx, main| {
+        let rec = Recorder::new(ctx, ReceivableEvents::default());
+        // nested to ensure the output is routed down the tree
+        let inner = ParentPane::new(ctx, "inner");
+        inner.add_element(Box::new(rec.clone()));
+        // menu items capture all the events they receive
+        let menu = MenuBar::top_menu_bar(ctx);
+        menu.add_item(ctx, "file/open".to_string(), None);
+        main.add_element(Box::new(menu));
+        main.add_element(Box::new(inner.clone()));
+        (inner, rec)
+    });
+    (tui, ctx, inner, rec)
+}
+
+/// let the spawned tasks run then process their outputs
+async fn settle(tui: &mut Tui) {
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    tui.advance_time(Duration::from_millis(10)).unwrap();
+}
+
+#[tokio::test]
+async fn output_delivered_to_element() {
+    let (mut tui, ctx, _, rec) = setup();
+    ctx.spawn(&rec.pane, async { 7u32 }).unwrap();
+    ctx.spawn(&rec.pane, async {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        8u32
+    })
+    .unwrap();
+    assert_eq!(ctx.tasks.running(&rec.id()), 2);
+    settle(&mut tui).await;
+    // the wrong type leaves the output in place
+    assert!(rec.task_outputs::<String>().is_empty());
+    assert_eq!(rec.task_outputs::<u32>(), vec![7, 8]);
+    assert_eq!(ctx.tasks.running(&rec.id()), 0);
+    // delivered tasks are no longer tracked
+    assert!(ctx.tasks.is_empty());
+}
+
+#[tokio::test]
+async fn cancelled_on_exit() {
+    let (mut tui, ctx, inner, rec) = setup();
+    ctx.spawn(&rec.pane, async {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        1u32
+    })
+    .unwrap();
+    let _ = inner.receive_event(&ctx, Event::Exit);
+    assert_eq!(ctx.tasks.running(&rec.id()), 0);
+    settle(&mut tui).await;
+    assert!(rec.task_outputs::<u32>().is_empty());
+}
+
+#[tokio::test]
+async fn cancelled_on_removal() {
+    let (mut tui, ctx, inner, rec) = setup();
+    ctx.spawn(&rec.pane, async {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        1u32
+    })
+    .unwrap();
+    inner.remove_element(&rec.id());
+    tui.render().unwrap();
+    assert_eq!(ctx.tasks.running(&rec.id()), 0);
+    settle(&mut tui).await;
+    assert!(rec.task_outputs::<u32>().is_empty());
+}
+
+#[tokio::test]
+async fn cancelled_after_completion() {
+    let (mut tui, ctx, _, rec) = setup();
+    let handle = ctx.spawn(&rec.pane, async { 1u32 }).unwrap();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    // completed but not yet delivered
+    handle.cancel();
+    tui.advance_time(Duration::from_millis(10)).unwrap();
+    assert!(rec.task_outputs::<u32>().is_empty());
+}
+
+#[test]
+fn spawn_requires_runtime() {
+    let (_, ctx, _, rec) = setup();
+    assert!(ctx.spawn(&rec.pane, async { 1u32 }).is_err());
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use {
+    common::{headless_with, Recorder},
+    yeehaw::*,
+};
+
+fn setup() -> (Tui, Context, Recorder) {
+    headless_with(10, 3, |ctx, main| {
+        // changes the title on key presses
+        let rec = Recorder::new(
+            ctx,
+            vec![Keyboard::KEY_P.into(), Keyboard::KEY_O.into()].into(),
+        )
+        .with_respond(Box::new(|ev| match ev {
+            Event::KeyCombo(keys) if keys[0] == Keyboard::KEY_P => {
+                let r = EventResponse::Title(TitleChange::Push("pushed".to_string()));
+                (true, r.into())
+            }
+            Event::KeyCombo(_) => (true, EventResponse::Title(TitleChange::Pop).into()),
+            _ => (false, EventResponses::default()),
+        }));
+        // nested to ensure focus events reach the whole tree
+        let inner = ParentPane::new(ctx, "inner");
+        inner.add_element(Box::new(rec.clone()));
+        main.add_element(Box::new(inner));
+        rec
+    })
+}
+
+#[test]
+fn focus_events_broadcast() {
+    let (mut tui, _, rec) = setup();
+    tui.process_event_focus(false).unwrap();
+    tui.process_event_focus(true).unwrap();
+    assert_eq!(
+        *rec.got.borrow(),
+        vec![Event::FocusLost, Event::FocusGained]
+    );
+}
+
+#[test]
+fn title_stack() {
+    let (mut tui, ctx, _) = setup();
+    assert_eq!(tui.title(), None);
+    ctx.terminal_title.set("app");
+    assert_eq!(tui.title(), Some("app".to_string()));
+
+    tui.process_event_key(Keyboard::KEY_P).unwrap();
+    assert_eq!(tui.title(), Some("pushed".to_string()));
+    tui.process_event_key(Keyboard::KEY_O).unwrap();
+    assert_eq!(tui.title(), Some("app".to_string()));
+    tui.pop_title();
+    assert_eq!(tui.title(), None);
+}
+
+#[test]
+fn title_escape_sequences() {
+    let title = TerminalTitle::default();
+    let mut be = CrosstermBackend::new(Vec::new(), Size::new(10, 3));
+
+    title.push("one");
+    title.push("two");
+    assert!(title.update_backend(&mut be).unwrap());
+    // the original title is saved once before the first change
+    assert_eq!(be.writer().as_slice(), b"\x1b[22;0t\x1b]0;two\x07");
+    assert!(!title.update_backend(&mut be).unwrap());
+
+    title.pop();
+    title.update_backend(&mut be).unwrap();
+    assert!(be.writer().ends_with(b"\x1b]0;one\x07"));
+
+    // restored on closedown
+    title.restore_backend(&mut be).unwrap();
+    assert!(be.writer().ends_with(b"\x1b[23;0t"));
+}
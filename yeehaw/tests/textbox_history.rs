@@ -0,0 +1,70 @@
+#![cfg(feature = "textbox")]
+
+mod common;
+
+use {common::headless_with, crossterm::event::KeyEvent, std::time::Duration, yeehaw::*};
+
+fn setup() -> (Tui, Context, TextBox) {
+    let (tui, ctx, tb) = headless_with(20, 5, |ctx, main| {
+        let tb = TextBox::new(ctx, "");
+        main.add_element(Box::new(tb.clone()));
+        tb
+    });
+    tb.pane.set_selectability(Selectability::Selected, false);
+    (tui, ctx, tb)
+}
+
+fn type_str(tui: &mut Tui, s: &str) {
+    for c in s.chars() {
+        let k = KeyEvent::new(
+            crossterm::event::KeyCode::Char(c),
+            crossterm::event::KeyModifiers::NONE,
+        );
+        tui.process_event_key(k).unwrap();
+    }
+}
+
+#[test]
+fn undo_groups_words() {
+    let (mut tui, _, tb) = setup();
+    type_str(&mut tui, "ab cd");
+    tui.advance_time(Duration::from_secs(2)).unwrap();
+    type_str(&mut tui, "e");
+    assert_eq!(tb.get_text(), "ab cde");
+
+    tui.process_event_key(Keyboard::KEY_CTRL_Z).unwrap();
+    assert_eq!(tb.get_text(), "ab cd");
+    tui.process_event_key(Keyboard::KEY_CTRL_Z).unwrap();
+    assert_eq!(tb.get_text(), "ab");
+    assert_eq!(tb.inner.borrow().get_cursor_pos(), 2);
+
+    tui.process_event_key(Keyboard::KEY_CTRL_SHIFT_Z).unwrap();
+    assert_eq!(tb.get_text(), "ab cd");
+    assert_eq!(tb.inner.borrow().get_cursor_pos(), 5);
+
+    // deletions are undone too
+    tui.process_event_key(Keyboard::KEY_BACKSPACE).unwrap();
+    tui.process_event_key(Keyboard::KEY_BACKSPACE).unwrap();
+    assert_eq!(tb.get_text(), "ab ");
+    tui.process_event_key(Keyboard::KEY_CTRL_Z).unwrap();
+    assert_eq!(tb.get_text(), "ab cd");
+    // the redo history was cleared by the deletion
+    tui.process_event_key(Keyboard::KEY_CTRL_Y).unwrap();
+    assert_eq!(tb.get_text(), "ab ");
+}
+
+#[test]
+fn set_text_and_history() {
+    let (mut tui, ctx, tb) = setup();
+    type_str(&mut tui, "abc");
+    tb.set_text("new".to_string());
+    tb.undo(&ctx);
+    assert_eq!(tb.get_text(), "new");
+
+    let tb = tb.with_undoable_set_text();
+    tb.set_text("newer".to_string());
+    tb.undo(&ctx);
+    assert_eq!(tb.get_text(), "new");
+    tb.redo(&ctx);
+    assert_eq!(tb.get_text(), "newer");
+}
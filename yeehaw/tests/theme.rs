@@ -0,0 +1,142 @@
+mod common;
+
+use {common::headless_with, crossterm::style::Color as CTColor, std::time::Duration, yeehaw::*};
+
+fn bg_at(tui: &Tui, text: &str) -> Option<CTColor> {
+    let screen = tui.headless_screen().unwrap();
+    let (x, y) = screen.find(text).unwrap();
+    screen.style_at(x, y).unwrap().background_color
+}
+
+fn day() -> ThemeEntries {
+    ThemeEntries::default().with_style(
+        "button.ready",
+        Style::new_const(Color::BLACK, Color::new(200, 200, 200)),
+    )
+}
+
+fn night() -> ThemeEntries {
+    ThemeEntries::default().with_style(
+        "button.ready",
+        Style::new_const(Color::WHITE, Color::new(20, 20, 60)),
+    )
+}
+
+#[test]
+fn theme_live_switch() {
+    let (mut tui, ctx, _) = headless_with(30, 6, |ctx, main| {
+        ctx.theme.set_entries(day());
+        main.add_element(Box::new(Button::new(ctx, "Themed").at(2, 1)));
+    });
+    assert_eq!(
+        bg_at(&tui, "Themed"),
+        Some(CTColor::Rgb {
+            r: 200,
+            g: 200,
+            b: 200
+        })
+    );
+
+    ctx.set_theme(night());
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+    assert_eq!(
+        bg_at(&tui, "Themed"),
+        Some(CTColor::Rgb {
+            r: 20,
+            g: 20,
+            b: 60
+        })
+    );
+}
+
+#[test]
+fn theme_subtree_override() {
+    let (mut tui, ctx, _) = headless_with(30, 6, |ctx, main| {
+        ctx.theme.set_entries(day());
+        let sub_ctx = ctx.clone().with_theme(ctx.theme.child().with_style(
+            "button.ready",
+            Style::new_const(Color::BLACK, Color::new(255, 0, 0)),
+        ));
+        main.add_element(Box::new(Button::new(ctx, "Outer").at(2, 1)));
+        main.add_element(Box::new(Button::new(&sub_ctx, "Inner").at(2, 3)));
+        let explicit = Button::new(ctx, "Explicit").at(14, 1);
+        explicit.pane.set_styles(SelStyles::transparent());
+        main.add_element(Box::new(explicit));
+    });
+
+    assert_eq!(
+        bg_at(&tui, "Outer"),
+        Some(CTColor::Rgb {
+            r: 200,
+            g: 200,
+            b: 200
+        })
+    );
+    assert_eq!(
+        bg_at(&tui, "Inner"),
+        Some(CTColor::Rgb { r: 255, g: 0, b: 0 })
+    );
+
+    // the subtree override survives a swap of the parent theme, explicit styles are untouched
+    let explicit_bg = bg_at(&tui, "Explicit");
+    ctx.set_theme(night());
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+    assert_eq!(
+        bg_at(&tui, "Outer"),
+        Some(CTColor::Rgb {
+            r: 20,
+            g: 20,
+            b: 60
+        })
+    );
+    assert_eq!(
+        bg_at(&tui, "Inner"),
+        Some(CTColor::Rgb { r: 255, g: 0, b: 0 })
+    );
+    assert_eq!(bg_at(&tui, "Explicit"), explicit_bg);
+}
+
+#[test]
+fn theme_live_switch_restyles_widgets() {
+    let sty =
+        |bg| ThemeEntries::default().with_style("scrollbar", Style::new_const(Color::WHITE, bg));
+    let (mut tui, ctx, _) = headless_with(30, 6, |ctx, main| {
+        ctx.theme.set_entries(sty(Color::new(200, 200, 200)));
+        let menu = MenuBar::top_menu_bar(ctx);
+        menu.add_item(ctx, "File".to_string(), None);
+        main.add_element(Box::new(menu));
+        let sb = VerticalScrollbar::new(ctx, DynVal::new_fixed(4), Size::new(1, 4), 10).at(20, 1);
+        main.add_element(Box::new(sb));
+    });
+    let sb_bg = |tui: &Tui| {
+        let screen = tui.headless_screen().unwrap();
+        let (x, y) = screen.find("▲").unwrap();
+        screen.style_at(x, y).unwrap().background_color
+    };
+    assert_eq!(
+        sb_bg(&tui),
+        Some(CTColor::Rgb {
+            r: 200,
+            g: 200,
+            b: 200
+        })
+    );
+
+    ctx.set_theme(sty(Color::new(20, 20, 60)).with_style(
+        "menu.unselected",
+        Style::new_const(Color::WHITE, Color::new(255, 0, 0)),
+    ));
+    tui.advance_time(Duration::from_millis(50)).unwrap();
+    assert_eq!(
+        sb_bg(&tui),
+        Some(CTColor::Rgb {
+            r: 20,
+            g: 20,
+            b: 60
+        })
+    );
+    assert_eq!(
+        bg_at(&tui, "File"),
+        Some(CTColor::Rgb { r: 255, g: 0, b: 0 })
+    );
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use {
+    common::headless_with,
+    crossterm::event::{KeyModifiers, MouseEvent as CTMouseEvent, MouseEventKind},
+    std::time::Duration,
+    yeehaw::*,
+};
+
+fn mouse(tui: &mut Tui, kind: MouseEventKind, column: u16, row: u16) {
+    let ev = CTMouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    };
+    tui.process_event_mouse(ev).unwrap();
+}
+
+fn setup() -> Tui {
+    let (tui, _, _) = headless_with(20, 5, |ctx, main| {
+        let el = Pane::new(ctx, "el").with_dyn_location(DynLocation::new_fixed(0, 4, 3, 5));
+        el.set_attribute("tooltip", b"hello".to_vec());
+        main.add_element(Box::new(el));
+    });
+    tui.with_tooltip_delay(Duration::from_millis(500))
+}
+
+#[test]
+fn tooltip_after_resting() {
+    let mut tui = setup();
+
+    // no tooltip away from the element
+    mouse(&mut tui, MouseEventKind::Moved, 10, 1);
+    tui.advance_time(Duration::from_secs(1)).unwrap();
+    assert!(!tui.headless_screen().unwrap().contains("hello"));
+
+    mouse(&mut tui, MouseEventKind::Moved, 1, 4);
+    tui.advance_time(Duration::from_millis(300)).unwrap();
+    assert!(!tui.headless_screen().unwrap().contains("hello"));
+    tui.advance_time(Duration::from_millis(300)).unwrap();
+    let scr = tui.headless_screen().unwrap();
+    // there is no room below the element so the tooltip is placed above the mouse
+    assert_eq!(scr.find("hello"), Some((2, 3)));
+
+    // the next key removes it
+    tui.process_event_key(Keyboard::KEY_A).unwrap();
+    tui.advance_time(Duration::from_millis(10)).unwrap();
+    assert!(!tui.headless_screen().unwrap().contains("hello"));
+
+    // as does the next mouse event
+    mouse(&mut tui, MouseEventKind::Moved, 2, 4);
+    tui.advance_time(Duration::from_secs(1)).unwrap();
+    assert!(tui.headless_screen().unwrap().contains("hello"));
+    mouse(&mut tui, MouseEventKind::ScrollDown, 2, 4);
+    tui.advance_time(Duration::from_secs(1)).unwrap();
+    assert!(!tui.headless_screen().unwrap().contains("hello"));
+}
+
+#[test]
+fn tooltips_disabled() {
+    let mut tui = setup().with_tooltips(false);
+    mouse(&mut tui, MouseEventKind::Moved, 1, 4);
+    tui.advance_time(Duration::from_secs(1)).unwrap();
+    assert!(!tui.headless_screen().unwrap().contains("hello"));
+}
+
+#[test]
+fn tooltip_within_scrolled_pane() {
+    let (tui, ctx) = Tui::new_headless(20, 5).unwrap();
+    let mut tui = tui.with_tooltip_delay(Duration::from_millis(500));
+    let sc = PaneScrollable::new(&ctx, 20, 20);
+    let el = Pane::new(&ctx, "el").with_dyn_location(DynLocation::new_fixed(0, 4, 10, 11));
+    el.set_attribute("tooltip", b"hello".to_vec());
+    sc.add_element(Box::new(el));
+    tui.start_headless(Box::new(sc.clone())).unwrap();
+    sc.set_content_y_offset(Some(&tui.draw_region()), 9);
+    tui.render().unwrap();
+
+    // the element is drawn on the second row
+    mouse(&mut tui, MouseEventKind::Moved, 1, 1);
+    tui.advance_time(Duration::from_secs(1)).unwrap();
+    assert!(tui.headless_screen().unwrap().contains("hello"));
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use {common::headless_with, std::time::Duration, yeehaw::*};
+
+fn key(tui: &mut Tui, key: crossterm::event::KeyEvent) {
+    tui.process_event_key(key).unwrap();
+    tui.advance_time(Duration::ZERO).unwrap();
+}
+
+fn setup() -> (Tui, Pane, WhichKey) {
+    let (tui, _, (editor, wk)) = headless_with(40, 12, |ctx, main| {
+        let editor = Pane::new(ctx, "editor")
+            .with_focused(true)
+            .with_focused_receivable_events(
+                vec![Keyboard::KEY_CTRL_S.into(), Keyboard::KEY_CTRL_W.into()].into(),
+            );
+        editor.set_key_description(&[Keyboard::KEY_CTRL_S.into()], "save");
+        main.add_element(Box::new(editor.clone()));
+
+        let wk = WhichKey::new(ctx, main);
+        main.add_element(Box::new(wk.clone()));
+        (editor, wk)
+    });
+    (tui, editor, wk)
+}
+
+#[test]
+fn which_key_lists_focused_bindings() {
+    let (mut tui, editor, wk) = setup();
+    assert!(!wk.is_open());
+    assert!(!tui.headless_screen().unwrap().contains("key help"));
+
+    key(&mut tui, WhichKey::DEFAULT_TRIGGER);
+    assert!(wk.is_open());
+    let screen = tui.headless_screen().unwrap();
+    assert!(screen.contains("key help"));
+    assert!(screen.contains("editor"));
+    assert!(screen.contains("ctrl+s  save"));
+    assert!(screen.contains("ctrl+w"));
+    assert!(screen.contains("f1      toggle key help"));
+
+    key(&mut tui, Keyboard::KEY_ESC);
+    assert!(!wk.is_open());
+    assert!(!tui.headless_screen().unwrap().contains("key help"));
+
+    // unfocused elements are not listed
+    editor.set_focused(false);
+    key(&mut tui, WhichKey::DEFAULT_TRIGGER);
+    assert!(!tui.headless_screen().unwrap().contains("editor"));
+    key(&mut tui, WhichKey::DEFAULT_TRIGGER);
+    assert!(!wk.is_open());
+}
+
+#[test]
+fn key_bindings_are_grouped_by_element() {
+    let (_, _, wk) = setup();
+    let kbs = wk.target_key_bindings();
+    let kinds = kbs.iter().map(|k| k.kind.as_str()).collect::<Vec<_>>();
+    // the which key sits above the editor
+    assert_eq!(kinds, vec!["which_key", "editor"]);
+    assert_eq!(
+        kbs[1].bindings,
+        vec![
+            ("ctrl+s".to_string(), "save".to_string()),
+            ("ctrl+w".to_string(), String::new())
+        ]
+    );
+}
+
+#[test]
+fn help_is_clamped_to_the_screen() {
+    let (mut tui, _, wk) = headless_with(12, 4, |ctx, main| {
+        let wk = WhichKey::new(ctx, main);
+        main.add_element(Box::new(wk.clone()));
+        wk
+    });
+
+    key(&mut tui, WhichKey::DEFAULT_TRIGGER);
+    let loc = wk.get_dyn_location_set().l.clone();
+    let size = Size::new(12, 4);
+    assert_eq!(loc.get_start_x_from_size(size), 0);
+    assert_eq!(loc.get_start_y_from_size(size), 0);
+    assert_eq!(loc.end_x.get_val(size.width), 12);
+    assert_eq!(loc.end_y.get_val(size.height), 4);
+    // the top of the help is shown rather than its center
+    assert!(tui.headless_screen().unwrap().contains("key help"));
+}
@@ -13,6 +13,12 @@ pub fn impl_element_from(attr: TokenStream, item: TokenStream) -> TokenStream {
     fn id(&self) -> ElementID;
     fn can_receive(&self, ev: &Event) -> bool;
     fn receivable(&self) -> Vec<Rc<RefCell<ReceivableEvents>>>;
+    fn accepts_mouse(&self, ev: &Event) -> bool;
+    fn key_bindings(&self) -> Vec<KeyBindings>;
+    fn tooltip_at(&self, ev: &MouseEvent) -> Option<String>;
+    fn save_state(&self, state: &mut SessionState);
+    fn restore_state(&self, state: &SessionState);
+    fn inspect(&self, dr: &DrawRegion, x: i32, y: i32) -> InspectNode;
     fn receive_event(&self, ctx: &Context, ev: Event) -> (bool, EventResponses);
     fn set_focused(&self, focused: bool);
     fn get_focused(&self) -> bool;
@@ -125,6 +131,7 @@ pub fn impl_pane_basics_from(attr: TokenStream, item: TokenStream) -> TokenStrea
         fn with_default_ch(self, ch: DrawCh);
         fn with_transparent(self);
         fn with_style(self, style: Style);
+        fn with_style_from_theme(self, key: &str, default: Style);
         fn with_bg(self, bg: Color);
         fn with_fg(self, fg: Color);
         fn with_focused_receivable_events(self, evs: ReceivableEvents);
@@ -162,6 +169,9 @@ pub fn impl_pane_basics_from(attr: TokenStream, item: TokenStream) -> TokenStrea
     fn set_content(&self, content: DrawChs2D);
     fn get_content(&self) -> Ref<DrawChs2D>;
     fn get_content_mut(&self) -> RefMut<DrawChs2D>;
+    fn set_dirty(&self);
+    fn is_dirty(&self) -> bool;
+    fn set_update_dirty_content(&self, update_fn: UpdateDirtyContentFn);
     fn set_content_from_string<S: Into<String>>(&self, s: S);
     fn set_content_from_string_with_style(&self, dr: &DrawRegion, s: &str, sty: Style);
     fn set_content_style(&self, sty: Style);
@@ -174,6 +184,9 @@ pub fn impl_pane_basics_from(attr: TokenStream, item: TokenStream) -> TokenStrea
     fn scroll_right(&self, dr: Option<&DrawRegion>);
     fn set_style(&self, style: Style);
     fn get_style(&self) -> Style;
+    fn theme_styles(&self) -> ThemedStyles;
+    fn set_style_from_theme(&self, key: &str, default: Style);
+    fn refresh_theme(&self) -> bool;
     fn set_bg(&self, bg: Color);
     fn set_fg(&self, fg: Color);
     fn set_default_ch(&self, ch: DrawCh);
@@ -181,6 +194,7 @@ pub fn impl_pane_basics_from(attr: TokenStream, item: TokenStream) -> TokenStrea
     fn set_focused_receivable_events(&self, evs: ReceivableEvents);
     fn set_always_receivable_events(&self, evs: ReceivableEvents);
     fn send_responses_upward(&self, ctx: &Context, resps: EventResponses);
+    fn send_dirty_upward(&self);
     fn has_parent(&self) -> bool;
     fn set_focused(&self, focused: bool);
     fn correct_offsets_to_view_position(&self, dr: &DrawRegion, x: usize, y: usize);